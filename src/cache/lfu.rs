@@ -0,0 +1,282 @@
+use super::order::{OrderList, Slab};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_evicts_least_frequently_used() {
+        let mut cache = LfuCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.get("a");
+        cache.get("a");
+        cache.get("b");
+        cache.put("c", 3);
+        assert!(!cache.contains("b"));
+        assert_eq!(cache.frequency("a"), Some(3));
+        assert_eq!(cache.frequency("c"), Some(1));
+        // "c" is now the least frequently used
+        cache.put("d", 4);
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec!["a", "d"]);
+    }
+
+    #[test]
+    fn test_ties_break_by_recency() {
+        let mut cache = LfuCache::new(3);
+        cache.put(1, ());
+        cache.put(2, ());
+        cache.put(3, ());
+        cache.get(&1);
+        cache.get(&2);
+        cache.get(&3);
+        cache.get(&1);
+        // 2 and 3 both have frequency 2; 2 was used longer ago
+        cache.put(4, ());
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn test_peek_and_put_frequency() {
+        let mut cache = LfuCache::new(2);
+        cache.put("a", 1);
+        assert_eq!(cache.peek("a"), Some(&1));
+        assert_eq!(cache.frequency("a"), Some(1));
+        assert_eq!(cache.put("a", 2), Some(1));
+        assert_eq!(cache.frequency("a"), Some(2));
+        *cache.get_mut("a").unwrap() += 1;
+        assert_eq!(cache.peek("a"), Some(&3));
+        assert_eq!(cache.frequency("a"), Some(3));
+    }
+
+    #[test]
+    fn test_remove_keeps_min_frequency_consistent() {
+        let mut cache = LfuCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.get("b");
+        assert_eq!(cache.remove("a"), Some(1));
+        cache.put("c", 3);
+        cache.put("d", 4);
+        assert!(cache.contains("b") && cache.contains("d"));
+        assert_eq!(cache.len(), 2);
+        cache.clear();
+        assert_eq!(cache.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_eviction_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&evicted);
+        let mut cache = LfuCache::new(1).with_eviction_callback(move |k, v| log.borrow_mut().push((k, v)));
+        cache.put('x', 1);
+        cache.get(&'x');
+        cache.put('y', 2);
+        cache.put('z', 3);
+        assert_eq!(evicted.take(), vec![('x', 1), ('y', 2)]);
+    }
+}
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    frequency: u64,
+}
+
+/// A least-frequently-used cache holding at most `capacity` entries.
+///
+/// Entries sit in one recency list per use count, and the cache tracks the
+/// lowest count in use, so `get`, `put` and `remove` are O(1). Ties between
+/// equally used entries evict the least recently used one.
+pub struct LfuCache<K, V> {
+    map: HashMap<K, usize>,
+    slab: Slab<Entry<K, V>>,
+    buckets: HashMap<u64, OrderList>,
+    min_frequency: u64,
+    capacity: usize,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K: Hash + Eq + Clone, V> LfuCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        LfuCache {
+            map: HashMap::with_capacity(capacity),
+            slab: Slab::with_capacity(capacity),
+            buckets: HashMap::new(),
+            min_frequency: 0,
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    /// Calls `f` with every entry evicted to make room. Entries removed with
+    /// `remove` or replaced by `put` are not reported.
+    pub fn with_eviction_callback(mut self, f: impl FnMut(K, V) + 'static) -> Self {
+        self.on_evict = Some(Box::new(f));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns how many times `key` has been used: inserted, read through
+    /// `get`/`get_mut`, or overwritten.
+    pub fn frequency<Q>(&self, key: &Q) -> Option<u64>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key).map(|&index| self.slab.get(index).frequency)
+    }
+
+    /// Returns the value for `key`, counting a use.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Returns the value for `key` mutably, counting a use.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = *self.map.get(key)?;
+        self.touch(index);
+        Some(&mut self.slab.get_mut(index).value)
+    }
+
+    /// Returns the value for `key` without counting a use.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key).map(|&index| &self.slab.get(index).value)
+    }
+
+    /// Inserts or replaces the value for `key`, counting a use, and evicts
+    /// the least frequently used entry if the cache is full. Returns the
+    /// replaced value.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&index) = self.map.get(&key) {
+            self.touch(index);
+            return Some(std::mem::replace(&mut self.slab.get_mut(index).value, value));
+        }
+        if self.capacity == 0 {
+            self.evicted(key, value);
+            return None;
+        }
+        if self.map.len() == self.capacity {
+            self.evict();
+        }
+        let index = self.slab.insert(Entry { key: key.clone(), value, frequency: 1 });
+        self.buckets.entry(1).or_default().push_front(&mut self.slab, index);
+        self.map.insert(key, index);
+        self.min_frequency = 1;
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.map.remove(key)?;
+        self.detach(index);
+        Some(self.slab.remove(index).value)
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.slab.clear();
+        self.buckets.clear();
+        self.min_frequency = 0;
+    }
+
+    /// Iterates the entries from most to least frequently used, most
+    /// recently used first among equals: the reverse of eviction order.
+    ///
+    /// Sorts the distinct use counts first, so this costs O(b log b) on top
+    /// of the entries for `b` distinct counts.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let mut frequencies: Vec<u64> = self.buckets.keys().copied().collect();
+        frequencies.sort_unstable_by(|a, b| b.cmp(a));
+        frequencies.into_iter().flat_map(move |frequency| {
+            self.buckets[&frequency].iter(&self.slab).map(|index| {
+                let entry = self.slab.get(index);
+                (&entry.key, &entry.value)
+            })
+        })
+    }
+
+    /// Moves the entry at `index` to the front of the next frequency bucket.
+    fn touch(&mut self, index: usize) {
+        let frequency = self.slab.get(index).frequency;
+        self.detach(index);
+        if self.min_frequency == frequency && !self.buckets.contains_key(&frequency) {
+            self.min_frequency += 1;
+        }
+        self.slab.get_mut(index).frequency += 1;
+        self.buckets.entry(frequency + 1).or_default().push_front(&mut self.slab, index);
+    }
+
+    /// Unlinks the entry at `index` from its bucket, dropping the bucket if
+    /// it empties. A stale `min_frequency` is only ever read after `put`
+    /// resets it or `touch` advances it.
+    fn detach(&mut self, index: usize) {
+        let frequency = self.slab.get(index).frequency;
+        let bucket = self.buckets.get_mut(&frequency).expect("entry's bucket exists");
+        bucket.unlink(&mut self.slab, index);
+        if bucket.is_empty() {
+            self.buckets.remove(&frequency);
+        }
+    }
+
+    fn evict(&mut self) {
+        let Some(index) = self.buckets.get(&self.min_frequency).and_then(OrderList::back) else {
+            return;
+        };
+        self.detach(index);
+        let entry = self.slab.remove(index);
+        self.map.remove(&entry.key);
+        self.evicted(entry.key, entry.value);
+    }
+
+    fn evicted(&mut self, key: K, value: V) {
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(key, value);
+        }
+    }
+}
+
+impl<K: fmt::Debug + Hash + Eq + Clone, V: fmt::Debug> fmt::Debug for LfuCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
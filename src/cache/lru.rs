@@ -0,0 +1,228 @@
+use super::order::{OrderList, Slab};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn keys<V>(cache: &LruCache<&'static str, V>) -> Vec<&'static str> {
+        cache.iter().map(|(k, _)| *k).collect()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get("a"), Some(&1));
+        cache.put("c", 3);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(keys(&cache), vec!["c", "a"]);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_peek_does_not_promote() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.peek("a"), Some(&1));
+        cache.put("c", 3);
+        assert!(!cache.contains("a"));
+        assert_eq!(keys(&cache), vec!["c", "b"]);
+    }
+
+    #[test]
+    fn test_put_replaces_and_promotes() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.put("a", 10), Some(1));
+        assert_eq!(keys(&cache), vec!["a", "b"]);
+        *cache.get_mut("b").unwrap() += 1;
+        assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"b", &3), (&"a", &10)]);
+    }
+
+    #[test]
+    fn test_remove_and_reuse_slots() {
+        let mut cache = LruCache::new(3);
+        for (i, key) in ["a", "b", "c"].into_iter().enumerate() {
+            cache.put(key, i);
+        }
+        assert_eq!(cache.remove("b"), Some(1));
+        assert_eq!(cache.remove("b"), None);
+        cache.put("d", 3);
+        cache.put("e", 4);
+        assert_eq!(keys(&cache), vec!["e", "d", "c"]);
+        cache.clear();
+        assert!(cache.is_empty());
+        cache.put("f", 5);
+        assert_eq!(keys(&cache), vec!["f"]);
+    }
+
+    #[test]
+    fn test_eviction_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&evicted);
+        let mut cache = LruCache::new(1).with_eviction_callback(move |k, v| log.borrow_mut().push((k, v)));
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.remove(&2);
+        cache.put(3, "three");
+        assert_eq!(evicted.take(), vec![(1, "one")]);
+
+        let mut empty = LruCache::new(0).with_eviction_callback(|k: u8, _: ()| assert_eq!(k, 7));
+        empty.put(7, ());
+        assert!(empty.is_empty());
+    }
+}
+
+/// A least-recently-used cache holding at most `capacity` entries.
+///
+/// `get`, `put` and `remove` are O(1): a hash map finds an entry's slot and
+/// the slots form a recency list, most recent at the front.
+pub struct LruCache<K, V> {
+    map: HashMap<K, usize>,
+    slab: Slab<(K, V)>,
+    order: OrderList,
+    capacity: usize,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            map: HashMap::with_capacity(capacity),
+            slab: Slab::with_capacity(capacity),
+            order: OrderList::default(),
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    /// Calls `f` with every entry evicted to make room. Entries removed with
+    /// `remove` or replaced by `put` are not reported.
+    pub fn with_eviction_callback(mut self, f: impl FnMut(K, V) + 'static) -> Self {
+        self.on_evict = Some(Box::new(f));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the value for `key` and marks it most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Returns the value for `key` mutably and marks it most recently used.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = *self.map.get(key)?;
+        self.order.move_to_front(&mut self.slab, index);
+        Some(&mut self.slab.get_mut(index).1)
+    }
+
+    /// Returns the value for `key` without changing its recency.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key).map(|&index| &self.slab.get(index).1)
+    }
+
+    /// Inserts or replaces the value for `key` and marks it most recently
+    /// used, evicting the least recently used entry if the cache is full.
+    /// Returns the replaced value.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&index) = self.map.get(&key) {
+            self.order.move_to_front(&mut self.slab, index);
+            return Some(std::mem::replace(&mut self.slab.get_mut(index).1, value));
+        }
+        if self.capacity == 0 {
+            self.evicted(key, value);
+            return None;
+        }
+        if self.map.len() == self.capacity {
+            self.evict();
+        }
+        let index = self.slab.insert((key.clone(), value));
+        self.order.push_front(&mut self.slab, index);
+        self.map.insert(key, index);
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.map.remove(key)?;
+        self.order.unlink(&mut self.slab, index);
+        Some(self.slab.remove(index).1)
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.slab.clear();
+        self.order = OrderList::default();
+    }
+
+    /// Iterates the entries from most to least recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.order.iter(&self.slab).map(|index| {
+            let (key, value) = self.slab.get(index);
+            (key, value)
+        })
+    }
+
+    fn evict(&mut self) {
+        if let Some(index) = self.order.back() {
+            self.order.unlink(&mut self.slab, index);
+            let (key, value) = self.slab.remove(index);
+            self.map.remove(&key);
+            self.evicted(key, value);
+        }
+    }
+
+    fn evicted(&mut self, key: K, value: V) {
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(key, value);
+        }
+    }
+}
+
+impl<K: fmt::Debug + Hash + Eq + Clone, V: fmt::Debug> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,9 @@
+//! Bounded caches with eviction callbacks.
+//!
+//! Entries live in a slab and are threaded onto index-linked order lists, so
+//! reordering an entry on access is O(1) and needs no unsafe code.
+
+pub mod lfu;
+pub mod lru;
+mod order;
+pub mod ttl;
@@ -0,0 +1,127 @@
+/// Slab storage for cache entries: a `Vec` of slots reused through a free
+/// list, so an entry's index stays valid until it is removed.
+#[derive(Debug, Clone)]
+pub(super) struct Slab<T> {
+    slots: Vec<Option<Slot<T>>>,
+    free: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    value: T,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+impl<T> Slab<T> {
+    pub(super) fn new() -> Self {
+        Slab { slots: Vec::new(), free: Vec::new() }
+    }
+
+    pub(super) fn with_capacity(capacity: usize) -> Self {
+        Slab { slots: Vec::with_capacity(capacity), free: Vec::new() }
+    }
+
+    /// Stores `value` unlinked and returns its index.
+    pub(super) fn insert(&mut self, value: T) -> usize {
+        let slot = Some(Slot { value, prev: None, next: None });
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = slot;
+                index
+            }
+            None => {
+                self.slots.push(slot);
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Frees the slot at `index`, which must already be unlinked.
+    pub(super) fn remove(&mut self, index: usize) -> T {
+        let slot = self.slots[index].take().expect("slot is occupied");
+        self.free.push(index);
+        slot.value
+    }
+
+    pub(super) fn get(&self, index: usize) -> &T {
+        &self.slot(index).value
+    }
+
+    pub(super) fn get_mut(&mut self, index: usize) -> &mut T {
+        &mut self.slot_mut(index).value
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.slots.clear();
+        self.free.clear();
+    }
+
+    fn slot(&self, index: usize) -> &Slot<T> {
+        self.slots[index].as_ref().expect("slot is occupied")
+    }
+
+    fn slot_mut(&mut self, index: usize) -> &mut Slot<T> {
+        self.slots[index].as_mut().expect("slot is occupied")
+    }
+}
+
+/// A doubly linked list threaded through the slots of a [`Slab`]. Several
+/// lists can share one slab as long as each slot is on at most one of them.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct OrderList {
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+impl OrderList {
+    pub(super) fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub(super) fn back(&self) -> Option<usize> {
+        self.tail
+    }
+
+    pub(super) fn push_front<T>(&mut self, slab: &mut Slab<T>, index: usize) {
+        let old_head = self.head;
+        let slot = slab.slot_mut(index);
+        slot.prev = None;
+        slot.next = old_head;
+        match old_head {
+            Some(head) => slab.slot_mut(head).prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+
+    pub(super) fn unlink<T>(&mut self, slab: &mut Slab<T>, index: usize) {
+        let slot = slab.slot_mut(index);
+        let (prev, next) = (slot.prev.take(), slot.next.take());
+        match prev {
+            Some(prev) => slab.slot_mut(prev).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => slab.slot_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    pub(super) fn move_to_front<T>(&mut self, slab: &mut Slab<T>, index: usize) {
+        if self.head != Some(index) {
+            self.unlink(slab, index);
+            self.push_front(slab, index);
+        }
+    }
+
+    /// Iterates the slot indices from front to back.
+    pub(super) fn iter<'a, T>(&self, slab: &'a Slab<T>) -> impl Iterator<Item = usize> + 'a {
+        let mut next = self.head;
+        std::iter::from_fn(move || {
+            let index = next?;
+            next = slab.slot(index).next;
+            Some(index)
+        })
+    }
+}
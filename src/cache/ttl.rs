@@ -0,0 +1,459 @@
+use super::order::{OrderList, Slab};
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn cache(capacity: usize, ttl_secs: u64) -> (TtlCache<&'static str, i32, ManualClock>, ManualClock) {
+        let clock = ManualClock::new();
+        (TtlCache::with_clock(capacity, Duration::from_secs(ttl_secs), clock.clone()), clock)
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let (mut cache, clock) = cache(4, 10);
+        cache.put("a", 1);
+        clock.advance(Duration::from_secs(6));
+        cache.put("b", 2);
+        assert_eq!(cache.time_to_live("a"), Some(Duration::from_secs(4)));
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(cache.peek("a"), None);
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some(&2));
+        assert_eq!(cache.len(), 1);
+        clock.advance(Duration::from_secs(6));
+        assert_eq!(cache.iter().count(), 0);
+        assert_eq!(cache.purge_expired(), 1);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_put_with_ttl_and_refresh() {
+        let (mut cache, clock) = cache(4, 10);
+        cache.put_with_ttl("short", 1, Duration::from_secs(1));
+        cache.put("long", 2);
+        clock.advance(Duration::from_secs(5));
+        assert!(!cache.contains("short"));
+        // Replacing a value restarts its time to live
+        assert_eq!(cache.put("long", 3), Some(2));
+        clock.advance(Duration::from_secs(9));
+        assert_eq!(cache.get("long"), Some(&3));
+        // Reading does not
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(cache.get("long"), None);
+    }
+
+    #[test]
+    fn test_unrepresentable_deadline_never_expires() {
+        let (mut cache, clock) = cache(2, 10);
+        cache.put_with_ttl("forever", 1, Duration::MAX);
+        cache.put("a", 2);
+        assert_eq!(cache.time_to_live("forever"), Some(Duration::MAX));
+        clock.advance(Duration::from_secs(1_000_000));
+        assert_eq!(cache.get("forever"), Some(&1));
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(cache.len(), 1);
+        // Writing a normal time to live puts the entry back on the clock
+        cache.put("forever", 3);
+        assert_eq!(cache.time_to_live("forever"), Some(Duration::from_secs(10)));
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.purge_expired(), 1);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_overwriting_expired_entry_reports_it() {
+        let clock = ManualClock::new();
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&evicted);
+        let mut cache = TtlCache::with_clock(2, Duration::from_secs(10), clock.clone())
+            .with_eviction_callback(move |k, v, reason| log.borrow_mut().push((k, v, reason)));
+        cache.put("a", 1);
+        assert_eq!(cache.put("a", 2), Some(1));
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.put("a", 3), None);
+        assert_eq!(evicted.take(), vec![("a", 2, Eviction::Expired)]);
+        assert_eq!(cache.get("a"), Some(&3));
+    }
+
+    #[test]
+    fn test_removing_expired_entry_reports_it() {
+        let clock = ManualClock::new();
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&evicted);
+        let mut cache = TtlCache::with_clock(2, Duration::from_secs(10), clock.clone())
+            .with_eviction_callback(move |k, v, reason| log.borrow_mut().push((k, v, reason)));
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.remove("a"), Some(1));
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.remove("b"), None);
+        assert_eq!(evicted.take(), vec![("b", 2, Eviction::Expired)]);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_evicts_expired_before_recent() {
+        let clock = ManualClock::new();
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&evicted);
+        let mut cache = TtlCache::with_clock(2, Duration::from_secs(10), clock.clone())
+            .with_eviction_callback(move |k, _, reason| log.borrow_mut().push((k, reason)));
+        cache.put_with_ttl("a", 1, Duration::from_secs(1));
+        cache.put("b", 2);
+        clock.advance(Duration::from_secs(2));
+        cache.put("c", 3);
+        cache.get("b");
+        cache.put("d", 4);
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec!["d", "b"]);
+        assert_eq!(evicted.take(), vec![("a", Eviction::Expired), ("c", Eviction::Capacity)]);
+    }
+
+    #[test]
+    fn test_remove_and_clear() {
+        let (mut cache, clock) = cache(2, 1);
+        cache.put("a", 1);
+        assert_eq!(cache.remove("a"), Some(1));
+        cache.put("b", 2);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(cache.remove("b"), None);
+        cache.put("c", 3);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.purge_expired(), 0);
+    }
+
+    #[test]
+    fn test_system_clock_cache() {
+        let mut cache = TtlCache::new(1, Duration::from_secs(60));
+        cache.put(1, "one");
+        assert_eq!(cache.get(&1), Some(&"one"));
+    }
+}
+
+/// A source of the current time, so expiry can be driven by a fake clock.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// Reads `Instant::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock { now: Rc::new(Cell::new(Instant::now())) }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+/// Why an entry left a [`TtlCache`] on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eviction {
+    /// Its time to live ran out.
+    Expired,
+    /// It was the least recently used entry of a full cache.
+    Capacity,
+}
+
+type EvictionCallback<K, V> = Box<dyn FnMut(K, V, Eviction)>;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    // None when the deadline is too far off for `Instant` to represent
+    expires: Option<(Instant, u64)>,
+}
+
+/// An LRU cache whose entries also expire a fixed time after they were
+/// last written.
+///
+/// Expiry times are indexed in a `BTreeMap`, so lookups, writes and
+/// removals are O(log n). Expired entries are dropped when touched, when
+/// room is needed, or by `purge_expired`; until then they count towards
+/// `len` but are invisible to lookups and iteration. An entry whose
+/// deadline lies beyond what `Instant` can represent never expires.
+pub struct TtlCache<K, V, C = SystemClock> {
+    map: HashMap<K, usize>,
+    slab: Slab<Entry<K, V>>,
+    order: OrderList,
+    // Keyed by expiry time, then insertion sequence to keep keys unique
+    expiries: BTreeMap<(Instant, u64), usize>,
+    sequence: u64,
+    capacity: usize,
+    ttl: Duration,
+    clock: C,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+impl<K: Hash + Eq + Clone, V> TtlCache<K, V> {
+    /// A cache using the system clock.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        TtlCache::with_clock(capacity, ttl, SystemClock)
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, C: Clock> TtlCache<K, V, C> {
+    /// A cache giving each written entry `ttl` to live as measured by `clock`.
+    pub fn with_clock(capacity: usize, ttl: Duration, clock: C) -> Self {
+        TtlCache {
+            map: HashMap::with_capacity(capacity),
+            slab: Slab::new(),
+            order: OrderList::default(),
+            expiries: BTreeMap::new(),
+            sequence: 0,
+            capacity,
+            ttl,
+            clock,
+            on_evict: None,
+        }
+    }
+
+    /// Calls `f` with every entry that expires or is evicted to make room,
+    /// including an expired entry overwritten by `put`. Entries removed with
+    /// `remove` or replaced by `put` while still live are not reported.
+    pub fn with_eviction_callback(mut self, f: impl FnMut(K, V, Eviction) + 'static) -> Self {
+        self.on_evict = Some(Box::new(f));
+        self
+    }
+
+    /// Number of stored entries, including expired ones not yet purged.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek(key).is_some()
+    }
+
+    /// Returns how long the entry for `key` has left to live, or
+    /// `Duration::MAX` if it never expires.
+    pub fn time_to_live<Q>(&self, key: &Q) -> Option<Duration>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some((expires, _)) = self.slab.get(*self.map.get(key)?).expires else {
+            return Some(Duration::MAX);
+        };
+        expires.checked_duration_since(self.clock.now()).filter(|left| !left.is_zero())
+    }
+
+    /// Returns the live value for `key` and marks it most recently used.
+    /// Drops the entry if it has expired.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = *self.map.get(key)?;
+        if self.is_expired(index, self.clock.now()) {
+            let entry = self.unlink(index);
+            self.evicted(entry, Eviction::Expired);
+            return None;
+        }
+        self.order.move_to_front(&mut self.slab, index);
+        Some(&mut self.slab.get_mut(index).value)
+    }
+
+    /// Returns the live value for `key` without changing its recency.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = *self.map.get(key)?;
+        (!self.is_expired(index, self.clock.now())).then(|| &self.slab.get(index).value)
+    }
+
+    /// Writes `value` for `key` with the cache's time to live.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        self.put_with_ttl(key, value, self.ttl)
+    }
+
+    /// Writes `value` for `key`, living for `ttl` from now, and marks it
+    /// most recently used. A full cache first drops expired entries, then
+    /// the least recently used one. Returns the replaced value if it was
+    /// still live; an expired one goes to the eviction callback instead.
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        let now = self.clock.now();
+        let expires = now.checked_add(ttl).map(|deadline| (deadline, self.sequence));
+        self.sequence += 1;
+        if let Some(&index) = self.map.get(&key) {
+            let live = !self.is_expired(index, now);
+            let entry = self.slab.get_mut(index);
+            let old_expiry = std::mem::replace(&mut entry.expires, expires);
+            let old = std::mem::replace(&mut entry.value, value);
+            let old_key = (!live).then(|| entry.key.clone());
+            if let Some(old_expiry) = old_expiry {
+                self.expiries.remove(&old_expiry);
+            }
+            if let Some(expires) = expires {
+                self.expiries.insert(expires, index);
+            }
+            self.order.move_to_front(&mut self.slab, index);
+            return match old_key {
+                Some(key) => {
+                    self.evicted(Entry { key, value: old, expires: old_expiry }, Eviction::Expired);
+                    None
+                }
+                None => Some(old),
+            };
+        }
+        if self.capacity == 0 {
+            self.evicted(Entry { key, value, expires }, Eviction::Capacity);
+            return None;
+        }
+        if self.map.len() == self.capacity {
+            self.purge_expired();
+        }
+        if self.map.len() == self.capacity {
+            if let Some(index) = self.order.back() {
+                let entry = self.unlink(index);
+                self.evicted(entry, Eviction::Capacity);
+            }
+        }
+        let index = self.slab.insert(Entry { key: key.clone(), value, expires });
+        self.order.push_front(&mut self.slab, index);
+        if let Some(expires) = expires {
+            self.expiries.insert(expires, index);
+        }
+        self.map.insert(key, index);
+        None
+    }
+
+    /// Removes the entry for `key`, returning its value if it was still live.
+    /// An expired entry is reported to the eviction callback instead.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = *self.map.get(key)?;
+        let live = !self.is_expired(index, self.clock.now());
+        let entry = self.unlink(index);
+        if live {
+            return Some(entry.value);
+        }
+        self.evicted(entry, Eviction::Expired);
+        None
+    }
+
+    /// Drops every expired entry, reporting each to the eviction callback,
+    /// and returns how many there were.
+    pub fn purge_expired(&mut self) -> usize {
+        let now = self.clock.now();
+        let mut purged = 0;
+        while let Some((&(expires, _), &index)) = self.expiries.first_key_value() {
+            if expires > now {
+                break;
+            }
+            let entry = self.unlink(index);
+            self.evicted(entry, Eviction::Expired);
+            purged += 1;
+        }
+        purged
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.slab.clear();
+        self.order = OrderList::default();
+        self.expiries.clear();
+    }
+
+    /// Iterates the live entries from most to least recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let now = self.clock.now();
+        self.order.iter(&self.slab).filter(move |&index| !self.is_expired(index, now)).map(|index| {
+            let entry = self.slab.get(index);
+            (&entry.key, &entry.value)
+        })
+    }
+
+    /// An entry is expired from its expiry instant onwards.
+    fn is_expired(&self, index: usize, now: Instant) -> bool {
+        self.slab.get(index).expires.is_some_and(|(expires, _)| expires <= now)
+    }
+
+    fn unlink(&mut self, index: usize) -> Entry<K, V> {
+        self.order.unlink(&mut self.slab, index);
+        let entry = self.slab.remove(index);
+        if let Some(expires) = &entry.expires {
+            self.expiries.remove(expires);
+        }
+        self.map.remove(&entry.key);
+        entry
+    }
+
+    fn evicted(&mut self, entry: Entry<K, V>, reason: Eviction) {
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(entry.key, entry.value, reason);
+        }
+    }
+}
+
+impl<K, V, C> fmt::Debug for TtlCache<K, V, C>
+where
+    K: fmt::Debug + Hash + Eq + Clone,
+    V: fmt::Debug,
+    C: Clock,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
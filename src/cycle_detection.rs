@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Follows an index-linked list where `next[i]` is the node after `i`.
+    fn successor(next: &[Option<usize>]) -> impl Fn(&usize) -> Option<usize> + '_ {
+        move |&i| next[i]
+    }
+
+    #[test]
+    fn test_floyd_finds_cycle_entry_and_length() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2
+        let next = [Some(1), Some(2), Some(3), Some(4), Some(5), Some(2)];
+        assert_eq!(floyd(0, successor(&next)), Some(Cycle { start: 2, tail: 2, length: 4 }));
+        assert_eq!(floyd(3, successor(&next)), Some(Cycle { start: 3, tail: 0, length: 4 }));
+    }
+
+    #[test]
+    fn test_floyd_on_acyclic_list() {
+        let next = [Some(1), Some(2), None];
+        assert_eq!(floyd(0, successor(&next)), None);
+        assert_eq!(floyd(2, successor(&next)), None);
+    }
+
+    #[test]
+    fn test_floyd_self_loop() {
+        let next = [Some(1), Some(1)];
+        assert_eq!(floyd(0, successor(&next)), Some(Cycle { start: 1, tail: 1, length: 1 }));
+    }
+
+    #[test]
+    fn test_floyd_on_iterated_function() {
+        // x -> x² + 1 mod 255 starting from 3 enters a cycle
+        let found = floyd(3u32, |&x| Some((x * x + 1) % 255)).unwrap();
+        let mut x = found.start;
+        for _ in 0..found.length {
+            x = (x * x + 1) % 255;
+        }
+        assert_eq!(x, found.start);
+        let mut y = 3;
+        for _ in 0..found.tail {
+            y = (y * y + 1) % 255;
+        }
+        assert_eq!(y, found.start);
+    }
+}
+
+/// A cycle reached by repeatedly following a successor function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T> {
+    /// The first node on the cycle.
+    pub start: T,
+    /// Steps from the starting node to `start`.
+    pub tail: usize,
+    /// Number of nodes on the cycle.
+    pub length: usize,
+}
+
+/// Floyd's tortoise and hare: follows `next` from `start` and returns the
+/// cycle it runs into, or `None` if `next` reaches the end of the chain.
+///
+/// Works on any linked structure that can express a cycle, such as nodes
+/// linked by index or an iterated function, in O(tail + length) steps and
+/// O(1) space.
+pub fn floyd<T, F>(start: T, next: F) -> Option<Cycle<T>>
+where
+    T: Clone + PartialEq,
+    F: Fn(&T) -> Option<T>,
+{
+    // The hare moves two steps per tortoise step; they can only meet on a cycle
+    let mut tortoise = next(&start)?;
+    let mut hare = next(&next(&start)?)?;
+    while tortoise != hare {
+        tortoise = next(&tortoise)?;
+        hare = next(&next(&hare)?)?;
+    }
+
+    // The meeting point is as far from the cycle start as `start` is
+    let mut tortoise = start;
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise)?;
+        hare = next(&hare)?;
+        tail += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = next(&tortoise)?;
+    while tortoise != hare {
+        hare = next(&hare)?;
+        length += 1;
+    }
+    Some(Cycle { start: tortoise, tail, length })
+}
@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reject_policy() {
+        let queue = BoundedQueue::new(2, OverflowPolicy::Reject);
+        assert_eq!(queue.push(1), Ok(None));
+        assert_eq!(queue.push(2), Ok(None));
+        let err = queue.push(3).unwrap_err();
+        assert_eq!(err, QueueFullError { item: 3, capacity: 2 });
+        assert_eq!(err.to_string(), "queue is full (capacity 2)");
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.push(3), Ok(None));
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_drop_oldest_policy() {
+        let queue = BoundedQueue::new(2, OverflowPolicy::DropOldest);
+        queue.push("a").unwrap();
+        queue.push("b").unwrap();
+        assert_eq!(queue.push("c"), Ok(Some("a")));
+        assert_eq!(queue.drain().collect::<Vec<_>>(), vec!["b", "c"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_block_policy_waits_for_space() {
+        let queue = Arc::new(BoundedQueue::new(1, OverflowPolicy::Block));
+        queue.push(1).unwrap();
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                // Blocks until the consumer below takes the first item
+                queue.push(2).unwrap();
+            })
+        };
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(queue.length(), 1);
+        assert_eq!(queue.pop_wait(), 1);
+        producer.join().unwrap();
+        assert_eq!(queue.pop_wait(), 2);
+    }
+
+    #[test]
+    fn test_extend_and_collect() {
+        let queue: BoundedQueue<u8> = (1..=3).collect();
+        assert_eq!(queue.capacity(), 3);
+        let mut handle = &queue;
+        handle.extend([4, 5]);
+        assert_eq!(queue.length(), 3);
+        let queue = BoundedQueue::new(3, OverflowPolicy::DropOldest);
+        (&queue).extend(1..=5);
+        assert_eq!(queue.drain().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert!(format!("{:?}", queue).contains("DropOldest"));
+    }
+
+    #[test]
+    fn test_zero_capacity_never_accepts() {
+        let queue = BoundedQueue::new(0, OverflowPolicy::DropOldest);
+        assert_eq!(queue.push(1), Err(QueueFullError { item: 1, capacity: 0 }));
+        assert_eq!(queue.pop(), None);
+    }
+}
+
+/// What `BoundedQueue::push` does when the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Hand the new item back in an error.
+    Reject,
+    /// Evict the item at the front to make room.
+    DropOldest,
+    /// Wait until another thread pops an item.
+    Block,
+}
+
+/// Returned by `BoundedQueue::push` when the item cannot be queued.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueFullError<T> {
+    pub item: T,
+    pub capacity: usize,
+}
+
+impl<T> fmt::Display for QueueFullError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "queue is full (capacity {})", self.capacity)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for QueueFullError<T> {}
+
+/// A fixed-capacity FIFO queue that can be shared between threads.
+///
+/// All methods take `&self`; wrap the queue in an `Arc` to share it. What a
+/// push into a full queue does is decided by its `OverflowPolicy`.
+#[derive(Debug)]
+pub struct BoundedQueue<T> {
+    queue: Mutex<VecDeque<T>>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    not_full: Condvar,
+    not_empty: Condvar,
+}
+
+impl<T> BoundedQueue<T> {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        BoundedQueue {
+            queue: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            policy,
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<T>> {
+        // A panic while holding the lock cannot leave the VecDeque inconsistent
+        self.queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn length(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Appends `item` at the back, applying the overflow policy when full.
+    ///
+    /// Returns the evicted item under `DropOldest`, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns the item under `Reject` when the queue is full, and under
+    /// every policy when the capacity is zero.
+    pub fn push(&self, item: T) -> Result<Option<T>, QueueFullError<T>> {
+        if self.capacity == 0 {
+            return Err(QueueFullError { item, capacity: 0 });
+        }
+        let mut queue = self.lock();
+        let mut evicted = None;
+        if queue.len() == self.capacity {
+            match self.policy {
+                OverflowPolicy::Reject => return Err(QueueFullError { item, capacity: self.capacity }),
+                OverflowPolicy::DropOldest => evicted = queue.pop_front(),
+                OverflowPolicy::Block => {
+                    while queue.len() == self.capacity {
+                        queue = self.not_full.wait(queue).unwrap_or_else(|poisoned| poisoned.into_inner());
+                    }
+                }
+            }
+        }
+        queue.push_back(item);
+        drop(queue);
+        self.not_empty.notify_one();
+        Ok(evicted)
+    }
+
+    /// Removes the item at the front, if any, without waiting.
+    pub fn pop(&self) -> Option<T> {
+        let item = self.lock().pop_front();
+        if item.is_some() {
+            self.not_full.notify_one();
+        }
+        item
+    }
+
+    /// Removes the item at the front, waiting for one to arrive if the queue is empty.
+    pub fn pop_wait(&self) -> T {
+        let mut queue = self.lock();
+        loop {
+            if let Some(item) = queue.pop_front() {
+                drop(queue);
+                self.not_full.notify_one();
+                return item;
+            }
+            queue = self.not_empty.wait(queue).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    /// Removes and returns every queued item, front to back.
+    pub fn drain(&self) -> std::collections::vec_deque::IntoIter<T> {
+        let items = std::mem::take(&mut *self.lock());
+        self.not_full.notify_all();
+        items.into_iter()
+    }
+}
+
+impl<T> IntoIterator for BoundedQueue<T> {
+    type Item = T;
+    type IntoIter = std::collections::vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.queue.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()).into_iter()
+    }
+}
+
+/// Pushes each item under the queue's policy. Implemented on `&BoundedQueue`
+/// so that a `Block` queue can wait for other threads; items the policy
+/// rejects are dropped.
+impl<T> Extend<T> for &BoundedQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            let _ = self.push(item);
+        }
+    }
+}
+
+/// Collects into a queue exactly as large as the items, with the `Reject` policy.
+impl<T> FromIterator<T> for BoundedQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let items = VecDeque::from_iter(iter);
+        let queue = BoundedQueue::new(items.len(), OverflowPolicy::Reject);
+        *queue.lock() = items;
+        queue
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_until_full() {
+        let mut stack = BoundedStack::new(2);
+        assert_eq!(stack.push("a"), Ok(()));
+        assert_eq!(stack.push("b"), Ok(()));
+        assert!(stack.is_full());
+        let err = stack.push("c").unwrap_err();
+        assert_eq!(err, StackFullError { item: "c", capacity: 2 });
+        assert_eq!(err.to_string(), "stack is full (capacity 2)");
+        assert_eq!(stack.pop(), Some("b"));
+        assert_eq!(stack.push("c"), Ok(()));
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut stack = BoundedStack::new(0);
+        assert!(stack.is_full());
+        assert_eq!(stack.push(1).unwrap_err().item, 1);
+        assert_eq!(stack.peek(), None);
+    }
+
+    #[test]
+    fn test_peek_mut_and_clear() {
+        let mut stack = BoundedStack::new(3);
+        stack.push(String::from("x")).unwrap();
+        stack.peek_mut().unwrap().push('y');
+        assert_eq!(stack.peek().map(String::as_str), Some("xy"));
+        assert_eq!(stack.length(), 1);
+        assert_eq!(stack.capacity(), 3);
+        stack.clear();
+        assert!(stack.is_empty());
+    }
+}
+
+/// Returned by `BoundedStack::push` when the stack is full. Holds the item
+/// that could not be pushed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFullError<T> {
+    pub item: T,
+    pub capacity: usize,
+}
+
+impl<T> fmt::Display for StackFullError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "stack is full (capacity {})", self.capacity)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for StackFullError<T> {}
+
+/// A stack with a fixed capacity that rejects pushes instead of growing.
+///
+/// Storage for all `capacity` items is allocated up front.
+#[derive(Debug, Clone)]
+pub struct BoundedStack<T> {
+    stack: Vec<T>,
+    capacity: usize,
+}
+
+impl<T> BoundedStack<T> {
+    pub fn new(capacity: usize) -> Self {
+        BoundedStack { stack: Vec::with_capacity(capacity), capacity }
+    }
+    pub fn length(&self) -> usize {
+        self.stack.len()
+    }
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+    pub fn is_full(&self) -> bool {
+        self.stack.len() == self.capacity
+    }
+    /// Pushes `item`, or hands it back in the error if the stack is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StackFullError` when `length() == capacity()`.
+    pub fn push(&mut self, item: T) -> Result<(), StackFullError<T>> {
+        if self.is_full() {
+            return Err(StackFullError { item, capacity: self.capacity });
+        }
+        self.stack.push(item);
+        Ok(())
+    }
+    pub fn pop(&mut self) -> Option<T> {
+        self.stack.pop()
+    }
+    pub fn peek(&self) -> Option<&T> {
+        self.stack.last()
+    }
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.stack.last_mut()
+    }
+    pub fn clear(&mut self) {
+        self.stack.clear()
+    }
+    /// Iterates from the bottom of the stack to the top.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.stack.iter()
+    }
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.stack.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for BoundedStack<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.stack.into_iter()
+    }
+}
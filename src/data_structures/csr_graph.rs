@@ -0,0 +1,336 @@
+use crate::data_structures::weighted_adj_list::WeightedAdjacencyList;
+use crate::graph::{GraphBase, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable, Visitable};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstras::dijkstras_shortest_path;
+    use std::time::Instant;
+
+    fn sample() -> CsrGraph<&'static str, u32> {
+        CsrGraph::from_edges(vec![
+            ("a", "b", 4),
+            ("a", "c", 1),
+            ("c", "b", 2),
+            ("b", "d", 5),
+        ])
+    }
+
+    #[test]
+    fn test_from_edges_interns_vertices() {
+        let graph = sample();
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.index_of(&"a"), Some(0));
+        assert_eq!(graph.index_of(&"d"), Some(3));
+        assert_eq!(graph.vertex(2), Some(&"c"));
+        assert_eq!(graph.index_of(&"z"), None);
+    }
+
+    #[test]
+    fn test_neighbor_slices() {
+        let graph = sample();
+        let a = graph.index_of(&"a").unwrap();
+        assert_eq!(graph.neighbor_indices(a), &[1, 2]);
+        assert_eq!(graph.neighbor_weights(a), &[4, 1]);
+        assert_eq!(graph.out_degree(3), 0);
+        assert_eq!(graph.get_weight(&"c", &"b"), Some(&2));
+        assert_eq!(graph.get_weight(&"b", &"c"), None);
+    }
+
+    #[test]
+    fn test_isolated_vertices_are_kept() {
+        let graph = CsrGraph::from_parts(vec![1, 2, 3], vec![(1, 2, 7)]);
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.out_degree(graph.index_of(&3).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_from_weighted_adjacency_list() {
+        let mut list: WeightedAdjacencyList<char, u8> = WeightedAdjacencyList::new();
+        list.add_edge('x', 'y', 3);
+        list.add_edge('y', 'z', 4);
+        list.add_vertex('w');
+        let graph = CsrGraph::from(&list);
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.get_weight(&'x', &'y'), Some(&3));
+        assert_eq!(graph.get_weight(&'y', &'z'), Some(&4));
+    }
+
+    #[test]
+    fn test_dijkstras_on_csr_graph() {
+        let graph = sample();
+        let path = dijkstras_shortest_path(0, 3, &graph).unwrap();
+        let names: Vec<_> = path.iter().map(|&i| *graph.vertex(i).unwrap()).collect();
+        assert_eq!(names, vec!["a", "c", "b", "d"]);
+    }
+
+    /// Compares neighbor iteration and Dijkstra on the HashMap layout and on CSR.
+    ///
+    /// Run with `cargo test --release bench_csr -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_csr_vs_hashmap_layout() {
+        const VERTICES: usize = 100_000;
+        const EDGES: usize = 1_000_000;
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut edges: Vec<(usize, usize, u64)> = (0..EDGES)
+            .map(|_| ((next() as usize) % VERTICES, (next() as usize) % VERTICES, next() % 100))
+            .collect();
+        // The HashMap layout cannot hold parallel edges, so drop them up front
+        edges.sort_unstable_by_key(|&(from, to, _)| (from, to));
+        edges.dedup_by_key(|&mut (from, to, _)| (from, to));
+
+        let start = Instant::now();
+        let mut list = WeightedAdjacencyList::new();
+        for &(from, to, weight) in &edges {
+            list.add_edge(from, to, weight);
+        }
+        println!("hashmap build:     {:?}", start.elapsed());
+        let start = Instant::now();
+        let csr = CsrGraph::from_parts(0..VERTICES, edges.iter().copied());
+        println!("csr build:         {:?}", start.elapsed());
+
+        let start = Instant::now();
+        let list_total: u64 = (0..VERTICES).flat_map(|v| (&list).edges(v)).map(|(_, _, &w)| w).sum();
+        println!("hashmap iterate:   {:?}", start.elapsed());
+        let start = Instant::now();
+        let csr_total: u64 = (0..VERTICES).flat_map(|v| csr.neighbor_weights(v)).sum();
+        println!("csr iterate:       {:?}", start.elapsed());
+        assert_eq!(list_total, csr_total);
+
+        let start = Instant::now();
+        let list_path = dijkstras_shortest_path(0, VERTICES - 1, &list);
+        println!("hashmap dijkstras: {:?}", start.elapsed());
+        let start = Instant::now();
+        let csr_path = dijkstras_shortest_path(0, VERTICES - 1, &csr);
+        println!("csr dijkstras:     {:?}", start.elapsed());
+        assert_eq!(list_path.is_some(), csr_path.is_some());
+    }
+}
+
+/// An immutable graph in compressed sparse row layout.
+///
+/// Vertex ids are interned to dense indices `0..vertex_count()` in order of
+/// first appearance. The outgoing edges of vertex `v` occupy
+/// `offsets[v]..offsets[v + 1]` in the contiguous `targets` and `weights`
+/// arrays, so neighbor lookups are O(1) slice operations. Parallel edges are
+/// kept as given.
+pub struct CsrGraph<T, W> {
+    vertices: Vec<T>,
+    index: HashMap<T, usize>,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<T, W> CsrGraph<T, W>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Builds a graph from `(from, to, weight)` triples.
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (T, T, W)>,
+    {
+        Self::from_parts(std::iter::empty(), edges)
+    }
+
+    /// Builds a graph from a vertex list and `(from, to, weight)` triples.
+    ///
+    /// The listed vertices are interned first, which keeps isolated vertices
+    /// and fixes their indices; vertices only mentioned by edges follow.
+    pub fn from_parts<V, I>(vertices: V, edges: I) -> Self
+    where
+        V: IntoIterator<Item = T>,
+        I: IntoIterator<Item = (T, T, W)>,
+    {
+        let mut graph = CsrGraph {
+            vertices: Vec::new(),
+            index: HashMap::new(),
+            offsets: Vec::new(),
+            targets: Vec::new(),
+            weights: Vec::new(),
+        };
+        for vertex in vertices {
+            graph.intern(vertex);
+        }
+        let edges: Vec<(usize, usize, W)> = edges
+            .into_iter()
+            .map(|(from, to, weight)| (graph.intern(from), graph.intern(to), weight))
+            .collect();
+
+        // Count out-degrees, then turn the counts into start offsets
+        let mut offsets = vec![0; graph.vertices.len() + 1];
+        for &(from, _, _) in &edges {
+            offsets[from + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        // Scatter the edges into their slots, preserving input order per vertex
+        let mut next = offsets.clone();
+        let mut slots: Vec<Option<(usize, W)>> = Vec::with_capacity(edges.len());
+        slots.resize_with(edges.len(), || None);
+        for (from, to, weight) in edges {
+            slots[next[from]] = Some((to, weight));
+            next[from] += 1;
+        }
+        let (targets, weights) = slots.into_iter().flatten().unzip();
+
+        graph.offsets = offsets;
+        graph.targets = targets;
+        graph.weights = weights;
+        graph
+    }
+
+    fn intern(&mut self, vertex: T) -> usize {
+        if let Some(&i) = self.index.get(&vertex) {
+            return i;
+        }
+        let i = self.vertices.len();
+        self.index.insert(vertex.clone(), i);
+        self.vertices.push(vertex);
+        i
+    }
+
+    /// Returns the dense index of a vertex id.
+    pub fn index_of(&self, vertex: &T) -> Option<usize> {
+        self.index.get(vertex).copied()
+    }
+
+    /// Returns the weight of the first edge between two vertex ids.
+    pub fn get_weight(&self, from: &T, to: &T) -> Option<&W> {
+        let from = self.index_of(from)?;
+        let to = self.index_of(to)?;
+        let position = self.neighbor_indices(from).iter().position(|&t| t == to)?;
+        Some(&self.neighbor_weights(from)[position])
+    }
+}
+
+impl<T, W> CsrGraph<T, W> {
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns the vertex id stored at a dense index.
+    pub fn vertex(&self, index: usize) -> Option<&T> {
+        self.vertices.get(index)
+    }
+
+    /// Returns the target indices of the outgoing edges of `vertex`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vertex >= vertex_count()`.
+    pub fn neighbor_indices(&self, vertex: usize) -> &[usize] {
+        &self.targets[self.offsets[vertex]..self.offsets[vertex + 1]]
+    }
+
+    /// Returns the weights of the outgoing edges of `vertex`, aligned with
+    /// `neighbor_indices`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vertex >= vertex_count()`.
+    pub fn neighbor_weights(&self, vertex: usize) -> &[W] {
+        &self.weights[self.offsets[vertex]..self.offsets[vertex + 1]]
+    }
+
+    /// Returns the number of outgoing edges of `vertex`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vertex >= vertex_count()`.
+    pub fn out_degree(&self, vertex: usize) -> usize {
+        self.offsets[vertex + 1] - self.offsets[vertex]
+    }
+
+    /// Returns the edge slot range of `vertex`, empty for an unknown vertex.
+    fn edge_range(&self, vertex: usize) -> Range<usize> {
+        if vertex < self.vertices.len() {
+            self.offsets[vertex]..self.offsets[vertex + 1]
+        } else {
+            0..0
+        }
+    }
+}
+
+impl<T, W> From<&WeightedAdjacencyList<T, W>> for CsrGraph<T, W>
+where
+    T: Hash + Eq + Clone,
+    W: Clone,
+{
+    fn from(list: &WeightedAdjacencyList<T, W>) -> Self {
+        CsrGraph::from_parts(
+            list.vertices().cloned(),
+            list.all_edges()
+                .map(|(from, to, weight)| (from.clone(), to.clone(), weight.clone())),
+        )
+    }
+}
+
+impl<T, W> GraphBase for &CsrGraph<T, W> {
+    type NodeId = usize;
+    type EdgeWeight = W;
+}
+
+impl<T, W> IntoNeighbors for &CsrGraph<T, W> {
+    fn neighbors(self, node: usize) -> impl Iterator<Item = usize> {
+        self.targets[self.edge_range(node)].iter().copied()
+    }
+}
+
+impl<T, W> IntoNodeIdentifiers for &CsrGraph<T, W> {
+    fn node_identifiers(self) -> impl Iterator<Item = usize> {
+        0..self.vertices.len()
+    }
+}
+
+impl<'a, T, W> IntoEdges for &'a CsrGraph<T, W> {
+    type EdgeRef = (usize, usize, &'a W);
+
+    fn edges(self, node: usize) -> impl Iterator<Item = Self::EdgeRef> {
+        let range = self.edge_range(node);
+        self.targets[range.clone()]
+            .iter()
+            .zip(self.weights[range].iter())
+            .map(move |(&to, weight)| (node, to, weight))
+    }
+}
+
+impl<T, W> NodeIndexable for &CsrGraph<T, W> {
+    fn node_bound(self) -> usize {
+        self.vertices.len()
+    }
+
+    fn to_index(self, node: usize) -> usize {
+        node
+    }
+
+    fn from_index(self, index: usize) -> usize {
+        index
+    }
+}
+
+impl<T, W> Visitable for &CsrGraph<T, W> {
+    type Map = Vec<bool>;
+
+    fn visit_map(self) -> Vec<bool> {
+        vec![false; self.vertices.len()]
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_pop_both_ends() {
+        let mut deque = Deque::new();
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        assert_eq!(deque.front(), Some(&1));
+        assert_eq!(deque.back(), Some(&3));
+        assert_eq!(deque.get(1), Some(&2));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_back(), None);
+    }
+
+    #[test]
+    fn test_mutation_and_iteration() {
+        let mut deque: Deque<String> = ["b", "c"].iter().map(|s| s.to_string()).collect();
+        deque.push_front(String::from("a"));
+        deque.front_mut().unwrap().push('!');
+        deque.back_mut().unwrap().push('?');
+        deque.extend([String::from("d")]);
+        assert_eq!(deque.iter().map(String::as_str).collect::<Vec<_>>(), vec!["a!", "b", "c?", "d"]);
+        assert_eq!(deque.iter().next_back().map(String::as_str), Some("d"));
+        assert_eq!(deque.length(), 4);
+        let drained: Vec<String> = deque.into_iter().collect();
+        assert_eq!(drained.len(), 4);
+    }
+}
+
+/// A double-ended queue with O(1) pushes and pops at both ends.
+/// Iteration runs from front to back.
+#[derive(Debug, Clone)]
+pub struct Deque<T> {
+    deque: VecDeque<T>,
+}
+
+impl<T> Deque<T> {
+    pub fn new() -> Self {
+        Deque { deque: VecDeque::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Deque { deque: VecDeque::with_capacity(capacity) }
+    }
+
+    pub fn length(&self) -> usize {
+        self.deque.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    pub fn push_front(&mut self, item: T) {
+        self.deque.push_front(item)
+    }
+
+    pub fn push_back(&mut self, item: T) {
+        self.deque.push_back(item)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.deque.front()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.deque.back()
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.deque.front_mut()
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.deque.back_mut()
+    }
+
+    /// Returns the item `index` places from the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.deque.get(index)
+    }
+
+    pub fn clear(&mut self) {
+        self.deque.clear()
+    }
+
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.deque.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::collections::vec_deque::IterMut<'_, T> {
+        self.deque.iter_mut()
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Deque::new()
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = std::collections::vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deque.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Pushes the items onto the back in order.
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.deque.extend(iter)
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Deque { deque: VecDeque::from_iter(iter) }
+    }
+}
@@ -0,0 +1,768 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn list(values: &[i32]) -> DoublyLinkedList<i32> {
+        values.iter().copied().collect()
+    }
+
+    fn values(list: &DoublyLinkedList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(values(&list), vec![1, 2, 3]);
+        assert_eq!((list.front(), list.back()), (Some(&1), Some(&3)));
+        *list.back_mut().unwrap() = 30;
+        assert_eq!(list.pop_back(), Some(30));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_double_ended_iterators() {
+        let mut list = list(&[1, 2, 3, 4, 5]);
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![&4, &3, &2]);
+        for value in list.iter_mut().rev().take(2) {
+            *value *= 10;
+        }
+        let mut owned = list.into_iter();
+        assert_eq!(owned.next_back(), Some(50));
+        assert_eq!(owned.next(), Some(1));
+        assert_eq!(owned.collect::<Vec<_>>(), vec![2, 3, 40]);
+    }
+
+    #[test]
+    fn test_append_and_split_off() {
+        let mut a = list(&[1, 2, 3]);
+        let mut b = list(&[4, 5]);
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(values(&a), vec![1, 2, 3, 4, 5]);
+
+        let tail = a.split_off(2);
+        assert_eq!((values(&a), values(&tail)), (vec![1, 2], vec![3, 4, 5]));
+        assert_eq!(tail.back(), Some(&5));
+        let all = a.split_off(0);
+        assert!(a.is_empty());
+        assert_eq!(values(&all), vec![1, 2]);
+        let mut all = all;
+        assert!(all.split_off(2).is_empty());
+        b.append(&mut all);
+        assert_eq!(values(&b), vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        list(&[1]).split_off(2);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut list = list(&[1, 2, 3, 4]);
+        list.reverse();
+        assert_eq!(values(&list), vec![4, 3, 2, 1]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        list.push_back(0);
+        assert_eq!((list.front(), list.back()), (Some(&4), Some(&0)));
+    }
+
+    #[test]
+    fn test_cursor_moves_through_ghost() {
+        let mut list = list(&[1, 2, 3]);
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+    }
+
+    #[test]
+    fn test_cursor_insert_and_remove() {
+        let mut list = list(&[1, 3]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(2);
+        cursor.insert_before(0);
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_after(-1);
+        cursor.insert_before(9);
+        assert_eq!(values(&list), vec![-1, 0, 2, 9]);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn test_cursor_splice() {
+        let mut list = list(&[1, 5]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after(self::list(&[2, 3, 4]));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        cursor.splice_before(self::list(&[6, 7]));
+        cursor.splice_after(self::list(&[-1]));
+        cursor.splice_after(DoublyLinkedList::new());
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(values(&list), vec![-1, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(list.iter().rev().count(), 8);
+    }
+
+    #[test]
+    fn test_cursor_split() {
+        let mut list = list(&[1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_prev();
+        cursor.move_prev();
+        let after = cursor.split_after();
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!((values(&before), values(&after)), (vec![1, 2], vec![4, 5]));
+        assert_eq!(values(&list), vec![3]);
+        assert_eq!((list.front(), list.back()), (Some(&3), Some(&3)));
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_prev();
+        let everything = cursor.split_after();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(values(&everything), vec![3]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_drops_every_element_once() {
+        let tracker = Rc::new(());
+        let mut list: DoublyLinkedList<_> = (0..6).map(|_| Rc::clone(&tracker)).collect();
+        let tail = list.split_off(3);
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after(tail);
+        drop(cursor.remove_current());
+        assert_eq!(Rc::strong_count(&tracker), 6);
+        let mut iter = list.into_iter();
+        iter.next();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_clone_eq_debug() {
+        let list = list(&[1, 2]);
+        assert_eq!(list.clone(), list);
+        assert_ne!(list, self::list(&[1]));
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+    }
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+/// A doubly linked list with O(1) push and pop at both ends and a
+/// [`CursorMut`] for O(1) edits in the middle.
+///
+/// Nodes are heap allocations owned by the list and reached only through raw
+/// pointers, never through long-lived references, so handing out `&T` and
+/// `&mut T` to distinct nodes never aliases.
+///
+/// The unsafe internals pass Miri; rerun `cargo +nightly miri test
+/// data_structures::doubly_linked_list` after changing any of them.
+pub struct DoublyLinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// SAFETY: the list owns its nodes exclusively, like a `Vec<T>` owns its buffer
+unsafe impl<T: Send> Send for DoublyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for DoublyLinkedList<T> {}
+
+impl<T> DoublyLinkedList<T> {
+    pub fn new() -> Self {
+        DoublyLinkedList { head: None, tail: None, len: 0, marker: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: `head` points to a live node owned by `self`
+        self.head.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `front`; `&mut self` makes the borrow unique
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: `tail` points to a live node owned by `self`
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `back`; `&mut self` makes the borrow unique
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        // SAFETY: `head` has no predecessor
+        unsafe { self.insert_between(value, None, self.head) };
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        // SAFETY: `tail` has no successor
+        unsafe { self.insert_between(value, self.tail, None) };
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: `head` is a node of this list
+        self.head.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: `tail` is a node of this list
+        self.tail.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Moves every element of `other` to the back of `self` in O(1), leaving
+    /// `other` empty.
+    pub fn append(&mut self, other: &mut Self) {
+        // SAFETY: `tail` has no successor
+        unsafe { self.splice_between(mem::take(other), self.tail, None) };
+    }
+
+    /// Splits the list in two at `at`: `self` keeps `[0, at)` and the rest
+    /// is returned. Walks from whichever end is closer, so O(min(at, len - at)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split index {} out of bounds for length {}", at, self.len);
+        let last_kept = if at == 0 {
+            None
+        } else if at <= self.len / 2 {
+            let mut node = self.head;
+            for _ in 1..at {
+                // SAFETY: fewer than `len` steps from `head` stay within the list
+                node = node.and_then(|node| unsafe { (*node.as_ptr()).next });
+            }
+            node
+        } else {
+            let mut node = self.tail;
+            for _ in at..self.len {
+                // SAFETY: fewer than `len` steps from `tail` stay within the list
+                node = node.and_then(|node| unsafe { (*node.as_ptr()).prev });
+            }
+            node
+        };
+        // SAFETY: `last_kept` is the node at index `at - 1`, or `None` for `at == 0`
+        unsafe { self.split_after_node(last_kept, at) }
+    }
+
+    /// Reverses the list in place in O(n) by swapping every node's links.
+    pub fn reverse(&mut self) {
+        let mut current = self.head;
+        while let Some(node) = current {
+            // SAFETY: `node` is live and no other reference to it exists
+            let node = unsafe { &mut *node.as_ptr() };
+            mem::swap(&mut node.prev, &mut node.next);
+            current = node.prev;
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { head: self.head, tail: self.tail, len: self.len, marker: PhantomData }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { head: self.head, tail: self.tail, len: self.len, marker: PhantomData }
+    }
+
+    /// A cursor on the first element, or on the ghost position if the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { current: self.head, index: 0, list: self }
+    }
+
+    /// A cursor on the last element, or on the ghost position if the list is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let index = self.len.saturating_sub(1);
+        CursorMut { current: self.tail, index, list: self }
+    }
+
+    /// Allocates a node for `value` and links it between `prev` and `next`,
+    /// updating `head`/`tail` where either side is `None`.
+    ///
+    /// # Safety
+    ///
+    /// `prev` and `next` must be adjacent in this list: `None` standing for
+    /// the position before `head` or after `tail`.
+    unsafe fn insert_between(&mut self, value: T, prev: Link<T>, next: Link<T>) {
+        let node = Box::new(Node { value, prev, next });
+        // SAFETY: `Box::into_raw` never returns null
+        let node = Some(unsafe { NonNull::new_unchecked(Box::into_raw(node)) });
+        // SAFETY: the caller guarantees `prev` and `next` are live nodes of this list
+        unsafe {
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = node,
+                None => self.head = node,
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = node,
+                None => self.tail = node,
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Unlinks `node`, frees it and returns its value.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list.
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        // SAFETY: nodes are allocated by `insert_between` and freed only here
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        // SAFETY: the neighbours of a node in this list are live nodes of it
+        unsafe {
+            match node.prev {
+                Some(prev) => (*prev.as_ptr()).next = node.next,
+                None => self.head = node.next,
+            }
+            match node.next {
+                Some(next) => (*next.as_ptr()).prev = node.prev,
+                None => self.tail = node.prev,
+            }
+        }
+        self.len -= 1;
+        node.value
+    }
+
+    /// Moves all of `other`'s nodes between `prev` and `next` in O(1).
+    ///
+    /// # Safety
+    ///
+    /// As for [`insert_between`](Self::insert_between).
+    unsafe fn splice_between(&mut self, mut other: Self, prev: Link<T>, next: Link<T>) {
+        let (Some(first), Some(last)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+        // SAFETY: `first` and `last` are live nodes now owned by `self`; the
+        // caller guarantees `prev` and `next` are live nodes of this list
+        unsafe {
+            (*first.as_ptr()).prev = prev;
+            (*last.as_ptr()).next = next;
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(first),
+                None => self.head = Some(first),
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(last),
+                None => self.tail = Some(last),
+            }
+        }
+        self.len += mem::take(&mut other.len);
+    }
+
+    /// Detaches every node after `node` (all of them for `None`) into a new
+    /// list.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list at index `kept - 1`, or `None`
+    /// with `kept == 0`.
+    unsafe fn split_after_node(&mut self, node: Link<T>, kept: usize) -> Self {
+        let first = match node {
+            // SAFETY: the caller guarantees `node` is a live node of this list
+            Some(node) => unsafe { (*node.as_ptr()).next.take() },
+            None => self.head.take(),
+        };
+        let Some(first) = first else {
+            return DoublyLinkedList::new();
+        };
+        // SAFETY: `first` followed `node`, so it is a live node of this list
+        unsafe { (*first.as_ptr()).prev = None };
+        let rest = DoublyLinkedList { head: Some(first), tail: self.tail, len: self.len - kept, marker: PhantomData };
+        self.tail = node;
+        self.len = kept;
+        rest
+    }
+}
+
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        DoublyLinkedList::new()
+    }
+}
+
+impl<T: Clone> Clone for DoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DoublyLinkedList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Extend<T> for DoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DoublyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// A mutable cursor over a [`DoublyLinkedList`].
+///
+/// The cursor rests either on an element or on a "ghost" position between
+/// the back and the front, so moving past either end wraps through the
+/// ghost. Every edit at the cursor is O(1).
+pub struct CursorMut<'a, T> {
+    list: &'a mut DoublyLinkedList<T>,
+    current: Link<T>,
+    // Equal to `list.len` while on the ghost
+    index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// The index of the current element, or `None` on the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: `current` is a live node; the cursor borrows the list uniquely
+        self.current.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `current`
+        self.next_node().map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `current`
+        self.prev_node().map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn move_next(&mut self) {
+        self.current = self.next_node();
+        self.index = match self.current {
+            Some(_) if self.index < self.list.len => self.index + 1,
+            Some(_) => 0,
+            None => self.list.len,
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.current = self.prev_node();
+        self.index = match self.current {
+            Some(_) if self.index > 0 && self.index < self.list.len => self.index - 1,
+            Some(_) => self.list.len - 1,
+            None => self.list.len,
+        };
+    }
+
+    /// Inserts `value` after the current element, or at the front on the ghost.
+    pub fn insert_after(&mut self, value: T) {
+        let next = self.next_node();
+        // SAFETY: `current` and its successor are adjacent
+        unsafe { self.list.insert_between(value, self.current, next) };
+        if self.current.is_none() {
+            self.index += 1;
+        }
+    }
+
+    /// Inserts `value` before the current element, or at the back on the ghost.
+    pub fn insert_before(&mut self, value: T) {
+        let prev = self.prev_node();
+        // SAFETY: `current` and its predecessor are adjacent
+        unsafe { self.list.insert_between(value, prev, self.current) };
+        self.index += 1;
+    }
+
+    /// Removes and returns the current element, moving the cursor to the one
+    /// after it. Does nothing on the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        self.current = self.next_node();
+        // SAFETY: `node` is a node of this list
+        let value = unsafe { self.list.unlink(node) };
+        if self.current.is_none() {
+            self.index = self.list.len;
+        }
+        Some(value)
+    }
+
+    /// Moves all of `other` after the current element, or to the front on
+    /// the ghost, in O(1).
+    pub fn splice_after(&mut self, other: DoublyLinkedList<T>) {
+        let added = other.len;
+        let next = self.next_node();
+        // SAFETY: `current` and its successor are adjacent
+        unsafe { self.list.splice_between(other, self.current, next) };
+        if self.current.is_none() {
+            self.index += added;
+        }
+    }
+
+    /// Moves all of `other` before the current element, or to the back on
+    /// the ghost, in O(1).
+    pub fn splice_before(&mut self, other: DoublyLinkedList<T>) {
+        let added = other.len;
+        let prev = self.prev_node();
+        // SAFETY: `current` and its predecessor are adjacent
+        unsafe { self.list.splice_between(other, prev, self.current) };
+        self.index += added;
+    }
+
+    /// Detaches everything after the current element into a new list, or
+    /// the whole list on the ghost.
+    pub fn split_after(&mut self) -> DoublyLinkedList<T> {
+        let kept = self.current.map_or(0, |_| self.index + 1);
+        // SAFETY: `current` is at index `kept - 1`, or `None` with `kept == 0`
+        let rest = unsafe { self.list.split_after_node(self.current, kept) };
+        if self.current.is_none() {
+            self.index = 0;
+        }
+        rest
+    }
+
+    /// Detaches everything before the current element into a new list, or
+    /// the whole list on the ghost. The cursor keeps its element, now at
+    /// index 0.
+    pub fn split_before(&mut self) -> DoublyLinkedList<T> {
+        let prev = self.prev_node();
+        let kept = self.current.map_or(self.list.len, |_| self.index);
+        // SAFETY: `prev` is at index `kept - 1`, or `None` with `kept == 0`
+        let rest = unsafe { self.list.split_after_node(prev, kept) };
+        self.index = 0;
+        mem::replace(self.list, rest)
+    }
+
+    fn next_node(&self) -> Link<T> {
+        match self.current {
+            // SAFETY: `current` is a live node of the list
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        }
+    }
+
+    fn prev_node(&self) -> Link<T> {
+        match self.current {
+            // SAFETY: `current` is a live node of the list
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail,
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: `len` nodes remain between `head` and `tail`, all borrowed for `'a`
+        self.head.map(|node| unsafe {
+            self.len -= 1;
+            self.head = (*node.as_ptr()).next;
+            &(*node.as_ptr()).value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: as in `next`
+        self.tail.map(|node| unsafe {
+            self.len -= 1;
+            self.tail = (*node.as_ptr()).prev;
+            &(*node.as_ptr()).value
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: `len` counts the nodes not yet yielded from either end, so
+        // each node is handed out at most once
+        self.head.map(|node| unsafe {
+            self.len -= 1;
+            self.head = (*node.as_ptr()).next;
+            &mut (*node.as_ptr()).value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: as in `next`
+        self.tail.map(|node| unsafe {
+            self.len -= 1;
+            self.tail = (*node.as_ptr()).prev;
+            &mut (*node.as_ptr()).value
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T> {
+    list: DoublyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_stack() {
+        let mut stack = MinStack::new();
+        assert_eq!(stack.min(), None);
+        for x in [5, 3, 7, 3, 8, 1] {
+            stack.push(x);
+        }
+        assert_eq!(stack.min(), Some(&1));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.min(), Some(&3));
+        stack.pop();
+        stack.pop();
+        // The duplicate 3 is still on the stack
+        assert_eq!(stack.min(), Some(&3));
+        stack.pop();
+        stack.pop();
+        assert_eq!(stack.min(), Some(&5));
+        assert_eq!(stack.peek(), Some(&5));
+        assert_eq!(stack.length(), 1);
+    }
+
+    #[test]
+    fn test_max_stack() {
+        let mut stack: MaxStack<String> = ["b", "d", "a", "d"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(stack.max().map(String::as_str), Some("d"));
+        stack.pop();
+        assert_eq!(stack.max().map(String::as_str), Some("d"));
+        stack.pop();
+        stack.pop();
+        assert_eq!(stack.max().map(String::as_str), Some("b"));
+        stack.clear();
+        assert!(stack.is_empty());
+        assert_eq!(stack.max(), None);
+    }
+}
+
+/// A stack that also tracks the indices of its running extremes.
+///
+/// `extremes` holds the positions of every item that was at least as extreme
+/// as everything below it when pushed, so the current extreme is always the
+/// item at its last entry.
+#[derive(Debug, Clone)]
+struct TrackedStack<T> {
+    items: Vec<T>,
+    extremes: Vec<usize>,
+    // Returns true if the first argument may replace the second as the extreme
+    replaces: fn(&T, &T) -> bool,
+}
+
+impl<T> TrackedStack<T> {
+    fn new(replaces: fn(&T, &T) -> bool) -> Self {
+        TrackedStack { items: Vec::new(), extremes: Vec::new(), replaces }
+    }
+
+    fn push(&mut self, item: T) {
+        let replaces = match self.extremes.last() {
+            Some(&i) => (self.replaces)(&item, &self.items[i]),
+            None => true,
+        };
+        if replaces {
+            self.extremes.push(self.items.len());
+        }
+        self.items.push(item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let item = self.items.pop()?;
+        if self.extremes.last() == Some(&self.items.len()) {
+            self.extremes.pop();
+        }
+        Some(item)
+    }
+
+    fn extreme(&self) -> Option<&T> {
+        self.extremes.last().map(|&i| &self.items[i])
+    }
+
+    fn clear(&mut self) {
+        self.items.clear();
+        self.extremes.clear();
+    }
+}
+
+/// A stack with O(1) access to its smallest item.
+#[derive(Debug, Clone)]
+pub struct MinStack<T> {
+    stack: TrackedStack<T>,
+}
+
+impl<T: Ord> MinStack<T> {
+    pub fn new() -> Self {
+        MinStack { stack: TrackedStack::new(|item, min| item <= min) }
+    }
+    pub fn push(&mut self, item: T) {
+        self.stack.push(item)
+    }
+    pub fn pop(&mut self) -> Option<T> {
+        self.stack.pop()
+    }
+    pub fn peek(&self) -> Option<&T> {
+        self.stack.items.last()
+    }
+    /// Returns the smallest item on the stack.
+    pub fn min(&self) -> Option<&T> {
+        self.stack.extreme()
+    }
+    pub fn length(&self) -> usize {
+        self.stack.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.stack.items.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.stack.clear()
+    }
+    /// Iterates from the bottom of the stack to the top.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.stack.items.iter()
+    }
+}
+
+impl<T: Ord> Default for MinStack<T> {
+    fn default() -> Self {
+        MinStack::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for MinStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = MinStack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+/// A stack with O(1) access to its largest item.
+#[derive(Debug, Clone)]
+pub struct MaxStack<T> {
+    stack: TrackedStack<T>,
+}
+
+impl<T: Ord> MaxStack<T> {
+    pub fn new() -> Self {
+        MaxStack { stack: TrackedStack::new(|item, max| item >= max) }
+    }
+    pub fn push(&mut self, item: T) {
+        self.stack.push(item)
+    }
+    pub fn pop(&mut self) -> Option<T> {
+        self.stack.pop()
+    }
+    pub fn peek(&self) -> Option<&T> {
+        self.stack.items.last()
+    }
+    /// Returns the largest item on the stack.
+    pub fn max(&self) -> Option<&T> {
+        self.stack.extreme()
+    }
+    pub fn length(&self) -> usize {
+        self.stack.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.stack.items.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.stack.clear()
+    }
+    /// Iterates from the bottom of the stack to the top.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.stack.items.iter()
+    }
+}
+
+impl<T: Ord> Default for MaxStack<T> {
+    fn default() -> Self {
+        MaxStack::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for MaxStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MaxStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = MaxStack::new();
+        stack.extend(iter);
+        stack
+    }
+}
@@ -1,6 +1,17 @@
 pub mod queue;
+pub mod deque;
+pub mod bounded_queue;
+pub mod two_stack_queue;
+pub mod monotonic_queue;
+pub mod mpmc_queue;
+pub mod spsc_ring;
 pub mod stack;
+pub mod min_max_stack;
+pub mod bounded_stack;
 pub mod min_heap;
+pub mod singly_linked_list;
+pub mod doubly_linked_list;
 pub mod weighted_adj_list;
 pub mod weighted_adj_matrix;
 pub mod ring_buffer;
+pub mod csr_graph;
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_queue() {
+        let mut queue = MaxQueue::new();
+        for x in [1, 3, 2] {
+            queue.push(x);
+        }
+        assert_eq!(queue.extreme(), Some(&3));
+        assert_eq!(queue.length(), 3);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![3, 2]);
+        queue.pop_front();
+        queue.pop_front();
+        assert_eq!(queue.extreme(), Some(&2));
+        queue.pop_front();
+        assert_eq!(queue.extreme(), None);
+        assert!(queue.is_empty());
+        queue.pop_front();
+        assert_eq!(queue.length(), 0);
+    }
+
+    #[test]
+    fn test_min_queue_keeps_equal_values() {
+        let mut queue: MinQueue<i32> = [2, 1, 1].into_iter().collect();
+        queue.pop_front();
+        queue.pop_front();
+        assert_eq!(queue.extreme(), Some(&1));
+        queue.extend([5]);
+        queue.pop_front();
+        assert_eq!(queue.extreme(), Some(&5));
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let values = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(sliding_window_max(&values, 3), vec![3, 3, 5, 5, 6, 7]);
+        assert_eq!(sliding_window_min(&values, 3), vec![-1, -3, -3, -3, 3, 3]);
+        assert_eq!(sliding_window_max(&values, 1), values.to_vec());
+        assert!(sliding_window_max(&values, 9).is_empty());
+        assert!(sliding_window_max(&values, 0).is_empty());
+    }
+}
+
+/// Which extreme a `MonotonicQueue` tracks.
+pub trait Extreme {
+    /// Returns true if `new` makes `old` useless as a future extreme.
+    fn dominates<T: Ord>(new: &T, old: &T) -> bool;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Min;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Max;
+
+impl Extreme for Min {
+    fn dominates<T: Ord>(new: &T, old: &T) -> bool {
+        new < old
+    }
+}
+
+impl Extreme for Max {
+    fn dominates<T: Ord>(new: &T, old: &T) -> bool {
+        new > old
+    }
+}
+
+/// A FIFO queue with O(1) access to its minimum or maximum.
+///
+/// Only the items that can still become the extreme are stored: pushing an
+/// item evicts the older items it dominates, so the stored candidates are
+/// monotonic and the extreme is at the front. Each item is stored and evicted
+/// at most once, making `push` amortised O(1). `length` still counts every
+/// pushed item that has not been popped.
+#[derive(Debug, Clone)]
+pub struct MonotonicQueue<T, E> {
+    // Candidates tagged with their sequence number
+    candidates: VecDeque<(usize, T)>,
+    // Sequence numbers of the front item and of the next push
+    head: usize,
+    tail: usize,
+    extreme: PhantomData<E>,
+}
+
+pub type MinQueue<T> = MonotonicQueue<T, Min>;
+pub type MaxQueue<T> = MonotonicQueue<T, Max>;
+
+impl<T: Ord, E: Extreme> MonotonicQueue<T, E> {
+    pub fn new() -> Self {
+        MonotonicQueue { candidates: VecDeque::new(), head: 0, tail: 0, extreme: PhantomData }
+    }
+
+    pub fn length(&self) -> usize {
+        self.tail - self.head
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head == self.tail
+    }
+
+    pub fn push(&mut self, item: T) {
+        while let Some((_, last)) = self.candidates.back() {
+            if !E::dominates(&item, last) {
+                break;
+            }
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back((self.tail, item));
+        self.tail += 1;
+    }
+
+    /// Removes the front item, returning it if it was still a candidate.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let front = self.head;
+        self.head += 1;
+        match self.candidates.front() {
+            Some(&(sequence, _)) if sequence == front => self.candidates.pop_front().map(|(_, item)| item),
+            _ => None,
+        }
+    }
+
+    /// Returns the minimum or maximum of the queued items.
+    pub fn extreme(&self) -> Option<&T> {
+        self.candidates.front().map(|(_, item)| item)
+    }
+
+    /// Iterates over the stored candidates, from the current extreme to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.candidates.iter().map(|(_, item)| item)
+    }
+}
+
+impl<T: Ord, E: Extreme> Default for MonotonicQueue<T, E> {
+    fn default() -> Self {
+        MonotonicQueue::new()
+    }
+}
+
+impl<T: Ord, E: Extreme> Extend<T> for MonotonicQueue<T, E> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: Ord, E: Extreme> FromIterator<T> for MonotonicQueue<T, E> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = MonotonicQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+/// Consumes the queue, yielding the stored candidates.
+impl<T, E> IntoIterator for MonotonicQueue<T, E> {
+    type Item = T;
+    type IntoIter = std::iter::Map<std::collections::vec_deque::IntoIter<(usize, T)>, fn((usize, T)) -> T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.candidates.into_iter().map(|(_, item)| item)
+    }
+}
+
+fn sliding_window<T: Ord + Clone, E: Extreme>(values: &[T], window: usize) -> Vec<T> {
+    if window == 0 || window > values.len() {
+        return Vec::new();
+    }
+    let mut queue: MonotonicQueue<T, E> = MonotonicQueue::new();
+    let mut extremes = Vec::with_capacity(values.len() + 1 - window);
+    for (i, value) in values.iter().enumerate() {
+        queue.push(value.clone());
+        if i >= window {
+            queue.pop_front();
+        }
+        if i + 1 >= window {
+            extremes.push(queue.extreme().expect("window is not empty").clone());
+        }
+    }
+    extremes
+}
+
+/// Returns the maximum of every window of `window` consecutive values, O(n).
+pub fn sliding_window_max<T: Ord + Clone>(values: &[T], window: usize) -> Vec<T> {
+    sliding_window::<T, Max>(values, window)
+}
+
+/// Returns the minimum of every window of `window` consecutive values, O(n).
+pub fn sliding_window_min<T: Ord + Clone>(values: &[T], window: usize) -> Vec<T> {
+    sliding_window::<T, Min>(values, window)
+}
@@ -0,0 +1,443 @@
+#[cfg(loom)]
+use loom::sync::{Condvar, Mutex, MutexGuard};
+use std::collections::VecDeque;
+use std::fmt;
+#[cfg(not(loom))]
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    /// Yields at pseudo-random points so the scheduler explores more
+    /// interleavings than a straight run would.
+    fn jitter(state: &mut u32) {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        if state.is_multiple_of(4) {
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn test_send_and_recv() {
+        let queue = MpmcQueue::unbounded();
+        queue.send(1).unwrap();
+        queue.send(2).unwrap();
+        assert_eq!(queue.length(), 2);
+        assert_eq!(queue.recv(), Ok(1));
+        assert_eq!(queue.try_recv(), Ok(2));
+        assert_eq!(queue.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(
+            queue.recv_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_close_drains_then_disconnects() {
+        let queue = MpmcQueue::bounded(4);
+        queue.send("a").unwrap();
+        queue.close();
+        assert!(queue.is_closed());
+        assert_eq!(queue.send("b"), Err(SendError("b")));
+        assert_eq!(queue.try_send("b"), Err(TrySendError::Closed("b")));
+        assert_eq!(queue.recv(), Ok("a"));
+        assert_eq!(queue.recv(), Err(RecvError));
+        assert_eq!(queue.try_recv(), Err(TryRecvError::Closed));
+        assert_eq!(queue.recv_timeout(Duration::from_secs(1)), Err(RecvTimeoutError::Closed));
+    }
+
+    #[test]
+    fn test_recv_timeout_without_deadline() {
+        let queue = Arc::new(MpmcQueue::unbounded());
+        let sender = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                queue.send(7).unwrap();
+                queue.close();
+            })
+        };
+        assert_eq!(queue.recv_timeout(Duration::MAX), Ok(7));
+        assert_eq!(queue.recv_timeout(Duration::MAX), Err(RecvTimeoutError::Closed));
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn test_close_wakes_blocked_threads() {
+        let queue: Arc<MpmcQueue<u8>> = Arc::new(MpmcQueue::bounded(1));
+        let receivers: Vec<_> = (0..3)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || queue.recv())
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(20));
+        queue.close();
+        for receiver in receivers {
+            assert_eq!(receiver.join().unwrap(), Err(RecvError));
+        }
+    }
+
+    #[test]
+    fn test_bounded_try_send() {
+        let queue = MpmcQueue::bounded(1);
+        assert_eq!(queue.try_send(1), Ok(()));
+        assert_eq!(queue.try_send(2), Err(TrySendError::Full(2)));
+        assert_eq!(queue.capacity(), Some(1));
+        assert_eq!(TrySendError::Full(2).to_string(), "queue is full");
+    }
+
+    #[test]
+    fn test_stress_many_producers_and_consumers() {
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const PER_PRODUCER: usize = 5_000;
+        let queue = Arc::new(MpmcQueue::bounded(8));
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut state = 0x9e37_79b9 ^ (p as u32 + 1);
+                    for i in 0..PER_PRODUCER {
+                        jitter(&mut state);
+                        queue.send(p * PER_PRODUCER + i).unwrap();
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|c| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut state = 0x85eb_ca6b ^ (c as u32 + 1);
+                    let mut received = Vec::new();
+                    while let Ok(item) = queue.recv() {
+                        jitter(&mut state);
+                        received.push(item);
+                    }
+                    received
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        queue.close();
+        let mut all: Vec<usize> = Vec::new();
+        for consumer in consumers {
+            let received = consumer.join().unwrap();
+            // Items from one producer reach any single consumer in send order
+            for p in 0..PRODUCERS {
+                let from_p: Vec<_> = received.iter().filter(|&&x| x / PER_PRODUCER == p).collect();
+                assert!(from_p.windows(2).all(|w| w[0] < w[1]));
+            }
+            all.extend(received);
+        }
+        all.sort_unstable();
+        assert_eq!(all, (0..PRODUCERS * PER_PRODUCER).collect::<Vec<_>>());
+    }
+}
+
+/// Model checks of every interleaving `loom` can reach for small runs. Run
+/// with `RUSTFLAGS="--cfg loom" cargo test --release mpmc_queue`.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn loom_send_recv_and_close_deliver_in_order() {
+        loom::model(|| {
+            let queue = Arc::new(MpmcQueue::bounded(1));
+            let sender = {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    queue.send(0).unwrap();
+                    queue.send(1).unwrap();
+                    queue.close();
+                })
+            };
+            let mut received = Vec::new();
+            while let Ok(item) = queue.recv() {
+                received.push(item);
+            }
+            sender.join().unwrap();
+            assert_eq!(received, vec![0, 1]);
+            assert_eq!(queue.try_recv(), Err(TryRecvError::Closed));
+        });
+    }
+
+    #[test]
+    fn loom_close_wakes_blocked_receiver() {
+        loom::model(|| {
+            let queue: Arc<MpmcQueue<u8>> = Arc::new(MpmcQueue::unbounded());
+            let receiver = {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || queue.recv())
+            };
+            queue.close();
+            assert_eq!(receiver.join().unwrap(), Err(RecvError));
+        });
+    }
+
+    #[test]
+    fn loom_close_wakes_blocked_sender() {
+        loom::model(|| {
+            let queue = Arc::new(MpmcQueue::bounded(1));
+            queue.send(0).unwrap();
+            let sender = {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || queue.send(1))
+            };
+            queue.close();
+            assert_eq!(sender.join().unwrap(), Err(SendError(1)));
+            assert_eq!(queue.recv(), Ok(0));
+            assert_eq!(queue.recv(), Err(RecvError));
+        });
+    }
+}
+
+/// Returned by `send` on a closed queue, with the unsent item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// Returned by `try_send`, with the unsent item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    Full(T),
+    Closed(T),
+}
+
+/// Returned by `recv` once the queue is closed and drained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    Timeout,
+    Closed,
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sending on a closed queue")
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "queue is full"),
+            TrySendError::Closed(_) => write!(f, "sending on a closed queue"),
+        }
+    }
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "receiving on a closed and empty queue")
+    }
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "queue is empty"),
+            TryRecvError::Closed => write!(f, "receiving on a closed and empty queue"),
+        }
+    }
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => write!(f, "timed out waiting on the queue"),
+            RecvTimeoutError::Closed => write!(f, "receiving on a closed and empty queue"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for SendError<T> {}
+impl<T: fmt::Debug> std::error::Error for TrySendError<T> {}
+impl std::error::Error for RecvError {}
+impl std::error::Error for TryRecvError {}
+impl std::error::Error for RecvTimeoutError {}
+
+#[derive(Debug)]
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+}
+
+/// A blocking multi-producer, multi-consumer FIFO queue.
+///
+/// Share it between threads with an `Arc`. A bounded queue makes `send` wait
+/// while it is full. After `close`, sends fail and receivers drain what is
+/// left before getting a "closed" error.
+#[derive(Debug)]
+pub struct MpmcQueue<T> {
+    state: Mutex<State<T>>,
+    capacity: Option<usize>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T> MpmcQueue<T> {
+    pub fn unbounded() -> Self {
+        Self::with_capacity(None)
+    }
+
+    /// Creates a queue holding at most `capacity` items, at least one.
+    pub fn bounded(capacity: usize) -> Self {
+        Self::with_capacity(Some(capacity.max(1)))
+    }
+
+    fn with_capacity(capacity: Option<usize>) -> Self {
+        MpmcQueue {
+            state: Mutex::new(State { items: VecDeque::new(), closed: false }),
+            capacity,
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn is_full(&self, state: &State<T>) -> bool {
+        self.capacity.is_some_and(|capacity| state.items.len() >= capacity)
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    pub fn length(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Closes the queue and wakes every waiting sender and receiver.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// Appends `item`, waiting for space if the queue is bounded and full.
+    ///
+    /// # Errors
+    ///
+    /// Returns the item if the queue is closed, including while waiting.
+    pub fn send(&self, item: T) -> Result<(), SendError<T>> {
+        let mut state = self.lock();
+        while !state.closed && self.is_full(&state) {
+            state = self.not_full.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        if state.closed {
+            return Err(SendError(item));
+        }
+        state.items.push_back(item);
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Appends `item` without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns the item if the queue is full or closed.
+    pub fn try_send(&self, item: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(TrySendError::Closed(item));
+        }
+        if self.is_full(&state) {
+            return Err(TrySendError::Full(item));
+        }
+        state.items.push_back(item);
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    fn take(&self, mut state: MutexGuard<'_, State<T>>) -> Option<T> {
+        let item = state.items.pop_front()?;
+        drop(state);
+        self.not_full.notify_one();
+        Some(item)
+    }
+
+    /// Removes the front item, waiting for one if the queue is empty.
+    ///
+    /// # Errors
+    ///
+    /// Fails once the queue is closed and empty.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.lock();
+        while state.items.is_empty() && !state.closed {
+            state = self.not_empty.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        self.take(state).ok_or(RecvError)
+    }
+
+    /// Removes the front item without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the queue is empty, distinguishing a closed queue.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let state = self.lock();
+        let closed = state.closed;
+        self.take(state).ok_or(if closed { TryRecvError::Closed } else { TryRecvError::Empty })
+    }
+
+    /// Removes the front item, waiting at most `timeout` for one to arrive.
+    /// A timeout too large to represent as a deadline, such as
+    /// `Duration::MAX`, waits like `recv`.
+    ///
+    /// # Errors
+    ///
+    /// Fails on timeout, or once the queue is closed and empty.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            return self.recv().map_err(|RecvError| RecvTimeoutError::Closed);
+        };
+        let mut state = self.lock();
+        while state.items.is_empty() && !state.closed {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+            state = self
+                .not_empty
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+        self.take(state).ok_or(RecvTimeoutError::Closed)
+    }
+}
@@ -1,6 +1,46 @@
 use std::collections::VecDeque;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-#[derive(Debug)]
+    #[test]
+    fn test_enqueue_dequeue() {
+        let mut queue = Queue::new();
+        queue.enqueue("a");
+        queue.enqueue("b");
+        assert_eq!(queue.peek(), Some(&"a"));
+        assert_eq!(queue.length(), 2);
+        assert_eq!(queue.dequeue(), Some("a"));
+        assert_eq!(queue.dequeue(), Some("b"));
+        assert_eq!(queue.dequeue(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_iterators_and_collect() {
+        let mut queue: Queue<i32> = (1..=3).collect();
+        queue.extend([4, 5]);
+        for item in queue.iter_mut() {
+            *item += 1;
+        }
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6]);
+        assert_eq!((&queue).into_iter().count(), 5);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_capacity_and_clear() {
+        let mut queue: Queue<u8> = Queue::with_capacity(8);
+        assert!(queue.capacity() >= 8);
+        queue.enqueue(1);
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(Queue::<u8>::default().is_empty());
+    }
+}
+
+/// A first-in, first-out queue. Iteration runs from front to back.
+#[derive(Debug, Clone)]
 pub struct Queue<T> {
     queue: VecDeque<T>,
 }
@@ -10,10 +50,18 @@ impl<T> Queue<T> {
         Queue { queue: VecDeque::new() }
     }
 
+    pub fn with_capacity(capacity: usize) -> Self {
+        Queue { queue: VecDeque::with_capacity(capacity) }
+    }
+
     pub fn length(&self) -> usize {
         self.queue.len()
     }
 
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
     pub fn enqueue(&mut self, item: T) {
         self.queue.push_back(item);
     }
@@ -29,4 +77,53 @@ impl<T> Queue<T> {
     pub fn peek(&self) -> Option<&T> {
         self.queue.front()
     }
+
+    pub fn clear(&mut self) {
+        self.queue.clear()
+    }
+
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.queue.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::collections::vec_deque::IterMut<'_, T> {
+        self.queue.iter_mut()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = std::collections::vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.queue.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Enqueues the items in order.
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.queue.extend(iter)
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Queue { queue: VecDeque::from_iter(iter) }
+    }
 }
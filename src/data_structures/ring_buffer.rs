@@ -1,4 +1,5 @@
-#[cfg(test)]
+use std::fmt;
+use std::mem::MaybeUninit;
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,62 +51,446 @@ mod tests {
         assert!(buffer.is_empty());
     }
 
+    #[test]
+    fn test_full_buffer_reports_its_length() {
+        let mut buffer = RingBuffer::new(3);
+        for i in 0..3 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer.len(), 3);
+        assert!(buffer.is_full());
+        assert!(!buffer.is_empty());
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut buffer = RingBuffer::new(0);
+        assert_eq!(buffer.push(1), Some(1));
+        assert_eq!(buffer.pop(), None);
+        assert_eq!(buffer.peek(), None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let mut buffer = RingBuffer::new(2);
+        assert_eq!(buffer.policy(), OverflowPolicy::Overwrite);
+        buffer.push(1);
+        buffer.push(2);
+        assert_eq!(buffer.push(3), Some(1));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+
+        let mut buffer = RingBuffer::with_policy(2, OverflowPolicy::Reject);
+        buffer.push(1);
+        buffer.push(2);
+        assert_eq!(buffer.push(3), Some(3));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ring buffer is full")]
+    fn test_panic_policy() {
+        let mut buffer = RingBuffer::with_policy(1, OverflowPolicy::Panic);
+        buffer.push(1);
+        buffer.push(2);
+    }
+
+    #[test]
+    fn test_slices_and_indexing() {
+        let mut buffer = RingBuffer::new(4);
+        buffer.extend_from_slice(&[1, 2, 3, 4]);
+        buffer.pop();
+        buffer.pop();
+        buffer.extend_from_slice(&[5, 6]);
+        assert_eq!(buffer.as_slices(), (&[3, 4][..], &[5, 6][..]));
+        assert_eq!(buffer.get(2), Some(&5));
+        assert_eq!(buffer.get(4), None);
+        for item in buffer.iter_mut() {
+            *item *= 10;
+        }
+        *buffer.get_mut(0).unwrap() += 1;
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![31, 40, 50, 60]);
+        let (front, back) = buffer.as_mut_slices();
+        front[1] = 0;
+        back[1] = 0;
+        assert_eq!((&buffer).into_iter().copied().collect::<Vec<_>>(), vec![31, 0, 50, 0]);
+    }
+
+    #[test]
+    fn test_drain_and_rotate() {
+        let mut buffer: RingBuffer<i32> = RingBuffer::new(5);
+        buffer.extend(1..=5);
+        buffer.rotate_left(2);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 1, 2]);
+        buffer.rotate_right(1);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5, 1]);
+        buffer.pop();
+        buffer.rotate_left(6);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![5, 1, 3, 4]);
+        assert_eq!(buffer.drain().take(2).collect::<Vec<_>>(), vec![5, 1]);
+        // Dropping the drain empties the buffer
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_reserve_keeps_order() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.extend_from_slice(&["a", "b", "c", "d"]);
+        buffer.reserve(2);
+        assert_eq!(buffer.capacity(), 5);
+        buffer.push("e");
+        buffer.push("f");
+        assert_eq!(buffer.push("g"), Some("b"));
+        assert_eq!(buffer.into_iter().collect::<Vec<_>>(), vec!["c", "d", "e", "f", "g"]);
+    }
+
+    #[test]
+    fn test_items_are_dropped() {
+        let item = std::rc::Rc::new(());
+        let mut buffer = RingBuffer::new(3);
+        for _ in 0..5 {
+            buffer.push(std::rc::Rc::clone(&item));
+        }
+        assert_eq!(std::rc::Rc::strong_count(&item), 4);
+        let copy = buffer.clone();
+        assert_eq!(std::rc::Rc::strong_count(&item), 7);
+        drop(buffer);
+        drop(copy);
+        assert_eq!(std::rc::Rc::strong_count(&item), 1);
+    }
+}
+
+/// What `RingBuffer::push` does when the buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Evict the oldest item to make room and return it.
+    #[default]
+    Overwrite,
+    /// Leave the buffer unchanged and return the new item.
+    Reject,
+    /// Panic.
+    Panic,
 }
 
+/// A fixed-capacity FIFO buffer stored in a circular array.
+///
+/// The items occupy `len` slots starting at `read_pos`, wrapping around the
+/// end of the array; the next write goes `len` slots after `read_pos`.
+/// Keeping the count explicit distinguishes a full buffer from an empty one.
+/// Iteration runs from the oldest item to the newest.
 pub struct RingBuffer<T> {
-    buffer: Vec<Option<T>>,
-    capacity: usize,
+    buffer: Box<[MaybeUninit<T>]>,
     read_pos: usize,
-    write_pos: usize,
+    len: usize,
+    policy: OverflowPolicy,
 }
 
 impl<T> RingBuffer<T> {
+    /// Creates a buffer that overwrites its oldest item when full.
     pub fn new(capacity: usize) -> Self {
-        let mut buffer = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            buffer.push(None);
-        }
+        Self::with_policy(capacity, OverflowPolicy::Overwrite)
+    }
+
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
         RingBuffer {
-            buffer,
-            capacity,
+            buffer: (0..capacity).map(|_| MaybeUninit::uninit()).collect(),
             read_pos: 0,
-            write_pos: 0,
+            len: 0,
+            policy,
         }
     }
-    pub fn push(&mut self, item: T) {
-        self.buffer[self.write_pos] = Some(item);
-        if self.len() == self.capacity {
-            self.read_pos = (self.read_pos + 1) % self.capacity; // Move read position if the buffer is full
+
+    /// Returns the array slot of the `index`-th item. Requires `capacity() > 0`.
+    fn slot(&self, index: usize) -> usize {
+        (self.read_pos + index) % self.buffer.len()
+    }
+
+    /// Appends `item`, applying the overflow policy when the buffer is full.
+    ///
+    /// Returns the evicted oldest item under `Overwrite` and the rejected
+    /// new item under `Reject`; `None` when there was room. A zero-capacity
+    /// buffer hands every item straight back.
+    ///
+    /// # Panics
+    ///
+    /// Panics under `OverflowPolicy::Panic` when the buffer is full.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if self.len < self.buffer.len() {
+            let slot = self.slot(self.len);
+            self.buffer[slot].write(item);
+            self.len += 1;
+            return None;
+        }
+        match self.policy {
+            OverflowPolicy::Panic => panic!("ring buffer is full (capacity {})", self.buffer.len()),
+            OverflowPolicy::Reject => Some(item),
+            OverflowPolicy::Overwrite if self.buffer.is_empty() => Some(item),
+            OverflowPolicy::Overwrite => {
+                // The oldest slot is also the next write slot
+                let slot = self.read_pos;
+                // SAFETY: the buffer is full, so every slot is initialised
+                let evicted = unsafe { std::mem::replace(&mut self.buffer[slot], MaybeUninit::new(item)).assume_init() };
+                self.read_pos = (self.read_pos + 1) % self.buffer.len();
+                Some(evicted)
+            }
         }
-        self.write_pos = (self.write_pos + 1) % self.capacity;
     }
 
     pub fn pop(&mut self) -> Option<T> {
-        if self.read_pos == self.write_pos {
+        if self.len == 0 {
             return None; // Buffer is empty
         }
-        let item = self.buffer[self.read_pos].take();
-        self.read_pos = (self.read_pos + 1) % self.capacity;
-        item
+        // SAFETY: read_pos holds the oldest initialised item, and moving
+        // read_pos past it below marks the slot uninitialised again
+        let item = unsafe { self.buffer[self.read_pos].assume_init_read() };
+        self.read_pos = (self.read_pos + 1) % self.buffer.len();
+        self.len -= 1;
+        Some(item)
     }
 
     pub fn peek(&self) -> Option<&T> {
-        self.buffer[self.read_pos].as_ref().map(|item| item)
+        self.get(0)
+    }
+
+    /// Returns the `index`-th oldest item.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: slots of the first `len` items are initialised
+        Some(unsafe { self.buffer[self.slot(index)].assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let slot = self.slot(index);
+        // SAFETY: slots of the first `len` items are initialised
+        Some(unsafe { self.buffer[slot].assume_init_mut() })
     }
 
     pub fn is_empty(&self) -> bool {
-        self.read_pos == self.write_pos
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.buffer.len()
     }
 
     pub fn len(&self) -> usize {
-        if self.read_pos <= self.write_pos {
-            self.write_pos - self.read_pos
+        self.len
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: OverflowPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the array ranges holding the items, oldest first.
+    fn ranges(&self) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let end = self.read_pos + self.len;
+        if end <= self.buffer.len() {
+            (self.read_pos..end, 0..0)
         } else {
-            self.capacity - self.read_pos + self.write_pos
+            (self.read_pos..self.buffer.len(), 0..end - self.buffer.len())
         }
     }
 
-    pub fn capacity(&self) -> usize {
-        self.capacity
+    /// Returns the items as two contiguous slices, oldest first. The second
+    /// slice is empty unless the items wrap around the end of the array.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.ranges();
+        // SAFETY: both ranges cover initialised slots only, and
+        // MaybeUninit<T> has the same layout as T
+        unsafe {
+            (
+                &*(&self.buffer[front] as *const [MaybeUninit<T>] as *const [T]),
+                &*(&self.buffer[back] as *const [MaybeUninit<T>] as *const [T]),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.ranges();
+        // The back range ends before the front range starts, so they do not overlap
+        let (head, tail) = self.buffer.split_at_mut(front.start);
+        // SAFETY: as in `as_slices`
+        unsafe {
+            (
+                &mut *(&mut tail[..front.len()] as *mut [MaybeUninit<T>] as *mut [T]),
+                &mut *(&mut head[back] as *mut [MaybeUninit<T>] as *mut [T]),
+            )
+        }
+    }
+
+    pub fn iter(&self) -> std::iter::Chain<std::slice::Iter<'_, T>, std::slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> std::iter::Chain<std::slice::IterMut<'_, T>, std::slice::IterMut<'_, T>> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back.iter_mut())
+    }
+
+    /// Removes every item, oldest first. Items not consumed by the time the
+    /// iterator is dropped are dropped too.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { buffer: self }
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Moves the `n` oldest items to the back, wrapping `n` around `len()`.
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let n = n % self.len;
+        if self.is_full() {
+            // Every slot is occupied, so moving the read position is enough
+            self.read_pos = self.slot(n);
+            return;
+        }
+        for _ in 0..n {
+            let item = self.pop().expect("buffer is not empty");
+            let slot = self.slot(self.len);
+            self.buffer[slot].write(item);
+            self.len += 1;
+        }
+    }
+
+    /// Moves the `n` newest items to the front, wrapping `n` around `len()`.
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.len > 0 {
+            self.rotate_left(self.len - n % self.len);
+        }
+    }
+
+    /// Grows the capacity by `additional` slots, keeping the items in order.
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = self.buffer.len() + additional;
+        let mut buffer: Box<[MaybeUninit<T>]> = (0..capacity).map(|_| MaybeUninit::uninit()).collect();
+        let len = self.len;
+        for slot in buffer.iter_mut().take(len) {
+            slot.write(self.pop().expect("len items are queued"));
+        }
+        self.buffer = buffer;
+        self.read_pos = 0;
+        self.len = len;
+    }
+}
+
+impl<T: Clone> RingBuffer<T> {
+    /// Pushes clones of `items` in order under the overflow policy; evicted
+    /// and rejected items are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics under `OverflowPolicy::Panic` if the items do not fit.
+    pub fn extend_from_slice(&mut self, items: &[T]) {
+        for item in items {
+            self.push(item.clone());
+        }
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut copy = RingBuffer::with_policy(self.capacity(), self.policy);
+        for item in self.iter() {
+            copy.push(item.clone());
+        }
+        copy
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Pushes the items in order under the overflow policy; evicted and
+/// rejected items are dropped.
+impl<T> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { buffer: self }
+    }
+}
+
+/// An iterator that pops every item of a `RingBuffer`, see `RingBuffer::drain`.
+pub struct Drain<'a, T> {
+    buffer: &'a mut RingBuffer<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buffer.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.len, Some(self.buffer.len))
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+/// An owning iterator over the items of a `RingBuffer`, oldest first.
+pub struct IntoIter<T> {
+    buffer: RingBuffer<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buffer.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.len, Some(self.buffer.len))
     }
 }
@@ -0,0 +1,407 @@
+use std::fmt;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[i32]) -> SinglyLinkedList<i32> {
+        values.iter().copied().collect()
+    }
+
+    fn values(list: &SinglyLinkedList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_push_pop_peek() {
+        let mut list = SinglyLinkedList::new();
+        list.push_front(String::from("b"));
+        list.push_front(String::from("a"));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.peek().map(String::as_str), Some("a"));
+        list.peek_mut().unwrap().push('!');
+        assert_eq!(list.pop_front().as_deref(), Some("a!"));
+        assert_eq!(list.pop_front().as_deref(), Some("b"));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_collect_extend_and_iterate() {
+        let mut list = list(&[1, 2, 3]);
+        list.extend([4, 5]);
+        for value in list.iter_mut() {
+            *value *= 2;
+        }
+        assert_eq!(values(&list), vec![2, 4, 6, 8, 10]);
+        assert_eq!(format!("{:?}", list), "[2, 4, 6, 8, 10]");
+        assert_eq!(list.clone(), list);
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![10, 8, 6, 4, 2]);
+    }
+
+    #[test]
+    fn test_into_iter_from_both_ends() {
+        let mut iter = list(&[1, 2, 3, 4, 5, 6]).into_iter();
+        assert_eq!((iter.next_back(), iter.next(), iter.next_back()), (Some(6), Some(1), Some(5)));
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!((iter.next(), iter.next(), iter.next()), (Some(2), Some(3), Some(4)));
+        assert_eq!((iter.next(), iter.next_back()), (None, None));
+
+        // Alternating ends over a long list stays linear overall
+        let mut iter: IntoIter<u32> = (0..200_000).collect::<SinglyLinkedList<_>>().into_iter();
+        for i in 0..100_000 {
+            assert_eq!((iter.next_back(), iter.next()), (Some(199_999 - i), Some(i)));
+        }
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut list = list(&[1, 2, 3]);
+        list.reverse();
+        assert_eq!(values(&list), vec![3, 2, 1]);
+        list.push_front(4);
+        assert_eq!(values(&list), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let merged = list(&[1, 4, 6]).merge_sorted(list(&[2, 3, 6, 9]));
+        assert_eq!(values(&merged), vec![1, 2, 3, 4, 6, 6, 9]);
+        assert_eq!(merged.len(), 7);
+        assert_eq!(values(&list(&[]).merge_sorted(list(&[1]))), vec![1]);
+    }
+
+    #[test]
+    fn test_reverse_in_groups() {
+        let mut list = list(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        list.reverse_in_groups(3);
+        assert_eq!(values(&list), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+        list.reverse_in_groups(1);
+        assert_eq!(values(&list), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+        list.reverse_in_groups(8);
+        assert_eq!(values(&list), vec![8, 7, 4, 5, 6, 1, 2, 3]);
+        assert_eq!(list.len(), 8);
+    }
+
+    #[test]
+    fn test_middle() {
+        assert_eq!(list(&[1, 2, 3, 4, 5]).middle(), Some(&3));
+        assert_eq!(list(&[1, 2, 3, 4]).middle(), Some(&3));
+        assert_eq!(list(&[7]).middle(), Some(&7));
+        assert_eq!(list(&[]).middle(), None);
+    }
+
+    #[test]
+    fn test_long_list_drops_without_recursion() {
+        let list: SinglyLinkedList<u32> = (0..200_000).collect();
+        assert_eq!(list.len(), 200_000);
+        drop(list);
+    }
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+/// A singly linked list of boxed nodes, used like a stack: O(1) push and pop
+/// at the front. Iteration runs from the front.
+pub struct SinglyLinkedList<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> SinglyLinkedList<T> {
+    pub fn new() -> Self {
+        SinglyLinkedList { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let next = self.head.take();
+        self.head = Some(Box::new(Node { value, next }));
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Reverses the list in place by relinking its nodes.
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut rest = self.head.take();
+        while let Some(mut node) = rest {
+            rest = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    /// Reverses each consecutive group of `k` nodes; a shorter final group
+    /// is left as it is. `k` of 0 or 1 changes nothing.
+    pub fn reverse_in_groups(&mut self, k: usize) {
+        if k < 2 {
+            return;
+        }
+        let mut rest = self.head.take();
+        let mut result: Link<T> = None;
+        let mut tail = &mut result;
+        let mut remaining = self.len;
+        while remaining >= k {
+            let mut group: Link<T> = None;
+            for _ in 0..k {
+                let mut node = rest.expect("at least k nodes remain");
+                rest = node.next.take();
+                node.next = group;
+                group = Some(node);
+            }
+            *tail = group;
+            while let Some(node) = tail {
+                tail = &mut node.next;
+            }
+            remaining -= k;
+        }
+        *tail = rest;
+        self.head = result;
+    }
+
+    /// Returns the middle element, the second of the two middles for an even
+    /// length, by advancing one pointer twice as fast as the other.
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = self.head.as_deref();
+        while let Some(next) = fast.and_then(|node| node.next.as_deref()) {
+            slow = slow.next.as_deref().expect("slow trails fast");
+            fast = next.next.as_deref();
+        }
+        Some(&slow.value)
+    }
+
+    /// Splits the list in two at `at`, returning the nodes from `at` on.
+    fn split_off(&mut self, at: usize) -> Self {
+        let mut link = &mut self.head;
+        for _ in 0..at {
+            link = &mut link.as_mut().expect("at is within the list").next;
+        }
+        let rest = SinglyLinkedList { head: link.take(), len: self.len - at };
+        self.len = at;
+        rest
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref(), len: self.len }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head.as_deref_mut(), len: self.len }
+    }
+}
+
+impl<T: Ord> SinglyLinkedList<T> {
+    /// Merges two sorted lists into one sorted list by relinking their nodes.
+    /// Equal elements from `self` come first.
+    pub fn merge_sorted(mut self, mut other: Self) -> Self {
+        let len = self.len + other.len;
+        let (mut a, mut b) = (self.head.take(), other.head.take());
+        let mut merged: Link<T> = None;
+        let mut tail = &mut merged;
+        loop {
+            let take_a = match (&a, &b) {
+                (Some(x), Some(y)) => x.value <= y.value,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            let source = if take_a { &mut a } else { &mut b };
+            let mut node = source.take().expect("checked above");
+            *source = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        SinglyLinkedList { head: merged, len }
+    }
+}
+
+impl<T> Drop for SinglyLinkedList<T> {
+    fn drop(&mut self) {
+        // Unlink iteratively; dropping the boxes recursively could overflow the stack
+        let mut rest = self.head.take();
+        while let Some(mut node) = rest {
+            rest = node.next.take();
+        }
+    }
+}
+
+impl<T> Default for SinglyLinkedList<T> {
+    fn default() -> Self {
+        SinglyLinkedList::new()
+    }
+}
+
+impl<T: Clone> Clone for SinglyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SinglyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for SinglyLinkedList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for SinglyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Appends the items at the back, in order.
+impl<T> Extend<T> for SinglyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut tail = &mut self.head;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        for value in iter {
+            tail = &mut tail.insert(Box::new(Node { value, next: None })).next;
+            self.len += 1;
+        }
+    }
+}
+
+/// Collects the items in order, so the first item ends up at the front.
+impl<T> FromIterator<T> for SinglyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SinglyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        self.len -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        self.len -= 1;
+        Some(&mut node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+/// Iterating from the back keeps the remaining tail reversed in a second
+/// list. Whichever end runs dry takes over half of the other end's nodes,
+/// so any mix of `next` and `next_back` is amortised O(1) per element.
+pub struct IntoIter<T> {
+    front: SinglyLinkedList<T>,
+    // The last elements, in reverse order
+    back: SinglyLinkedList<T>,
+}
+
+impl<T> IntoIter<T> {
+    /// Moves the later half of `from` into the empty `to`, reversed.
+    fn refill(from: &mut SinglyLinkedList<T>, to: &mut SinglyLinkedList<T>) {
+        *to = from.split_off(from.len / 2);
+        to.reverse();
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            IntoIter::refill(&mut self.back, &mut self.front);
+        }
+        self.front.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len + self.back.len;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.back.is_empty() {
+            IntoIter::refill(&mut self.front, &mut self.back);
+        }
+        self.back.pop_front()
+    }
+}
+
+impl<T> IntoIterator for SinglyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { front: self, back: SinglyLinkedList::new() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SinglyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
@@ -0,0 +1,306 @@
+#[cfg(loom)]
+use loom::cell::UnsafeCell;
+#[cfg(loom)]
+use loom::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(loom)]
+use loom::sync::Arc;
+#[cfg(not(loom))]
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+#[cfg(not(loom))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(loom))]
+use std::sync::Arc;
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::thread;
+
+    #[test]
+    fn test_push_and_pop() {
+        let (mut producer, mut consumer) = spsc_ring(2);
+        assert_eq!(consumer.pop(), None);
+        assert_eq!(producer.push(1), Ok(()));
+        assert_eq!(producer.push(2), Ok(()));
+        assert_eq!(producer.push(3), Err(3));
+        assert_eq!(producer.length(), 2);
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(producer.push(3), Ok(()));
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert!(consumer.is_empty());
+        assert_eq!(consumer.capacity(), 2);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let (mut producer, mut consumer) = spsc_ring(0);
+        assert_eq!(producer.push("x"), Err("x"));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_unread_items_are_dropped() {
+        let item = Rc::new(());
+        {
+            let (mut producer, mut consumer) = spsc_ring(4);
+            for _ in 0..3 {
+                producer.push(Rc::clone(&item)).unwrap();
+            }
+            consumer.pop();
+            assert_eq!(Rc::strong_count(&item), 3);
+        }
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn test_stress_preserves_order_across_threads() {
+        const ITEMS: usize = 200_000;
+        let (mut producer, mut consumer) = spsc_ring(16);
+        let sender = thread::spawn(move || {
+            let mut state: u32 = 0x2545_f491;
+            for i in 0..ITEMS {
+                let mut item = i;
+                while let Err(rejected) = producer.push(item) {
+                    item = rejected;
+                    thread::yield_now();
+                }
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                if state.is_multiple_of(64) {
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut expected = 0;
+        while expected < ITEMS {
+            match consumer.pop() {
+                Some(item) => {
+                    assert_eq!(item, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        sender.join().unwrap();
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_short_runs_on_one_slot() {
+        // Stress only: many tiny runs on a one-slot ring race the full/empty
+        // boundary under whatever schedules the OS picks. The exhaustive
+        // check of the same boundary is in `loom_tests`.
+        for round in 0..500 {
+            let (mut producer, mut consumer) = spsc_ring(1);
+            let sender = thread::spawn(move || {
+                for i in 0..3 {
+                    let mut item = (round, i);
+                    while let Err(rejected) = producer.push(item) {
+                        item = rejected;
+                        thread::yield_now();
+                    }
+                }
+            });
+            let mut received = Vec::new();
+            while received.len() < 3 {
+                match consumer.pop() {
+                    Some(item) => received.push(item),
+                    None => thread::yield_now(),
+                }
+            }
+            sender.join().unwrap();
+            assert_eq!(received, vec![(round, 0), (round, 1), (round, 2)]);
+        }
+    }
+}
+
+/// Model checks of every interleaving `loom` can reach for small runs. Run
+/// with `RUSTFLAGS="--cfg loom" cargo test --release spsc_ring`.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use loom::thread;
+
+    fn push_until_accepted(producer: &mut Producer<usize>, mut item: usize) {
+        while let Err(rejected) = producer.push(item) {
+            item = rejected;
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn loom_push_and_pop_preserve_order() {
+        loom::model(|| {
+            let (mut producer, mut consumer) = spsc_ring(1);
+            let sender = thread::spawn(move || {
+                for i in 0..3 {
+                    push_until_accepted(&mut producer, i);
+                }
+            });
+            let mut received = Vec::new();
+            while received.len() < 3 {
+                match consumer.pop() {
+                    Some(item) => received.push(item),
+                    None => thread::yield_now(),
+                }
+            }
+            sender.join().unwrap();
+            assert_eq!(received, vec![0, 1, 2]);
+        });
+    }
+
+    #[test]
+    fn loom_unread_items_are_dropped() {
+        loom::model(|| {
+            let (mut producer, mut consumer) = spsc_ring(2);
+            let sender = thread::spawn(move || {
+                push_until_accepted(&mut producer, 0);
+                push_until_accepted(&mut producer, 1);
+            });
+            let first = consumer.pop();
+            sender.join().unwrap();
+            assert!(first.is_none() || first == Some(0));
+            assert_eq!(consumer.length(), if first.is_some() { 1 } else { 2 });
+        });
+    }
+}
+
+struct Shared<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    // Free-running counters; slot index is position % capacity
+    read_pos: AtomicUsize,
+    write_pos: AtomicUsize,
+}
+
+// SAFETY: a slot is written only by the producer while it is outside
+// read_pos..write_pos, and read only by the consumer while inside it. The
+// Release stores and Acquire loads of the positions order those accesses.
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        // Both halves are gone, so relaxed loads see the final positions
+        let (read, write) = (self.read_pos.load(Ordering::Relaxed), self.write_pos.load(Ordering::Relaxed));
+        for pos in read..write {
+            // SAFETY: slots between the positions hold initialised, unread items
+            drop(unsafe { self.read_slot(pos) });
+        }
+    }
+}
+
+/// The sending half of an SPSC ring.
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The receiving half of an SPSC ring.
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Creates a wait-free single-producer, single-consumer ring holding up to
+/// `capacity` items.
+///
+/// Follows `RingBuffer`'s read/write-position design, but the positions are
+/// atomics owned by one side each: only the producer advances `write_pos`
+/// and only the consumer advances `read_pos`. They count up without
+/// wrapping, so `write_pos - read_pos` is the length and a full ring is
+/// distinguishable from an empty one. Neither half ever waits on the other;
+/// `push` fails when full and `pop` returns `None` when empty.
+pub fn spsc_ring<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    let slots = (0..capacity).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect();
+    let shared = Arc::new(Shared { slots, read_pos: AtomicUsize::new(0), write_pos: AtomicUsize::new(0) });
+    (Producer { shared: Arc::clone(&shared) }, Consumer { shared })
+}
+
+impl<T> Shared<T> {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn length(&self) -> usize {
+        let read = self.read_pos.load(Ordering::Acquire);
+        let write = self.write_pos.load(Ordering::Acquire);
+        write.wrapping_sub(read)
+    }
+
+    /// # Safety
+    ///
+    /// Only the producer may call this, for a slot outside read..write.
+    unsafe fn write_slot(&self, pos: usize, item: T) {
+        let slot = &self.slots[pos % self.capacity()];
+        #[cfg(not(loom))]
+        (*slot.get()).write(item);
+        #[cfg(loom)]
+        slot.with_mut(|slot| (*slot).write(item));
+    }
+
+    /// # Safety
+    ///
+    /// Only the consumer may call this, once, for a slot inside read..write.
+    unsafe fn read_slot(&self, pos: usize) -> T {
+        let slot = &self.slots[pos % self.capacity()];
+        #[cfg(not(loom))]
+        return (*slot.get()).assume_init_read();
+        #[cfg(loom)]
+        return slot.with(|slot| (*slot).assume_init_read());
+    }
+}
+
+impl<T> Producer<T> {
+    /// Appends `item`, or hands it back if the ring is full.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        let shared = &*self.shared;
+        let write = shared.write_pos.load(Ordering::Relaxed);
+        let read = shared.read_pos.load(Ordering::Acquire);
+        if write.wrapping_sub(read) == shared.capacity() {
+            return Err(item);
+        }
+        // SAFETY: the slot is outside read..write, so the consumer is not using it
+        unsafe { shared.write_slot(write, item) };
+        shared.write_pos.store(write.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Returns the number of queued items; may be stale by the time it is used.
+    pub fn length(&self) -> usize {
+        self.shared.length()
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Removes the oldest item, or returns `None` if the ring is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let shared = &*self.shared;
+        let read = shared.read_pos.load(Ordering::Relaxed);
+        let write = shared.write_pos.load(Ordering::Acquire);
+        if read == write {
+            return None;
+        }
+        // SAFETY: the slot is inside read..write, so the producer initialised
+        // it and will not touch it until read_pos moves past it
+        let item = unsafe { shared.read_slot(read) };
+        shared.read_pos.store(read.wrapping_add(1), Ordering::Release);
+        Some(item)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Returns the number of queued items; may be stale by the time it is used.
+    pub fn length(&self) -> usize {
+        self.shared.length()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length() == 0
+    }
+}
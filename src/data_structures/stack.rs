@@ -1,15 +1,75 @@
-#[derive(Debug)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop_peek() {
+        let mut stack = Stack::new();
+        assert!(stack.is_empty());
+        stack.push(String::from("a"));
+        stack.push(String::from("b"));
+        assert_eq!(stack.peek(), Some(&String::from("b")));
+        assert_eq!(stack.length(), 2);
+        assert_eq!(stack.pop().as_deref(), Some("b"));
+        assert_eq!(stack.pop().as_deref(), Some("a"));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn test_peek_mut() {
+        let mut stack: Stack<Vec<i32>> = Stack::with_capacity(4);
+        assert!(stack.peek_mut().is_none());
+        stack.push(vec![1]);
+        stack.peek_mut().unwrap().push(2);
+        assert_eq!(stack.pop(), Some(vec![1, 2]));
+        assert!(stack.capacity() >= 4);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut stack: Stack<i32> = (1..=3).collect();
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        for item in stack.iter_mut() {
+            *item *= 10;
+        }
+        stack.extend([40, 50]);
+        assert_eq!((&stack).into_iter().last(), Some(&50));
+        assert_eq!(stack.peek(), Some(&50));
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![10, 20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn test_clear_and_default() {
+        let mut stack: Stack<Box<u8>> = Stack::default();
+        stack.push(Box::new(1));
+        stack.clear();
+        assert!(stack.is_empty());
+        assert_eq!(stack.peek(), None);
+    }
+}
+
+/// A last-in, first-out stack.
+///
+/// Iteration, by reference or by value, runs from the bottom of the stack to
+/// the top, i.e. in push order.
+#[derive(Debug, Clone)]
 pub struct Stack<T> {
     stack: Vec<T>,
 }
 
-impl<T: Copy> Stack<T> {
+impl<T> Stack<T> {
     pub fn new() -> Self {
         Stack { stack: Vec::new() }
     }
+    pub fn with_capacity(capacity: usize) -> Self {
+        Stack { stack: Vec::with_capacity(capacity) }
+    }
     pub fn length(&self) -> usize {
         self.stack.len()
     }
+    pub fn capacity(&self) -> usize {
+        self.stack.capacity()
+    }
     pub fn pop(&mut self) -> Option<T> {
         self.stack.pop()
     }
@@ -22,9 +82,27 @@ impl<T: Copy> Stack<T> {
     pub fn peek(&self) -> Option<&T> {
         self.stack.last()
     }
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.stack.last_mut()
+    }
+    pub fn clear(&mut self) {
+        self.stack.clear()
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.stack.iter()
+    }
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.stack.iter_mut()
+    }
 }
 
-impl<T: Copy> IntoIterator for Stack<T> {
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
     type Item = T;
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
@@ -32,3 +110,35 @@ impl<T: Copy> IntoIterator for Stack<T> {
         self.stack.into_iter()
     }
 }
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Stack<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Pushes the items in order, so the last item ends up on top.
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.stack.extend(iter)
+    }
+}
+
+/// Pushes the items in order, so the last item ends up on top.
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Stack { stack: Vec::from_iter(iter) }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::data_structures::stack::Stack;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_order() {
+        let mut queue = TwoStackQueue::new();
+        queue.enqueue(1);
+        queue.enqueue(2);
+        assert_eq!(queue.peek(), Some(&1));
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(3);
+        assert_eq!(queue.peek(), Some(&2));
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.dequeue(), Some(3));
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    #[test]
+    fn test_each_item_moves_at_most_once() {
+        let mut queue: TwoStackQueue<usize> = TwoStackQueue::new();
+        let mut dequeued = 0;
+        for round in 0..100 {
+            queue.extend(0..round % 7);
+            for _ in 0..round % 5 {
+                dequeued += queue.dequeue().is_some() as usize;
+            }
+        }
+        let enqueued: usize = (0..100).map(|round| round % 7).sum();
+        assert!(queue.moves() <= enqueued);
+        assert_eq!(queue.length(), enqueued - dequeued);
+    }
+
+    #[test]
+    fn test_collect_and_into_iter() {
+        let mut queue: TwoStackQueue<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        queue.dequeue();
+        queue.enqueue(String::from("c"));
+        assert_eq!(format!("{:?}", queue.iter().collect::<Vec<_>>()), r#"["b", "c"]"#);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec!["b", "c"]);
+    }
+}
+
+/// A FIFO queue built from two `Stack`s.
+///
+/// Items are pushed onto `inbox`. When `outbox` runs dry, the whole inbox is
+/// popped onto it, which reverses it into FIFO order. Each item is moved at
+/// most once, so although a single `dequeue` can cost O(n), any sequence of
+/// n operations costs O(n): amortised O(1) per operation. `moves` counts the
+/// transfers so the bound can be observed.
+#[derive(Debug, Clone)]
+pub struct TwoStackQueue<T> {
+    inbox: Stack<T>,
+    outbox: Stack<T>,
+    moves: usize,
+}
+
+impl<T> TwoStackQueue<T> {
+    pub fn new() -> Self {
+        TwoStackQueue { inbox: Stack::new(), outbox: Stack::new(), moves: 0 }
+    }
+
+    pub fn length(&self) -> usize {
+        self.inbox.length() + self.outbox.length()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inbox.is_empty() && self.outbox.is_empty()
+    }
+
+    /// Returns how many items have been moved from the inbox to the outbox.
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    pub fn enqueue(&mut self, item: T) {
+        self.inbox.push(item)
+    }
+
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            while let Some(item) = self.inbox.pop() {
+                self.outbox.push(item);
+                self.moves += 1;
+            }
+        }
+        self.outbox.pop()
+    }
+
+    /// Returns the front item without moving anything between the stacks.
+    pub fn peek(&self) -> Option<&T> {
+        self.outbox.peek().or_else(|| self.inbox.iter().next())
+    }
+
+    /// Iterates from front to back.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.outbox.iter().rev().chain(self.inbox.iter())
+    }
+}
+
+impl<T> Default for TwoStackQueue<T> {
+    fn default() -> Self {
+        TwoStackQueue::new()
+    }
+}
+
+impl<T> IntoIterator for TwoStackQueue<T> {
+    type Item = T;
+    type IntoIter = std::iter::Chain<std::iter::Rev<std::vec::IntoIter<T>>, std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.outbox.into_iter().rev().chain(self.inbox)
+    }
+}
+
+/// Enqueues the items in order.
+impl<T> Extend<T> for TwoStackQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.inbox.extend(iter)
+    }
+}
+
+impl<T> FromIterator<T> for TwoStackQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        TwoStackQueue { inbox: Stack::from_iter(iter), outbox: Stack::new(), moves: 0 }
+    }
+}
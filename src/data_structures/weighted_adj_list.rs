@@ -1,5 +1,8 @@
-use std::collections::HashMap;
+use crate::graph::{GraphBase, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable, Visitable};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 
+#[derive(Debug)]
 pub struct WeightedAdjacencyList<T, W> {
     vertices: HashMap<T, HashMap<T, W>>,
 }
@@ -16,6 +19,16 @@ where
     }
 }
 
+impl<T, W> PartialEq for WeightedAdjacencyList<T, W>
+where
+    T: std::hash::Hash + Eq,
+    W: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+    }
+}
+
 impl<T, W> WeightedAdjacencyList<T, W>
 where
     T: std::hash::Hash + Eq + Clone,
@@ -55,4 +68,85 @@ where
     pub fn len(&self) -> usize {
         self.vertices.len()
     }
+
+    // Method to iterate over every vertex in the adjacency list
+    pub fn vertices(&self) -> impl Iterator<Item = &T> {
+        self.vertices.keys()
+    }
+
+    // Method to iterate over every edge as (from, to, weight)
+    pub fn all_edges(&self) -> impl Iterator<Item = (&T, &T, &W)> {
+        self.vertices
+            .iter()
+            .flat_map(|(from, adjs)| adjs.iter().map(move |(to, weight)| (from, to, weight)))
+    }
+}
+
+impl<T, W> GraphBase for &WeightedAdjacencyList<T, W>
+where
+    T: Hash + Eq + Copy,
+{
+    type NodeId = T;
+    type EdgeWeight = W;
+}
+
+impl<T, W> IntoNeighbors for &WeightedAdjacencyList<T, W>
+where
+    T: Hash + Eq + Copy,
+{
+    fn neighbors(self, node: T) -> impl Iterator<Item = T> {
+        self.vertices
+            .get(&node)
+            .into_iter()
+            .flat_map(|adjs| adjs.keys().copied())
+    }
+}
+
+impl<T, W> IntoNodeIdentifiers for &WeightedAdjacencyList<T, W>
+where
+    T: Hash + Eq + Copy,
+{
+    fn node_identifiers(self) -> impl Iterator<Item = T> {
+        self.vertices.keys().copied()
+    }
+}
+
+impl<'a, T, W> IntoEdges for &'a WeightedAdjacencyList<T, W>
+where
+    T: Hash + Eq + Copy,
+{
+    type EdgeRef = (T, T, &'a W);
+
+    fn edges(self, node: T) -> impl Iterator<Item = Self::EdgeRef> {
+        self.vertices
+            .get(&node)
+            .into_iter()
+            .flat_map(move |adjs| adjs.iter().map(move |(&to, weight)| (node, to, weight)))
+    }
+}
+
+impl<T, W> Visitable for &WeightedAdjacencyList<T, W>
+where
+    T: Hash + Eq + Copy,
+{
+    type Map = HashSet<T>;
+
+    fn visit_map(self) -> HashSet<T> {
+        HashSet::with_capacity(self.vertices.len())
+    }
+}
+
+// Vertex ids double as indices, so lists keyed by `usize` can back `Vec`-based lookups
+impl<W> NodeIndexable for &WeightedAdjacencyList<usize, W> {
+    fn node_bound(self) -> usize {
+        self.vertices.keys().max().map_or(0, |&max| max + 1)
+    }
+
+    fn to_index(self, node: usize) -> usize {
+        node
+    }
+
+    fn from_index(self, index: usize) -> usize {
+        index
+    }
 }
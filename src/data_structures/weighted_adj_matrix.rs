@@ -1,84 +1,566 @@
-pub struct WeightedAdjacencyMatrix {
-    matrix: Vec<Vec<u64>>,
+use crate::graph::{GraphBase, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable, Visitable};
+use crate::numeric::Numeric;
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> WeightedAdjacencyMatrix<u64> {
+        WeightedAdjacencyMatrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn test_new_rejects_ragged_rows() {
+        let err = WeightedAdjacencyMatrix::new(vec![vec![1u64, 2], vec![3]]).unwrap_err();
+        assert_eq!(err, MatrixError::RaggedRow { row: 1, expected: 2, found: 1 });
+    }
+
+    #[test]
+    fn test_get_and_set_value() {
+        let mut matrix = sample();
+        assert_eq!(matrix.get_value(1, 2), Some(6));
+        assert_eq!(matrix.set_value(1, 2, 10), Ok(()));
+        assert_eq!(matrix.get_value(1, 2), Some(10));
+        assert_eq!(
+            matrix.set_value(2, 0, 1),
+            Err(MatrixError::OutOfBounds { row: 2, col: 0, shape: (2, 3) })
+        );
+    }
+
+    #[test]
+    fn test_absent_cells_are_distinct_from_zero() {
+        let mut matrix: WeightedAdjacencyMatrix<u64> = WeightedAdjacencyMatrix::empty(2, 2);
+        matrix.set_value(0, 1, 0).unwrap();
+        assert_eq!(matrix.get_value(0, 1), Some(0));
+        assert_eq!(matrix.get_value(1, 0), None);
+        assert_eq!(matrix.remove_value(0, 1), Ok(Some(0)));
+        assert_eq!(matrix[(0, 1)], None);
+    }
+
+    #[test]
+    fn test_transpose_and_column_of_empty_matrix() {
+        let matrix: WeightedAdjacencyMatrix<u64> = WeightedAdjacencyMatrix::new(vec![]).unwrap();
+        assert_eq!(matrix.transpose().shape(), (0, 0));
+        assert_eq!(matrix.get_column(0), None);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let transposed = sample().transpose();
+        assert_eq!(transposed.shape(), (3, 2));
+        assert_eq!(transposed.get_column(1), Some(vec![Some(4), Some(5), Some(6)]));
+        assert_eq!(transposed.get_row(2), Some(&[Some(3), Some(6)][..]));
+    }
+
+    #[test]
+    fn test_subtract_underflow_is_an_error() {
+        let mut small = WeightedAdjacencyMatrix::new(vec![vec![1u64]]).unwrap();
+        let big = WeightedAdjacencyMatrix::new(vec![vec![2u64]]).unwrap();
+        assert_eq!(small.subtract_matrix(&big), Err(MatrixError::Overflow { row: 0, col: 0 }));
+        assert_eq!(small.get_value(0, 0), Some(1));
+        small.saturating_subtract_matrix(&big).unwrap();
+        assert_eq!(small.get_value(0, 0), Some(0));
+    }
+
+    #[test]
+    fn test_shape_mismatch() {
+        let mut a = sample();
+        let b: WeightedAdjacencyMatrix<u64> = WeightedAdjacencyMatrix::zeros(3, 3);
+        assert_eq!(
+            a.add_matrix(&b),
+            Err(MatrixError::ShapeMismatch { expected: (2, 3), found: (3, 3) })
+        );
+        // A product needs the right operand to have as many rows as the left has columns
+        assert_eq!(a.multiply_matrix(&a), Err(MatrixError::ShapeMismatch { expected: (3, 3), found: (2, 3) }));
+        assert!((&a * &a).is_err());
+    }
+
+    #[test]
+    fn test_add_keeps_absent_cells_sparse() {
+        let a = WeightedAdjacencyMatrix::from_options(vec![vec![Some(1i32), None], vec![None, None]]).unwrap();
+        let b = WeightedAdjacencyMatrix::from_options(vec![vec![None, Some(2)], vec![None, None]]).unwrap();
+        let sum = (&a + &b).unwrap();
+        assert_eq!(sum.get_row(0), Some(&[Some(1), Some(2)][..]));
+        assert_eq!(sum.get_row(1), Some(&[None, None][..]));
+        let difference = (a - b).unwrap();
+        assert_eq!(difference.get_row(0), Some(&[Some(1), Some(-2)][..]));
+    }
+
+    #[test]
+    fn test_matrix_multiplication() {
+        let a = sample();
+        let b = a.transpose();
+        let product = (&a * &b).unwrap();
+        assert_eq!(product, WeightedAdjacencyMatrix::new(vec![vec![14, 32], vec![32, 77]]).unwrap());
+        let identity = WeightedAdjacencyMatrix::identity(3);
+        assert_eq!(a.multiply_matrix(&identity).unwrap(), a);
+    }
+
+    #[test]
+    fn test_scalar_multiplication_overflow() {
+        let mut matrix = WeightedAdjacencyMatrix::new(vec![vec![1u8, 200]]).unwrap();
+        assert_eq!(matrix.multiply_scalar(2), Err(MatrixError::Overflow { row: 0, col: 1 }));
+        assert_eq!(matrix.get_value(0, 0), Some(1));
+        matrix.saturating_multiply_scalar(2);
+        assert_eq!(matrix.get_row(0), Some(&[Some(2), Some(255)][..]));
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut matrix: WeightedAdjacencyMatrix<f64> = WeightedAdjacencyMatrix::empty(2, 2);
+        matrix[(1, 0)] = Some(0.5);
+        assert_eq!(matrix.get_value(1, 0), Some(0.5));
+    }
+
+    #[test]
+    fn test_display() {
+        let matrix = WeightedAdjacencyMatrix::from_options(vec![vec![Some(1u64), None], vec![Some(0), Some(7)]]).unwrap();
+        assert_eq!(matrix.to_string(), "1 - \n0 7 \n");
+        // Rows without columns still get a line each
+        let matrix: WeightedAdjacencyMatrix<u64> = WeightedAdjacencyMatrix::empty(2, 0);
+        assert_eq!(matrix.to_string(), "\n\n");
+    }
 }
 
-impl WeightedAdjacencyMatrix {
-    pub fn new(matrix: Vec<Vec<u64>>) -> Self {
-        WeightedAdjacencyMatrix { matrix }
+/// Errors produced by `WeightedAdjacencyMatrix` operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The operands of a matrix operation have incompatible dimensions.
+    /// `expected` is the shape the right-hand operand needed and `found` is
+    /// its actual shape: the left operand's shape for cell-wise operations,
+    /// or `(left.cols, right.cols)` for a product.
+    ShapeMismatch { expected: (usize, usize), found: (usize, usize) },
+    /// A row passed to a constructor does not have the same length as the first row.
+    RaggedRow { row: usize, expected: usize, found: usize },
+    /// A coordinate lies outside of the matrix.
+    OutOfBounds { row: usize, col: usize, shape: (usize, usize) },
+    /// The result of an arithmetic operation at the given cell is not representable.
+    Overflow { row: usize, col: usize },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::ShapeMismatch { expected, found } => write!(
+                f,
+                "shape mismatch: expected {}x{}, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            MatrixError::RaggedRow { row, expected, found } => write!(
+                f,
+                "row {} has {} columns, expected {}",
+                row, found, expected
+            ),
+            MatrixError::OutOfBounds { row, col, shape } => write!(
+                f,
+                "coordinates ({}, {}) out of bounds for {}x{} matrix",
+                row, col, shape.0, shape.1
+            ),
+            MatrixError::Overflow { row, col } => {
+                write!(f, "arithmetic overflow at ({}, {})", row, col)
+            }
+        }
     }
+}
+
+impl std::error::Error for MatrixError {}
+
+/// A weighted adjacency matrix stored as a flat row-major vector of cells.
+///
+/// Each cell is an `Option<W>`: `None` means "no edge", which is distinct from
+/// an edge of weight zero. For the arithmetic operations absent cells behave as
+/// structural zeros, so the sum or product of two absent cells stays absent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedAdjacencyMatrix<W = u64> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Option<W>>,
+}
 
-    pub fn get_value(&self, row: usize, col: usize) -> Option<u64> {
-        self.matrix.get(row).and_then(|r| r.get(col).cloned())
+impl<W: Numeric> WeightedAdjacencyMatrix<W> {
+    /// Creates a dense matrix where every cell holds an edge.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixError::RaggedRow` if the rows have different lengths.
+    pub fn new(matrix: Vec<Vec<W>>) -> Result<Self, MatrixError> {
+        Self::from_options(
+            matrix
+                .into_iter()
+                .map(|row| row.into_iter().map(Some).collect())
+                .collect(),
+        )
     }
 
-    pub fn set_value(&mut self, row: usize, col: usize, value: u64) -> Result<(), &'static str> {
-        if let Some(row_vec) = self.matrix.get_mut(row) {
-            if let Some(cell) = row_vec.get_mut(col) {
-                *cell = value;
-                return Ok(());
+    /// Creates a matrix from rows of optional weights, `None` meaning "no edge".
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixError::RaggedRow` if the rows have different lengths.
+    pub fn from_options(matrix: Vec<Vec<Option<W>>>) -> Result<Self, MatrixError> {
+        let rows = matrix.len();
+        let cols = matrix.first().map_or(0, |row| row.len());
+        let mut cells = Vec::with_capacity(rows * cols);
+        for (row, values) in matrix.into_iter().enumerate() {
+            if values.len() != cols {
+                return Err(MatrixError::RaggedRow { row, expected: cols, found: values.len() });
             }
+            cells.extend(values);
         }
-        Err("Coordinates out of bounds")
+        Ok(WeightedAdjacencyMatrix { rows, cols, cells })
     }
 
-    pub fn transpose(&self) -> WeightedAdjacencyMatrix {
-        let transposed = (0..self.matrix[0].len())
-            .map(|i| (0..self.matrix.len()).map(|j| self.matrix[j][i]).collect())
-            .collect();
-        WeightedAdjacencyMatrix::new(transposed)
+    /// Creates a `rows` x `cols` matrix with no edges.
+    pub fn empty(rows: usize, cols: usize) -> Self {
+        WeightedAdjacencyMatrix { rows, cols, cells: vec![None; rows * cols] }
     }
 
-    pub fn get_row(&self, row_index: usize) -> Option<&Vec<u64>> {
-        self.matrix.get(row_index)
+    /// Creates a `rows` x `cols` matrix where every cell is an edge of weight zero.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        WeightedAdjacencyMatrix { rows, cols, cells: vec![Some(W::zero()); rows * cols] }
     }
 
-    pub fn get_column(&self, col_index: usize) -> Option<Vec<u64>> {
-        if col_index >= self.matrix[0].len() {
-            return None;
+    /// Creates the `n` x `n` identity matrix, with absent cells off the diagonal.
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Self::empty(n, n);
+        for i in 0..n {
+            matrix.cells[i * n + i] = Some(W::one());
         }
-        Some(self.matrix.iter().map(|row| row[col_index]).collect())
+        matrix
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Returns the weight at the given cell, or `None` if there is no edge or
+    /// the coordinates are out of bounds.
+    pub fn get_value(&self, row: usize, col: usize) -> Option<W> {
+        self.offset(row, col).and_then(|i| self.cells[i])
     }
 
-    pub fn multiply_scalar(&mut self, scalar: u64) {
-        for row in self.matrix.iter_mut() {
-            for cell in row.iter_mut() {
-                *cell *= scalar;
+    pub fn set_value(&mut self, row: usize, col: usize, value: W) -> Result<(), MatrixError> {
+        let i = self.checked_offset(row, col)?;
+        self.cells[i] = Some(value);
+        Ok(())
+    }
+
+    /// Removes the edge at the given cell, returning its previous weight.
+    pub fn remove_value(&mut self, row: usize, col: usize) -> Result<Option<W>, MatrixError> {
+        let i = self.checked_offset(row, col)?;
+        Ok(self.cells[i].take())
+    }
+
+    pub fn transpose(&self) -> WeightedAdjacencyMatrix<W> {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for col in 0..self.cols {
+            for row in 0..self.rows {
+                cells.push(self.cells[row * self.cols + col]);
             }
         }
+        WeightedAdjacencyMatrix { rows: self.cols, cols: self.rows, cells }
+    }
+
+    pub fn get_row(&self, row_index: usize) -> Option<&[Option<W>]> {
+        if row_index >= self.rows {
+            return None;
+        }
+        let start = row_index * self.cols;
+        Some(&self.cells[start..start + self.cols])
     }
 
-    pub fn add_matrix(&mut self, other: &WeightedAdjacencyMatrix) -> Option<()> {
-        if self.matrix.len() != other.matrix.len() || self.matrix[0].len() != other.matrix[0].len() {
-            return None; // Matrices must have the same dimensions to add
+    pub fn get_column(&self, col_index: usize) -> Option<Vec<Option<W>>> {
+        if col_index >= self.cols {
+            return None;
         }
-        for i in 0..self.matrix.len() {
-            for j in 0..self.matrix[i].len() {
-                self.matrix[i][j] += other.matrix[i][j];
+        Some((0..self.rows).map(|row| self.cells[row * self.cols + col_index]).collect())
+    }
+
+    /// Multiplies every edge weight by `scalar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MatrixError::Overflow` for the first cell whose product is not
+    /// representable; the matrix is left unchanged in that case.
+    pub fn multiply_scalar(&mut self, scalar: W) -> Result<(), MatrixError> {
+        let mut cells = self.cells.clone();
+        for (i, cell) in cells.iter_mut().enumerate() {
+            if let Some(value) = cell {
+                *value = value
+                    .checked_mul(scalar)
+                    .ok_or_else(|| self.overflow_at(i))?;
             }
         }
-        Some(())
+        self.cells = cells;
+        Ok(())
     }
 
-    pub fn subtract_matrix(&mut self, other: &WeightedAdjacencyMatrix) -> Option<()> {
-        if self.matrix.len() != other.matrix.len() || self.matrix[0].len() != other.matrix[0].len() {
-            return None; // Matrices must have the same dimensions to subtract
+    /// Multiplies every edge weight by `scalar`, clamping at the numeric bounds.
+    pub fn saturating_multiply_scalar(&mut self, scalar: W) {
+        for value in self.cells.iter_mut().flatten() {
+            *value = value.saturating_mul(scalar);
         }
-        for i in 0..self.matrix.len() {
-            for j in 0..self.matrix[i].len() {
-                self.matrix[i][j] -= other.matrix[i][j];
+    }
+
+    /// Adds `other` cell by cell. The matrix is left unchanged on error.
+    pub fn add_matrix(&mut self, other: &WeightedAdjacencyMatrix<W>) -> Result<(), MatrixError> {
+        self.cells = self.combine(other, W::checked_add)?;
+        Ok(())
+    }
+
+    /// Adds `other` cell by cell, clamping at the numeric bounds.
+    pub fn saturating_add_matrix(&mut self, other: &WeightedAdjacencyMatrix<W>) -> Result<(), MatrixError> {
+        self.cells = self.combine(other, |a, b| Some(a.saturating_add(b)))?;
+        Ok(())
+    }
+
+    /// Subtracts `other` cell by cell. The matrix is left unchanged on error.
+    pub fn subtract_matrix(&mut self, other: &WeightedAdjacencyMatrix<W>) -> Result<(), MatrixError> {
+        self.cells = self.combine(other, W::checked_sub)?;
+        Ok(())
+    }
+
+    /// Subtracts `other` cell by cell, clamping at the numeric bounds.
+    pub fn saturating_subtract_matrix(&mut self, other: &WeightedAdjacencyMatrix<W>) -> Result<(), MatrixError> {
+        self.cells = self.combine(other, |a, b| Some(a.saturating_sub(b)))?;
+        Ok(())
+    }
+
+    /// Computes the matrix product `self * other` with checked arithmetic.
+    ///
+    /// A cell of the result is absent when no pair of present cells contributes to it.
+    pub fn multiply_matrix(&self, other: &WeightedAdjacencyMatrix<W>) -> Result<WeightedAdjacencyMatrix<W>, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::ShapeMismatch {
+                expected: (self.cols, other.cols),
+                found: other.shape(),
+            });
+        }
+        let mut product = Self::empty(self.rows, other.cols);
+        for row in 0..self.rows {
+            for k in 0..self.cols {
+                let Some(a) = self.cells[row * self.cols + k] else {
+                    continue;
+                };
+                for col in 0..other.cols {
+                    let Some(b) = other.cells[k * other.cols + col] else {
+                        continue;
+                    };
+                    let overflow = MatrixError::Overflow { row, col };
+                    let term = a.checked_mul(b).ok_or(overflow)?;
+                    let cell = &mut product.cells[row * other.cols + col];
+                    *cell = Some(match *cell {
+                        Some(sum) => sum.checked_add(term).ok_or(overflow)?,
+                        None => term,
+                    });
+                }
             }
         }
-        Some(())
+        Ok(product)
     }
 
     pub fn display_matrix(&self) {
-        // Printing the array with row and column indices
-        for row in self.matrix.iter() {
-            for col in row.iter() {
-                print!("{} ", col);
+        print!("{}", self);
+    }
+
+    fn offset(&self, row: usize, col: usize) -> Option<usize> {
+        if row < self.rows && col < self.cols {
+            Some(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    fn checked_offset(&self, row: usize, col: usize) -> Result<usize, MatrixError> {
+        self.offset(row, col)
+            .ok_or(MatrixError::OutOfBounds { row, col, shape: self.shape() })
+    }
+
+    fn overflow_at(&self, offset: usize) -> MatrixError {
+        MatrixError::Overflow { row: offset / self.cols, col: offset % self.cols }
+    }
+
+    /// Combines two equally shaped matrices cell by cell, treating an absent
+    /// cell as zero when the other operand is present.
+    fn combine<F>(&self, other: &WeightedAdjacencyMatrix<W>, op: F) -> Result<Vec<Option<W>>, MatrixError>
+    where
+        F: Fn(W, W) -> Option<W>,
+    {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::ShapeMismatch { expected: self.shape(), found: other.shape() });
+        }
+        self.cells
+            .iter()
+            .zip(other.cells.iter())
+            .enumerate()
+            .map(|(i, (&a, &b))| match (a, b) {
+                (None, None) => Ok(None),
+                (a, b) => op(a.unwrap_or_else(W::zero), b.unwrap_or_else(W::zero))
+                    .map(Some)
+                    .ok_or_else(|| self.overflow_at(i)),
+            })
+            .collect()
+    }
+}
+
+impl<W: fmt::Display> fmt::Display for WeightedAdjacencyMatrix<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // One line per row, absent cells printed as '-'
+        for row in 0..self.rows {
+            for cell in &self.cells[row * self.cols..(row + 1) * self.cols] {
+                match cell {
+                    Some(value) => write!(f, "{} ", value)?,
+                    None => write!(f, "- ")?,
+                }
             }
-            println!();
+            writeln!(f)?;
         }
+        Ok(())
+    }
+}
+
+impl<W> Index<(usize, usize)> for WeightedAdjacencyMatrix<W> {
+    type Output = Option<W>;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(row < self.rows && col < self.cols, "coordinates ({}, {}) out of bounds", row, col);
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl<W> IndexMut<(usize, usize)> for WeightedAdjacencyMatrix<W> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(row < self.rows && col < self.cols, "coordinates ({}, {}) out of bounds", row, col);
+        &mut self.cells[row * self.cols + col]
     }
 }
 
+impl<W: Numeric> Add for &WeightedAdjacencyMatrix<W> {
+    type Output = Result<WeightedAdjacencyMatrix<W>, MatrixError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut sum = self.clone();
+        sum.add_matrix(rhs)?;
+        Ok(sum)
+    }
+}
+
+impl<W: Numeric> Add for WeightedAdjacencyMatrix<W> {
+    type Output = Result<WeightedAdjacencyMatrix<W>, MatrixError>;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.add_matrix(&rhs)?;
+        Ok(self)
+    }
+}
+
+impl<W: Numeric> Sub for &WeightedAdjacencyMatrix<W> {
+    type Output = Result<WeightedAdjacencyMatrix<W>, MatrixError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut difference = self.clone();
+        difference.subtract_matrix(rhs)?;
+        Ok(difference)
+    }
+}
+
+impl<W: Numeric> Sub for WeightedAdjacencyMatrix<W> {
+    type Output = Result<WeightedAdjacencyMatrix<W>, MatrixError>;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self.subtract_matrix(&rhs)?;
+        Ok(self)
+    }
+}
+
+impl<W: Numeric> Mul for &WeightedAdjacencyMatrix<W> {
+    type Output = Result<WeightedAdjacencyMatrix<W>, MatrixError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.multiply_matrix(rhs)
+    }
+}
+
+impl<W: Numeric> Mul for WeightedAdjacencyMatrix<W> {
+    type Output = Result<WeightedAdjacencyMatrix<W>, MatrixError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.multiply_matrix(&rhs)
+    }
+}
+
+impl<W: Numeric> Mul<W> for &WeightedAdjacencyMatrix<W> {
+    type Output = Result<WeightedAdjacencyMatrix<W>, MatrixError>;
+
+    fn mul(self, scalar: W) -> Self::Output {
+        let mut product = self.clone();
+        product.multiply_scalar(scalar)?;
+        Ok(product)
+    }
+}
+
+// As a graph, row `i` holds the outgoing edges of node `i` and column `j` the
+// incoming edges of node `j`; absent cells are missing edges.
+impl<W> GraphBase for &WeightedAdjacencyMatrix<W> {
+    type NodeId = usize;
+    type EdgeWeight = W;
+}
+
+impl<W> IntoNeighbors for &WeightedAdjacencyMatrix<W> {
+    fn neighbors(self, node: usize) -> impl Iterator<Item = usize> {
+        self.edges(node).map(|(_, to, _)| to)
+    }
+}
+
+impl<W> IntoNodeIdentifiers for &WeightedAdjacencyMatrix<W> {
+    fn node_identifiers(self) -> impl Iterator<Item = usize> {
+        0..self.rows.max(self.cols)
+    }
+}
+
+impl<'a, W> IntoEdges for &'a WeightedAdjacencyMatrix<W> {
+    type EdgeRef = (usize, usize, &'a W);
+
+    fn edges(self, node: usize) -> impl Iterator<Item = Self::EdgeRef> {
+        let row = if node < self.rows {
+            &self.cells[node * self.cols..(node + 1) * self.cols]
+        } else {
+            &[]
+        };
+        row.iter()
+            .enumerate()
+            .filter_map(move |(to, cell)| cell.as_ref().map(|weight| (node, to, weight)))
+    }
+}
+
+impl<W> NodeIndexable for &WeightedAdjacencyMatrix<W> {
+    fn node_bound(self) -> usize {
+        self.rows.max(self.cols)
+    }
+
+    fn to_index(self, node: usize) -> usize {
+        node
+    }
+
+    fn from_index(self, index: usize) -> usize {
+        index
+    }
+}
+
+impl<W> Visitable for &WeightedAdjacencyMatrix<W> {
+    type Map = Vec<bool>;
+
+    fn visit_map(self) -> Vec<bool> {
+        vec![false; self.rows.max(self.cols)]
+    }
+}
@@ -0,0 +1,39 @@
+//! Sequence diffing.
+//!
+//! `myers` and `patience` compute an edit script between two sequences;
+//! `patch` groups a script into hunks that render as a unified diff and can
+//! be applied to the old sequence to recover the new one.
+
+pub mod myers;
+pub mod patch;
+pub mod patience;
+
+/// One step of an edit script turning `old` into `new`.
+///
+/// Indices refer to positions in the old and new sequences. A script visits
+/// every element of both sequences exactly once, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// `old[old] == new[new]` is kept.
+    Equal { old: usize, new: usize },
+    /// `old[old]` is removed.
+    Delete { old: usize },
+    /// `new[new]` is inserted.
+    Insert { new: usize },
+}
+
+impl Edit {
+    pub fn is_change(&self) -> bool {
+        !matches!(self, Edit::Equal { .. })
+    }
+
+    /// Moves the edit from subsequences starting at `old_offset` / `new_offset`
+    /// into the coordinates of the full sequences.
+    fn shifted(self, old_offset: usize, new_offset: usize) -> Edit {
+        match self {
+            Edit::Equal { old, new } => Edit::Equal { old: old + old_offset, new: new + new_offset },
+            Edit::Delete { old } => Edit::Delete { old: old + old_offset },
+            Edit::Insert { new } => Edit::Insert { new: new + new_offset },
+        }
+    }
+}
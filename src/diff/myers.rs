@@ -0,0 +1,133 @@
+use crate::diff::Edit;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dp::lcs::longest_common_subsequence;
+    use crate::rng::XorShift64;
+
+    fn changes(edits: &[Edit]) -> usize {
+        edits.iter().filter(|edit| edit.is_change()).count()
+    }
+
+    #[test]
+    fn test_myers_diff() {
+        let a = b"ABCABBA";
+        let b = b"CBABAC";
+        let edits = myers_diff(a, b);
+        assert_eq!(changes(&edits), 5);
+        let kept: Vec<u8> = edits
+            .iter()
+            .filter_map(|edit| match *edit {
+                Edit::Equal { old, new } => {
+                    assert_eq!(a[old], b[new]);
+                    Some(a[old])
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kept.len(), 4);
+    }
+
+    #[test]
+    fn test_myers_diff_is_shortest() {
+        let a = ["a", "b", "c", "d", "e", "f", "g"];
+        let b = ["w", "a", "b", "x", "y", "z", "e", "f", "g", "h"];
+        let edits = myers_diff(&a, &b);
+        let lcs = longest_common_subsequence(&a, &b).len();
+        assert_eq!(changes(&edits), a.len() + b.len() - 2 * lcs);
+    }
+
+    #[test]
+    fn test_myers_diff_is_shortest_on_random_inputs() {
+        let mut rng = XorShift64::new(34);
+        for _ in 0..200 {
+            let a: Vec<u64> = (0..rng.below(30)).map(|_| rng.below(3)).collect();
+            let b: Vec<u64> = (0..rng.below(30)).map(|_| rng.below(3)).collect();
+            let edits = myers_diff(&a, &b);
+            let lcs = longest_common_subsequence(&a, &b).len();
+            assert_eq!(changes(&edits), a.len() + b.len() - 2 * lcs);
+            assert_eq!(edits.len(), a.len() + b.len() - lcs);
+        }
+    }
+
+    #[test]
+    fn test_myers_diff_edge_cases() {
+        let empty: [char; 0] = [];
+        assert!(myers_diff(&empty, &empty).is_empty());
+        assert_eq!(myers_diff(&['x'], &empty), vec![Edit::Delete { old: 0 }]);
+        assert_eq!(myers_diff(&empty, &['x']), vec![Edit::Insert { new: 0 }]);
+        assert_eq!(
+            myers_diff(&['x', 'y'], &['x', 'y']),
+            vec![Edit::Equal { old: 0, new: 0 }, Edit::Equal { old: 1, new: 1 }]
+        );
+        // A substitution lists the deletion before the insertion
+        assert_eq!(myers_diff(&['x'], &['y']), vec![Edit::Delete { old: 0 }, Edit::Insert { new: 0 }]);
+    }
+}
+
+/// Computes a shortest edit script from `a` to `b` with Myers' O(ND)
+/// algorithm, where D is the number of insertions plus deletions.
+///
+/// Keeps the reachable part of each frontier for backtracking, 2d + 3
+/// entries at edit distance d, so memory is O(D²) on top of the output.
+/// Deletions are listed before insertions within a change.
+pub fn myers_diff<T: Eq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    // frontier[k + offset] is the furthest x reached on diagonal k = x - y
+    let mut frontier = vec![0isize; 2 * max + 3];
+    // trace[d][k + d + 1] is frontier[k + offset] before step d, for the
+    // diagonals -d - 1..=d + 1 that step d reads
+    let mut trace = Vec::new();
+
+    'search: for d in 0..=max as isize {
+        trace.push(frontier[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && frontier[i - 1] < frontier[i + 1]) {
+                frontier[i + 1]
+            } else {
+                frontier[i - 1] + 1
+            };
+            let mut y = x - k;
+            // Follow the snake of equal elements
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            frontier[i] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Walk back through the frontiers from (n, m) to (0, 0)
+    let mut edits = Vec::with_capacity(max);
+    let (mut x, mut y) = (n, m);
+    for (d, frontier) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let reached = |k: isize| frontier[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && reached(k - 1) < reached(k + 1)) { k + 1 } else { k - 1 };
+        let prev_x = reached(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal { old: x as usize, new: y as usize });
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push(Edit::Insert { new: prev_y as usize });
+            } else {
+                edits.push(Edit::Delete { old: prev_x as usize });
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+    edits
+}
@@ -0,0 +1,260 @@
+use crate::diff::myers::myers_diff;
+use crate::diff::Edit;
+use std::fmt;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::patience::patience_diff;
+
+    fn lines(text: &str) -> Vec<&str> {
+        text.lines().collect()
+    }
+
+    const OLD: &str = "listen = 80\nhost = example.org\nworkers = 4\ntimeout = 30\nretries = 3\nlog = info\ncache = on\nmode = prod\n";
+    const NEW: &str = "listen = 8080\nhost = example.org\nworkers = 4\ntimeout = 30\nretries = 3\nlog = info\ncache = on\nmode = prod\ntls = on\n";
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff("a/app.conf", "b/app.conf", &lines(OLD), &lines(NEW), 2);
+        assert_eq!(
+            diff,
+            "--- a/app.conf\n+++ b/app.conf\n\
+             @@ -1,3 +1,3 @@\n-listen = 80\n+listen = 8080\n host = example.org\n workers = 4\n\
+             @@ -7,2 +7,3 @@\n cache = on\n mode = prod\n+tls = on\n"
+        );
+        assert_eq!(unified_diff("a", "b", &lines(OLD), &lines(OLD), 3), "");
+    }
+
+    #[test]
+    fn test_hunks_merge_when_context_overlaps() {
+        let (old, new) = (lines(OLD), lines(NEW));
+        assert_eq!(Patch::new(&old, &new, &myers_diff(&old, &new), 3).hunks().len(), 2);
+        let patch = Patch::new(&old, &new, &myers_diff(&old, &new), 4);
+        assert_eq!(patch.hunks().len(), 1);
+        let hunk = &patch.hunks()[0];
+        assert_eq!((hunk.old_start, hunk.old_len, hunk.new_start, hunk.new_len), (0, 8, 0, 9));
+        let patch = Patch::new(&old, &new, &myers_diff(&old, &new), 0);
+        assert_eq!(patch.hunks().len(), 2);
+        assert_eq!(patch.to_string(), "@@ -1 +1 @@\n-listen = 80\n+listen = 8080\n@@ -8,0 +9 @@\n+tls = on\n");
+    }
+
+    #[test]
+    fn test_apply_recovers_new_sequence() {
+        let old = lines("a\nb\nc\nd\ne\nf\ng\nh\n");
+        let new = lines("a\nc\nd\nx\ne\nf\nh\ny\n");
+        for edits in [myers_diff(&old, &new), patience_diff(&old, &new)] {
+            for context in 0..4 {
+                let patch = Patch::new(&old, &new, &edits, context);
+                assert_eq!(patch.apply(&old).unwrap(), new);
+            }
+        }
+        let empty: Vec<&str> = Vec::new();
+        assert_eq!(Patch::new(&empty, &new, &myers_diff(&empty, &new), 3).apply(&empty).unwrap(), new);
+        assert_eq!(Patch::new(&old, &empty, &myers_diff(&old, &empty), 3).apply(&old).unwrap(), empty);
+    }
+
+    #[test]
+    fn test_apply_rejects_mismatched_input() {
+        let (old, new) = (lines(OLD), lines(NEW));
+        let patch = Patch::new(&old, &new, &myers_diff(&old, &new), 1);
+        let mut edited = old.clone();
+        edited[1] = "host = example.com";
+        assert_eq!(patch.apply(&edited), Err(PatchError::Mismatch { hunk: 0, index: 1 }));
+        assert_eq!(patch.apply(&old[..5]), Err(PatchError::OutOfBounds { hunk: 1 }));
+    }
+}
+
+/// One line of a hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line<T> {
+    Context(T),
+    Delete(T),
+    Insert(T),
+}
+
+/// A group of nearby changes together with their surrounding context.
+///
+/// `old_start` and `new_start` are 0-based; the unified rendering converts
+/// them to the 1-based line numbers used by `diff -u`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk<T> {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<Line<T>>,
+}
+
+/// Why a patch could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The hunk reaches past the end of the input, or overlaps the previous hunk.
+    OutOfBounds { hunk: usize },
+    /// A context or deleted line of the hunk differs from the input at `index`.
+    Mismatch { hunk: usize, index: usize },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::OutOfBounds { hunk } => write!(f, "hunk {} does not fit the input", hunk),
+            PatchError::Mismatch { hunk, index } => {
+                write!(f, "hunk {} does not match the input at index {}", hunk, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+/// A set of hunks that turns one sequence into another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch<T> {
+    hunks: Vec<Hunk<T>>,
+}
+
+impl<T: Clone> Patch<T> {
+    /// Groups an edit script from `old` to `new` into hunks with up to
+    /// `context` unchanged elements around each change. Changes separated by at
+    /// most `2 * context` unchanged elements share a hunk.
+    pub fn new(old: &[T], new: &[T], edits: &[Edit], context: usize) -> Self {
+        // positions[i] is the (old, new) position just before edits[i]
+        let mut positions = Vec::with_capacity(edits.len() + 1);
+        let (mut x, mut y) = (0, 0);
+        for edit in edits {
+            positions.push((x, y));
+            match edit {
+                Edit::Equal { .. } => (x, y) = (x + 1, y + 1),
+                Edit::Delete { .. } => x += 1,
+                Edit::Insert { .. } => y += 1,
+            }
+        }
+        positions.push((x, y));
+
+        // Spans of edit indices, each covering a run of changes plus context
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        for (i, _) in edits.iter().enumerate().filter(|(_, edit)| edit.is_change()) {
+            let start = i.saturating_sub(context);
+            let end = (i + 1 + context).min(edits.len());
+            match spans.last_mut() {
+                Some(span) if start <= span.1 => span.1 = end,
+                _ => spans.push((start, end)),
+            }
+        }
+
+        let hunks = spans
+            .into_iter()
+            .map(|(start, end)| {
+                let lines = edits[start..end]
+                    .iter()
+                    .map(|edit| match *edit {
+                        Edit::Equal { old: i, .. } => Line::Context(old[i].clone()),
+                        Edit::Delete { old: i } => Line::Delete(old[i].clone()),
+                        Edit::Insert { new: j } => Line::Insert(new[j].clone()),
+                    })
+                    .collect();
+                let (old_start, new_start) = positions[start];
+                let (old_end, new_end) = positions[end];
+                Hunk {
+                    old_start,
+                    old_len: old_end - old_start,
+                    new_start,
+                    new_len: new_end - new_start,
+                    lines,
+                }
+            })
+            .collect();
+        Patch { hunks }
+    }
+}
+
+impl<T> Patch<T> {
+    pub fn hunks(&self) -> &[Hunk<T>] {
+        &self.hunks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+}
+
+impl<T: Clone + PartialEq> Patch<T> {
+    /// Applies the patch to `old`, returning the patched sequence.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a hunk's context or deleted lines do not match `old` exactly
+    /// at the hunk's position.
+    pub fn apply(&self, old: &[T]) -> Result<Vec<T>, PatchError> {
+        let mut patched = Vec::with_capacity(old.len());
+        let mut cursor = 0;
+        for (h, hunk) in self.hunks.iter().enumerate() {
+            if hunk.old_start < cursor || hunk.old_start + hunk.old_len > old.len() {
+                return Err(PatchError::OutOfBounds { hunk: h });
+            }
+            patched.extend_from_slice(&old[cursor..hunk.old_start]);
+            cursor = hunk.old_start;
+            for line in &hunk.lines {
+                match line {
+                    Line::Context(value) | Line::Delete(value) => {
+                        if old[cursor] != *value {
+                            return Err(PatchError::Mismatch { hunk: h, index: cursor });
+                        }
+                        if let Line::Context(_) = line {
+                            patched.push(value.clone());
+                        }
+                        cursor += 1;
+                    }
+                    Line::Insert(value) => patched.push(value.clone()),
+                }
+            }
+        }
+        patched.extend_from_slice(&old[cursor..]);
+        Ok(patched)
+    }
+}
+
+/// Formats a hunk range the way `diff -u` does: `start,len`, with `,1`
+/// omitted and an empty range numbered after the line it follows.
+fn range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Patch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for hunk in &self.hunks {
+            writeln!(
+                f,
+                "@@ -{} +{} @@",
+                range(hunk.old_start, hunk.old_len),
+                range(hunk.new_start, hunk.new_len)
+            )?;
+            for line in &hunk.lines {
+                match line {
+                    Line::Context(value) => writeln!(f, " {}", value)?,
+                    Line::Delete(value) => writeln!(f, "-{}", value)?,
+                    Line::Insert(value) => writeln!(f, "+{}", value)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders a unified diff of two line sequences using `myers_diff`, with
+/// `context` unchanged lines around each change. Returns an empty string when
+/// the sequences are equal.
+pub fn unified_diff<T>(old_name: &str, new_name: &str, old: &[T], new: &[T], context: usize) -> String
+where
+    T: Eq + Clone + fmt::Display,
+{
+    let patch = Patch::new(old, new, &myers_diff(old, new), context);
+    if patch.is_empty() {
+        return String::new();
+    }
+    format!("--- {}\n+++ {}\n{}", old_name, new_name, patch)
+}
@@ -0,0 +1,137 @@
+use crate::diff::myers::myers_diff;
+use crate::diff::Edit;
+use crate::dp::lis::longest_increasing_subsequence;
+use std::collections::HashMap;
+use std::hash::Hash;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kept<'a>(a: &[&'a str], edits: &[Edit]) -> Vec<&'a str> {
+        edits
+            .iter()
+            .filter_map(|edit| match *edit {
+                Edit::Equal { old, .. } => Some(a[old]),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_patience_diff_anchors_on_unique_lines() {
+        // Myers happily matches the braces of different functions; patience
+        // anchors on the unique signatures instead.
+        let a = ["fn a() {", "    x();", "}", "", "fn b() {", "    y();", "}"];
+        let b = ["fn b() {", "    y();", "}", "", "fn c() {", "    z();", "}"];
+        let edits = patience_diff(&a, &b);
+        assert_eq!(kept(&a, &edits), vec!["fn b() {", "    y();", "}"]);
+    }
+
+    #[test]
+    fn test_patience_diff_covers_both_sequences() {
+        let a = ["a", "b", "c", "a", "b", "c"];
+        let b = ["b", "a", "c", "c", "b"];
+        let edits = patience_diff(&a, &b);
+        let olds: Vec<usize> = edits
+            .iter()
+            .filter_map(|edit| match *edit {
+                Edit::Equal { old, .. } | Edit::Delete { old } => Some(old),
+                Edit::Insert { .. } => None,
+            })
+            .collect();
+        let news: Vec<usize> = edits
+            .iter()
+            .filter_map(|edit| match *edit {
+                Edit::Equal { new, .. } | Edit::Insert { new } => Some(new),
+                Edit::Delete { .. } => None,
+            })
+            .collect();
+        assert_eq!(olds, (0..a.len()).collect::<Vec<_>>());
+        assert_eq!(news, (0..b.len()).collect::<Vec<_>>());
+        for edit in edits {
+            if let Edit::Equal { old, new } = edit {
+                assert_eq!(a[old], b[new]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_patience_diff_without_unique_lines_falls_back() {
+        let a = ["x", "x", "y", "y"];
+        let b = ["y", "y", "x", "x"];
+        assert_eq!(patience_diff(&a, &b), myers_diff(&a, &b));
+    }
+}
+
+/// Computes an edit script from `a` to `b` with patience diff.
+///
+/// Elements occurring exactly once in both sequences are matched up, the
+/// longest increasing run of those matches becomes a set of anchors, and the
+/// gaps between anchors are diffed recursively. Gaps without unique elements
+/// fall back to `myers_diff`. The result is not always minimal, but tends to
+/// line up with the structure of source and config files.
+pub fn patience_diff<T: Eq + Hash>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let mut edits = Vec::with_capacity(a.len().max(b.len()));
+    patience(a, b, 0, 0, &mut edits);
+    edits
+}
+
+fn patience<T: Eq + Hash>(a: &[T], b: &[T], a_offset: usize, b_offset: usize, edits: &mut Vec<Edit>) {
+    // Strip the common prefix and suffix
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    for i in 0..prefix {
+        edits.push(Edit::Equal { old: a_offset + i, new: b_offset + i });
+    }
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    let (a_start, b_start) = (a_offset + prefix, b_offset + prefix);
+
+    let anchors = unique_anchors(a_mid, b_mid);
+    if anchors.is_empty() {
+        edits.extend(myers_diff(a_mid, b_mid).into_iter().map(|edit| edit.shifted(a_start, b_start)));
+    } else {
+        let (mut i, mut j) = (0, 0);
+        for (x, y) in anchors {
+            patience(&a_mid[i..x], &b_mid[j..y], a_start + i, b_start + j, edits);
+            edits.push(Edit::Equal { old: a_start + x, new: b_start + y });
+            (i, j) = (x + 1, y + 1);
+        }
+        patience(&a_mid[i..], &b_mid[j..], a_start + i, b_start + j, edits);
+    }
+
+    for i in 0..suffix {
+        edits.push(Edit::Equal { old: a.len() - suffix + a_offset + i, new: b.len() - suffix + b_offset + i });
+    }
+}
+
+/// Pairs up elements unique to both sequences and keeps the longest run that
+/// is increasing in both, as `(index in a, index in b)`.
+fn unique_anchors<T: Eq + Hash>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    // Per element: occurrences in a, occurrences in b, last index in a and b
+    let mut counts: HashMap<&T, (usize, usize, usize, usize)> = HashMap::new();
+    for (i, x) in a.iter().enumerate() {
+        let entry = counts.entry(x).or_default();
+        entry.0 += 1;
+        entry.2 = i;
+    }
+    for (j, y) in b.iter().enumerate() {
+        if let Some(entry) = counts.get_mut(y) {
+            entry.1 += 1;
+            entry.3 = j;
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = counts
+        .into_values()
+        .filter(|&(in_a, in_b, _, _)| in_a == 1 && in_b == 1)
+        .map(|(_, _, i, j)| (i, j))
+        .collect();
+    pairs.sort_unstable();
+    let targets: Vec<usize> = pairs.iter().map(|&(_, j)| j).collect();
+    longest_increasing_subsequence(&targets).into_iter().map(|k| pairs[k]).collect()
+}
@@ -1,8 +1,11 @@
-use crate::data_structures::{weighted_adj_list::WeightedAdjacencyList,min_heap::MinHeap};
+use crate::data_structures::min_heap::MinHeap;
+use crate::graph::{EdgeRef, IntoEdges, NodeIndexable};
+use crate::numeric::Numeric;
 #[cfg(test)]
 mod tests {
     use crate::data_structures::weighted_adj_list::WeightedAdjacencyList;
-    use super::dijkstras_shortest_path;
+    use crate::data_structures::weighted_adj_matrix::WeightedAdjacencyMatrix;
+    use super::{dijkstras, dijkstras_shortest_path};
     #[test]
     fn dijkstras_shortest_path_primeagen_class_test(){
         //      (1) --- (4) ---- (5)
@@ -39,49 +42,142 @@ mod tests {
         graph.add_edge(5, 2, 18);
         graph.add_edge(6, 3, 1);
         graph.add_edge(6, 5, 1);
-        let path = dijkstras_shortest_path(source,sink,graph.clone());
+        let path = dijkstras_shortest_path(source,sink,&graph);
         assert_eq!(path.unwrap(), vec![0, 1, 4, 5]);
     }
+
+    #[test]
+    fn test_dijkstras_on_adjacency_matrix() {
+        let matrix = WeightedAdjacencyMatrix::from_options(vec![
+            vec![None, Some(4u32), Some(1), None],
+            vec![None, None, None, Some(1)],
+            vec![None, Some(2), None, Some(7)],
+            vec![None, None, None, None],
+        ]).unwrap();
+        let paths = dijkstras(&matrix, 0);
+        assert_eq!(paths.distance(3), Some(4));
+        assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(paths.path_to(0), Some(vec![0]));
+        assert_eq!(dijkstras_shortest_path(3, 0, &matrix), None);
+        let mut distances: Vec<_> = paths.distances().collect();
+        distances.sort();
+        assert_eq!(distances, vec![(0, 0), (1, 3), (2, 1), (3, 4)]);
+    }
+
+    #[test]
+    fn test_dijkstras_unknown_source() {
+        let mut graph: WeightedAdjacencyList<usize, u8> = WeightedAdjacencyList::new();
+        graph.add_edge(0, 1, 1);
+        assert_eq!(dijkstras_shortest_path(9, 1, &graph), None);
+    }
+}
+
+/// Shortest-path distances and predecessors computed from a single source.
+pub struct ShortestPaths<G: IntoEdges> {
+    graph: G,
+    source: G::NodeId,
+    dists: Vec<Option<G::EdgeWeight>>,
+    prev: Vec<Option<G::NodeId>>,
 }
 
-pub fn dijkstras_shortest_path( source: usize, sink: usize, graph: WeightedAdjacencyList<usize, usize>) -> Option<Vec<usize>> {
-    let mut visited: MinHeap<(f64, usize)> = MinHeap::new();
-    let mut prev: Vec<usize> = vec![usize::MAX; graph.len()];
-    let mut dists: Vec<f64> = vec![f64::INFINITY; graph.len()];
-    dists[source] = 0.0;
-    visited.push((0.0, source));
+impl<G> ShortestPaths<G>
+where
+    G: IntoEdges + NodeIndexable,
+    G::EdgeWeight: Numeric,
+{
+    pub fn source(&self) -> G::NodeId {
+        self.source
+    }
 
-    while let Some((_, curr)) = visited.pop() {
-        if curr == sink {
-            break; // found shortest path to sink
-        }
+    /// Returns the length of the shortest path to `node`, or `None` if it is unreachable.
+    pub fn distance(&self, node: G::NodeId) -> Option<G::EdgeWeight> {
+        self.dists.get(self.graph.to_index(node)).copied().flatten()
+    }
 
-        if let Some(adjs) = graph.get_neighbors(&curr) {
-            for (&to, &weight) in adjs.iter() {
-                let new_dist = dists[curr] + weight as f64;
-                if new_dist < dists[to] {
-                    dists[to] = new_dist;
-                    prev[to] = curr;
-                    visited.push((new_dist, to));
-                }
-            }
+    /// Returns the nodes of the shortest path from the source to `node`.
+    pub fn path_to(&self, node: G::NodeId) -> Option<Vec<G::NodeId>> {
+        self.distance(node)?;
+        let mut path = vec![node];
+        let mut curr = node;
+        while curr != self.source {
+            curr = self.prev[self.graph.to_index(curr)]?;
+            path.push(curr);
         }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns the node preceding `node` on its shortest path, or `None` for the
+    /// source and unreachable nodes.
+    pub fn predecessor(&self, node: G::NodeId) -> Option<G::NodeId> {
+        self.prev.get(self.graph.to_index(node)).copied().flatten()
+    }
+
+    /// Iterates over every reachable node with its distance from the source.
+    pub fn distances(&self) -> impl Iterator<Item = (G::NodeId, G::EdgeWeight)> + '_ {
+        self.dists
+            .iter()
+            .enumerate()
+            .filter_map(|(i, dist)| dist.map(|d| (self.graph.from_index(i), d)))
     }
+}
+
+/// Computes the shortest paths from `source` to every reachable node.
+pub fn dijkstras<G>(graph: G, source: G::NodeId) -> ShortestPaths<G>
+where
+    G: IntoEdges + NodeIndexable,
+    G::EdgeWeight: Numeric,
+{
+    run_dijkstras(graph, source, None)
+}
+
+pub fn dijkstras_shortest_path<G>(source: G::NodeId, sink: G::NodeId, graph: G) -> Option<Vec<G::NodeId>>
+where
+    G: IntoEdges + NodeIndexable,
+    G::EdgeWeight: Numeric,
+{
+    run_dijkstras(graph, source, Some(sink)).path_to(sink)
+}
 
-    // Check if sink is unreachable
-    if prev[sink] == usize::MAX {
-        return None;
+fn run_dijkstras<G>(graph: G, source: G::NodeId, sink: Option<G::NodeId>) -> ShortestPaths<G>
+where
+    G: IntoEdges + NodeIndexable,
+    G::EdgeWeight: Numeric,
+{
+    let bound = graph.node_bound();
+    let mut paths = ShortestPaths {
+        graph,
+        source,
+        dists: vec![None; bound],
+        prev: vec![None; bound],
+    };
+    if graph.to_index(source) >= bound {
+        return paths;
     }
+    let mut visited: MinHeap<(G::EdgeWeight, usize)> = MinHeap::new();
+    paths.dists[graph.to_index(source)] = Some(G::EdgeWeight::zero());
+    visited.push((G::EdgeWeight::zero(), graph.to_index(source)));
 
-    // Reconstruct shortest path
-    let mut path = Vec::new();
-    let mut curr = sink;
-    while curr != usize::MAX {
-        path.push(curr);
-        curr = prev[curr];
+    while let Some((dist, curr)) = visited.pop() {
+        let node = graph.from_index(curr);
+        if Some(node) == sink {
+            break; // found shortest path to sink
+        }
+        if paths.dists[curr].is_some_and(|best| best < dist) {
+            continue; // stale heap entry
+        }
+
+        for edge in graph.edges(node) {
+            let to = graph.to_index(edge.target());
+            let new_dist = dist.saturating_add(*edge.weight());
+            if paths.dists[to].is_none_or(|old| new_dist < old) {
+                paths.dists[to] = Some(new_dist);
+                paths.prev[to] = Some(node);
+                visited.push((new_dist, to));
+            }
+        }
     }
-    path.reverse();
-    Some(path)
+    paths
 }
 fn has_unvisited(seen: &[bool], dists: &[f64]) -> bool {
     seen.iter().enumerate().any(|(i, &s)| !s && dists[i] < f64::INFINITY)
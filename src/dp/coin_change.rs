@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_ways() {
+        assert_eq!(count_ways(&[1, 2, 5], 5), 4);
+        assert_eq!(count_ways(&[2], 3), 0);
+        assert_eq!(count_ways(&[7], 0), 1);
+        assert_eq!(count_ways(&[], 0), 1);
+    }
+
+    #[test]
+    fn test_count_ways_large() {
+        // 1, 2, 5, 10, 20, 50, 100, 200 pence making two pounds
+        assert_eq!(count_ways(&[1, 2, 5, 10, 20, 50, 100, 200], 200), 73682);
+    }
+
+    #[test]
+    fn test_min_coins() {
+        let change = min_coins(&[1, 3, 4], 6).unwrap();
+        assert_eq!(change.count, 2);
+        assert_eq!(change.coins, vec![3, 3]);
+        assert_eq!(min_coins(&[2], 3), None);
+        assert_eq!(min_coins(&[5, 10], 0), Some(Change { count: 0, coins: vec![] }));
+    }
+
+    #[test]
+    fn test_min_coins_beats_greedy() {
+        let change = min_coins(&[1, 15, 25], 30).unwrap();
+        assert_eq!(change.coins, vec![15, 15]);
+    }
+}
+
+/// Counts the combinations of coins (order ignored) that sum to `amount`,
+/// with each denomination usable any number of times. Zero-valued coins are ignored.
+pub fn count_ways(coins: &[usize], amount: usize) -> u128 {
+    // ways[a] is the number of ways to make `a` from the coins processed so far
+    let mut ways = vec![0u128; amount + 1];
+    ways[0] = 1;
+    for &coin in coins.iter().filter(|&&coin| coin > 0) {
+        for a in coin..=amount {
+            ways[a] += ways[a - coin];
+        }
+    }
+    ways[amount]
+}
+
+/// A way to make change: the number of coins and their values in descending order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub count: usize,
+    pub coins: Vec<usize>,
+}
+
+/// Makes `amount` with the fewest coins, or returns `None` if it cannot be made.
+pub fn min_coins(coins: &[usize], amount: usize) -> Option<Change> {
+    // fewest[a] is the fewest coins making `a`; last[a] the coin added to reach it
+    let mut fewest: Vec<Option<usize>> = vec![None; amount + 1];
+    let mut last = vec![0; amount + 1];
+    fewest[0] = Some(0);
+    for a in 1..=amount {
+        for &coin in coins.iter().filter(|&&coin| coin > 0 && coin <= a) {
+            if let Some(count) = fewest[a - coin] {
+                if fewest[a].is_none_or(|best| count + 1 < best) {
+                    fewest[a] = Some(count + 1);
+                    last[a] = coin;
+                }
+            }
+        }
+    }
+
+    let count = fewest[amount]?;
+    let mut used = Vec::with_capacity(count);
+    let mut a = amount;
+    while a > 0 {
+        used.push(last[a]);
+        a -= last[a];
+    }
+    used.sort_unstable_by(|x, y| y.cmp(x));
+    Some(Change { count, coins: used })
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replays an edit script on `a` and checks that it produces `b`.
+    fn apply<T: Clone + PartialEq + std::fmt::Debug>(a: &[T], b: &[T], script: &EditScript) -> Vec<T> {
+        let mut out = Vec::new();
+        for op in &script.ops {
+            match *op {
+                EditOp::Keep { a: i, b: j } => {
+                    assert_eq!(a[i], b[j]);
+                    out.push(a[i].clone());
+                }
+                EditOp::Substitute { b: j, .. } | EditOp::Insert { b: j } => out.push(b[j].clone()),
+                EditOp::Delete { .. } => {}
+                EditOp::Transpose { a: i, .. } => {
+                    out.push(a[i + 1].clone());
+                    out.push(a[i].clone());
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        let script = levenshtein(b"kitten", b"sitting");
+        assert_eq!(script.distance, 3);
+        assert_eq!(apply(b"kitten", b"sitting", &script), b"sitting".to_vec());
+        let edits = script.ops.iter().filter(|op| !matches!(op, EditOp::Keep { .. })).count();
+        assert_eq!(edits, 3);
+    }
+
+    #[test]
+    fn test_levenshtein_edge_cases() {
+        assert_eq!(levenshtein::<u8>(b"", b"").distance, 0);
+        let script = levenshtein(b"", b"abc");
+        assert_eq!(script.ops, vec![EditOp::Insert { b: 0 }, EditOp::Insert { b: 1 }, EditOp::Insert { b: 2 }]);
+        let script = levenshtein(b"ab", b"");
+        assert_eq!(script.ops, vec![EditOp::Delete { a: 0 }, EditOp::Delete { a: 1 }]);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_transposition() {
+        assert_eq!(levenshtein(b"abcd", b"acbd").distance, 2);
+        let script = damerau_levenshtein(b"abcd", b"acbd");
+        assert_eq!(script.distance, 1);
+        assert_eq!(
+            script.ops,
+            vec![EditOp::Keep { a: 0, b: 0 }, EditOp::Transpose { a: 1, b: 1 }, EditOp::Keep { a: 3, b: 3 }]
+        );
+        assert_eq!(apply(b"abcd", b"acbd", &script), b"acbd".to_vec());
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_words() {
+        let a: Vec<char> = "a cat sat".chars().collect();
+        let b: Vec<char> = "a act sta".chars().collect();
+        let script = damerau_levenshtein(&a, &b);
+        assert_eq!(script.distance, 2);
+        assert_eq!(apply(&a, &b, &script), b);
+    }
+}
+
+/// One step of an alignment between sequences `a` and `b`.
+///
+/// Indices refer to the position in `a` and/or `b` the operation consumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOp {
+    /// `a[a] == b[b]`, nothing to do.
+    Keep { a: usize, b: usize },
+    /// Replace `a[a]` with `b[b]`.
+    Substitute { a: usize, b: usize },
+    /// Insert `b[b]`.
+    Insert { b: usize },
+    /// Delete `a[a]`.
+    Delete { a: usize },
+    /// Swap `a[a]` and `a[a + 1]` to produce `b[b]` and `b[b + 1]`.
+    Transpose { a: usize, b: usize },
+}
+
+/// The edit distance between two sequences and an alignment achieving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditScript {
+    pub distance: usize,
+    pub ops: Vec<EditOp>,
+}
+
+/// Levenshtein distance (insert, delete, substitute) with its edit script.
+pub fn levenshtein<T: Eq>(a: &[T], b: &[T]) -> EditScript {
+    edit_distance(a, b, false)
+}
+
+/// Damerau-Levenshtein distance that also counts swapping two adjacent
+/// elements as one edit, with its edit script.
+///
+/// This is the optimal string alignment variant: no substring is edited more
+/// than once, so a transposed pair is not edited further.
+pub fn damerau_levenshtein<T: Eq>(a: &[T], b: &[T]) -> EditScript {
+    edit_distance(a, b, true)
+}
+
+fn edit_distance<T: Eq>(a: &[T], b: &[T], transpositions: bool) -> EditScript {
+    let (n, m) = (a.len(), b.len());
+    // table[i][j] is the distance between a[..i] and b[..j]
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in table[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (table[i - 1][j - 1] + cost)
+                .min(table[i - 1][j] + 1)
+                .min(table[i][j - 1] + 1);
+            if transpositions && i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(table[i - 2][j - 2] + 1);
+            }
+            table[i][j] = best;
+        }
+    }
+
+    // Walk back from the bottom-right corner, preferring diagonal moves
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        let here = table[i][j];
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && here == table[i - 1][j - 1] {
+            ops.push(EditOp::Keep { a: i - 1, b: j - 1 });
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && here == table[i - 1][j - 1] + 1 {
+            ops.push(EditOp::Substitute { a: i - 1, b: j - 1 });
+            i -= 1;
+            j -= 1;
+        } else if transpositions
+            && i > 1
+            && j > 1
+            && a[i - 1] == b[j - 2]
+            && a[i - 2] == b[j - 1]
+            && here == table[i - 2][j - 2] + 1
+        {
+            ops.push(EditOp::Transpose { a: i - 2, b: j - 2 });
+            i -= 2;
+            j -= 2;
+        } else if i > 0 && here == table[i - 1][j] + 1 {
+            ops.push(EditOp::Delete { a: i - 1 });
+            i -= 1;
+        } else {
+            ops.push(EditOp::Insert { b: j - 1 });
+            j -= 1;
+        }
+    }
+    ops.reverse();
+    EditScript { distance: table[n][m], ops }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<Item> {
+        vec![
+            Item { weight: 1, value: 1 },
+            Item { weight: 3, value: 4 },
+            Item { weight: 4, value: 5 },
+            Item { weight: 5, value: 7 },
+        ]
+    }
+
+    fn check(items: &[Item], capacity: usize, packed: &Knapsack) {
+        let weight: usize = packed.counts.iter().zip(items).map(|(&c, item)| c * item.weight).sum();
+        let value: u64 = packed.counts.iter().zip(items).map(|(&c, item)| c as u64 * item.value).sum();
+        assert!(weight <= capacity);
+        assert_eq!(value, packed.value);
+    }
+
+    #[test]
+    fn test_knapsack_01() {
+        let items = items();
+        let packed = knapsack_01(&items, 7);
+        assert_eq!(packed.value, 9);
+        assert_eq!(packed.counts, vec![0, 1, 1, 0]);
+        check(&items, 7, &packed);
+        assert_eq!(knapsack_01(&items, 0).value, 0);
+    }
+
+    #[test]
+    fn test_knapsack_unbounded() {
+        let items = items();
+        let packed = knapsack_unbounded(&items, 7);
+        assert_eq!(packed.value, 9);
+        check(&items, 7, &packed);
+        let packed = knapsack_unbounded(&items, 10);
+        assert_eq!(packed.value, 14);
+        check(&items, 10, &packed);
+    }
+
+    #[test]
+    fn test_knapsack_bounded() {
+        let items = vec![Item { weight: 2, value: 3 }, Item { weight: 3, value: 5 }];
+        let packed = knapsack_bounded(&items, &[3, 1], 12);
+        assert_eq!(packed.value, 14);
+        assert_eq!(packed.counts, vec![3, 1]);
+        check(&items, 12, &packed);
+        let packed = knapsack_bounded(&items, &[10, 10], 12);
+        assert_eq!(packed.value, 20);
+        check(&items, 12, &packed);
+    }
+
+    #[test]
+    #[should_panic(expected = "one limit per item")]
+    fn test_knapsack_bounded_requires_limits() {
+        knapsack_bounded(&items(), &[1], 3);
+    }
+}
+
+/// An item that can be packed into a knapsack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    pub weight: usize,
+    pub value: u64,
+}
+
+/// The best packing found: its total value and how many of each item it takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Knapsack {
+    pub value: u64,
+    pub counts: Vec<usize>,
+}
+
+/// 0/1 knapsack: each item is taken at most once. O(n·capacity).
+pub fn knapsack_01(items: &[Item], capacity: usize) -> Knapsack {
+    // best[i][w] is the best value using the first i items within weight w
+    let mut best = vec![vec![0u64; capacity + 1]; items.len() + 1];
+    for (i, item) in items.iter().enumerate() {
+        for w in 0..=capacity {
+            best[i + 1][w] = best[i][w];
+            if item.weight <= w {
+                best[i + 1][w] = best[i + 1][w].max(best[i][w - item.weight] + item.value);
+            }
+        }
+    }
+
+    let mut counts = vec![0; items.len()];
+    let mut w = capacity;
+    for i in (0..items.len()).rev() {
+        if best[i + 1][w] != best[i][w] {
+            counts[i] = 1;
+            w -= items[i].weight;
+        }
+    }
+    Knapsack { value: best[items.len()][capacity], counts }
+}
+
+/// Unbounded knapsack: each item may be taken any number of times. O(n·capacity).
+pub fn knapsack_unbounded(items: &[Item], capacity: usize) -> Knapsack {
+    // best[w] is the best value within weight w; choice[w] the last item added to reach it
+    let mut best = vec![0u64; capacity + 1];
+    let mut choice: Vec<Option<usize>> = vec![None; capacity + 1];
+    for w in 1..=capacity {
+        for (i, item) in items.iter().enumerate() {
+            if item.weight <= w && item.weight > 0 && best[w - item.weight] + item.value > best[w] {
+                best[w] = best[w - item.weight] + item.value;
+                choice[w] = Some(i);
+            }
+        }
+        if best[w - 1] > best[w] {
+            best[w] = best[w - 1];
+            choice[w] = None;
+        }
+    }
+
+    let mut counts = vec![0; items.len()];
+    let mut w = capacity;
+    while w > 0 {
+        match choice[w] {
+            Some(i) => {
+                counts[i] += 1;
+                w -= items[i].weight;
+            }
+            None => w -= 1,
+        }
+    }
+    Knapsack { value: best[capacity], counts }
+}
+
+/// Bounded knapsack: item `i` may be taken up to `limits[i]` times.
+///
+/// Splits each item into power-of-two bundles and solves the resulting 0/1
+/// problem, O(capacity · Σ log limits[i]).
+///
+/// # Panics
+///
+/// Panics if `limits` does not have one entry per item.
+pub fn knapsack_bounded(items: &[Item], limits: &[usize], capacity: usize) -> Knapsack {
+    assert_eq!(items.len(), limits.len(), "knapsack_bounded needs one limit per item");
+    let mut bundles = Vec::new();
+    let mut owners = Vec::new();
+    for (i, (item, &limit)) in items.iter().zip(limits).enumerate() {
+        let mut remaining = limit;
+        let mut size = 1;
+        while remaining > 0 {
+            let take = size.min(remaining);
+            bundles.push(Item { weight: item.weight * take, value: item.value * take as u64 });
+            owners.push((i, take));
+            remaining -= take;
+            size *= 2;
+        }
+    }
+
+    let packed = knapsack_01(&bundles, capacity);
+    let mut counts = vec![0; items.len()];
+    for (&(i, take), &chosen) in owners.iter().zip(&packed.counts) {
+        counts[i] += take * chosen;
+    }
+    Knapsack { value: packed.value, counts }
+}
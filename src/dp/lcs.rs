@@ -0,0 +1,101 @@
+use std::ops::Range;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_common_subsequence() {
+        let a = b"ABCBDAB";
+        let b = b"BDCABA";
+        let pairs = longest_common_subsequence(a, b);
+        assert_eq!(pairs.len(), 4);
+        let common: Vec<u8> = pairs.iter().map(|&(i, _)| a[i]).collect();
+        assert!(pairs.iter().all(|&(i, j)| a[i] == b[j]));
+        assert!(pairs.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+        assert_eq!(common.len(), 4);
+    }
+
+    #[test]
+    fn test_longest_common_subsequence_of_lines() {
+        let a = ["fn main() {", "    foo();", "}"];
+        let b = ["fn main() {", "    bar();", "    foo();", "}"];
+        assert_eq!(longest_common_subsequence(&a, &b), vec![(0, 0), (1, 2), (2, 3)]);
+        assert!(longest_common_subsequence(&a, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        let found = longest_common_substring(b"xabcdey", b"zzabcdq").unwrap();
+        assert_eq!(found, CommonSubstring { a: 1..5, b: 2..6 });
+        assert_eq!(found.len(), 4);
+        assert_eq!(longest_common_substring(b"abc", b"xyz"), None);
+    }
+}
+
+/// Returns a longest common subsequence of `a` and `b` as matching `(i, j)`
+/// index pairs with `a[i] == b[j]`, increasing in both coordinates.
+///
+/// Classic O(n·m) table filled from the back so the reconstruction walks forward.
+pub fn longest_common_subsequence<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    // table[i][j] is the LCS length of a[i..] and b[j..]
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(table[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// The position of a common substring in both inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommonSubstring {
+    pub a: Range<usize>,
+    pub b: Range<usize>,
+}
+
+impl CommonSubstring {
+    pub fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+}
+
+/// Returns the first longest contiguous run shared by `a` and `b`, or `None`
+/// if they have no element in common. O(n·m) time, O(m) space.
+pub fn longest_common_substring<T: Eq>(a: &[T], b: &[T]) -> Option<CommonSubstring> {
+    // run[j + 1] is the length of the common run ending at a[i] and b[j]
+    let mut run = vec![0usize; b.len() + 1];
+    let mut best: Option<(usize, usize, usize)> = None;
+    for (i, x) in a.iter().enumerate() {
+        for j in (0..b.len()).rev() {
+            run[j + 1] = if *x == b[j] { run[j] + 1 } else { 0 };
+            if run[j + 1] > best.map_or(0, |(_, _, len)| len) {
+                best = Some((i + 1, j + 1, run[j + 1]));
+            }
+        }
+    }
+    best.map(|(a_end, b_end, len)| CommonSubstring { a: a_end - len..a_end, b: b_end - len..b_end })
+}
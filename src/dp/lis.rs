@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_increasing_subsequence() {
+        let seq = [10, 9, 2, 5, 3, 7, 101, 18];
+        let lis = longest_increasing_subsequence(&seq);
+        assert_eq!(lis.len(), 4);
+        let values: Vec<i32> = lis.iter().map(|&i| seq[i]).collect();
+        assert_eq!(values, vec![2, 3, 7, 18]);
+    }
+
+    #[test]
+    fn test_longest_increasing_subsequence_is_strict() {
+        let seq = [3, 3, 3, 4, 4];
+        assert_eq!(longest_increasing_subsequence(&seq), vec![2, 4]);
+        assert_eq!(longest_non_decreasing_subsequence(&seq), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_longest_increasing_subsequence_edge_cases() {
+        let empty: [u8; 0] = [];
+        assert!(longest_increasing_subsequence(&empty).is_empty());
+        assert_eq!(longest_increasing_subsequence(&[5, 4, 3]), vec![2]);
+        let words = ["apple", "cherry", "banana", "date"];
+        assert_eq!(longest_increasing_subsequence(&words), vec![0, 2, 3]);
+    }
+}
+
+/// Returns the indices of a longest strictly increasing subsequence.
+///
+/// Uses patience sorting with binary search, O(n log n). Among subsequences
+/// of maximal length the one ending with the smallest values is returned.
+pub fn longest_increasing_subsequence<T: Ord>(seq: &[T]) -> Vec<usize> {
+    lis_by(seq, |tail, x| tail < x)
+}
+
+/// Returns the indices of a longest non-decreasing subsequence.
+pub fn longest_non_decreasing_subsequence<T: Ord>(seq: &[T]) -> Vec<usize> {
+    lis_by(seq, |tail, x| tail <= x)
+}
+
+/// Patience sorting where `extends(tail, x)` says whether `x` may follow `tail`.
+fn lis_by<T, F>(seq: &[T], extends: F) -> Vec<usize>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    // tails[k] is the index of the smallest element ending an increasing run of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev: Vec<Option<usize>> = vec![None; seq.len()];
+    for (i, x) in seq.iter().enumerate() {
+        let k = tails.partition_point(|&t| extends(&seq[t], x));
+        prev[i] = k.checked_sub(1).map(|k| tails[k]);
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+    }
+
+    let mut lis = Vec::with_capacity(tails.len());
+    let mut curr = tails.last().copied();
+    while let Some(i) = curr {
+        lis.push(i);
+        curr = prev[i];
+    }
+    lis.reverse();
+    lis
+}
@@ -0,0 +1,11 @@
+//! Dynamic programming algorithms.
+//!
+//! Every function returns the optimal value together with a reconstructed
+//! solution (chosen indices, an edit script, item counts, ...).
+
+pub mod coin_change;
+pub mod edit_distance;
+pub mod knapsack;
+pub mod lcs;
+pub mod lis;
+pub mod rod_cutting;
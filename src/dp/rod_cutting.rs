@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rod_cutting() {
+        let prices = [1, 5, 8, 9, 10, 17, 17, 20];
+        let cut = rod_cutting(&prices, 8);
+        assert_eq!(cut.revenue, 22);
+        assert_eq!(cut.pieces, vec![2, 6]);
+        assert_eq!(rod_cutting(&prices, 4).revenue, 10);
+    }
+
+    #[test]
+    fn test_rod_cutting_longer_than_price_list() {
+        let cut = rod_cutting(&[2, 5], 5);
+        assert_eq!(cut.revenue, 12);
+        assert_eq!(cut.pieces.iter().sum::<usize>(), 5);
+        assert_eq!(rod_cutting(&[], 3), RodCut { revenue: 0, pieces: vec![] });
+    }
+}
+
+/// The best way to cut a rod: the total revenue and the piece lengths, ascending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RodCut {
+    pub revenue: u64,
+    pub pieces: Vec<usize>,
+}
+
+/// Cuts a rod of `length` to maximise revenue, where `prices[i]` is the price
+/// of a piece of length `i + 1`. Lengths without a price cannot be sold, so a
+/// leftover remnant may be discarded. O(length · prices.len()).
+pub fn rod_cutting(prices: &[u64], length: usize) -> RodCut {
+    // best[l] is the best revenue for a rod of length l; first[l] the first piece cut from it
+    let mut best = vec![0u64; length + 1];
+    let mut first = vec![0usize; length + 1];
+    for l in 1..=length {
+        for (i, &price) in prices.iter().enumerate().take(l) {
+            let piece = i + 1;
+            if best[l - piece] + price > best[l] {
+                best[l] = best[l - piece] + price;
+                first[l] = piece;
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut l = length;
+    while l > 0 && first[l] > 0 {
+        pieces.push(first[l]);
+        l -= first[l];
+    }
+    pieces.sort_unstable();
+    RodCut { revenue: best[length], pieces }
+}
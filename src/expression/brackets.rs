@@ -0,0 +1,98 @@
+use crate::data_structures::stack::Stack;
+use std::fmt;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced() {
+        assert_eq!(check_brackets("fn main() { let v = vec![(1, 2)]; }"), Ok(()));
+        assert_eq!(check_brackets(""), Ok(()));
+        assert_eq!(check_brackets("no brackets"), Ok(()));
+    }
+
+    #[test]
+    fn test_unbalanced() {
+        assert_eq!(
+            check_brackets("[(])"),
+            Err(BracketError::Mismatched { open: '(', open_position: 1, close: ']', close_position: 2 })
+        );
+        assert_eq!(check_brackets("a)"), Err(BracketError::UnexpectedClose { close: ')', position: 1 }));
+        assert_eq!(
+            check_brackets("{ [ }"),
+            Err(BracketError::Mismatched { open: '[', open_position: 2, close: '}', close_position: 4 })
+        );
+        assert_eq!(check_brackets("((x)"), Err(BracketError::Unclosed { open: '(', position: 0 }));
+        assert_eq!(
+            BracketError::Unclosed { open: '{', position: 3 }.to_string(),
+            "'{' at position 3 is never closed"
+        );
+    }
+}
+
+/// Why a string's brackets do not balance. Positions are byte offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BracketError {
+    /// A closing bracket with nothing open.
+    UnexpectedClose { close: char, position: usize },
+    /// A closing bracket of a different kind than the innermost open one.
+    Mismatched { open: char, open_position: usize, close: char, close_position: usize },
+    /// An opening bracket left open at the end of the input.
+    Unclosed { open: char, position: usize },
+}
+
+impl fmt::Display for BracketError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BracketError::UnexpectedClose { close, position } => {
+                write!(f, "unexpected {:?} at position {}", close, position)
+            }
+            BracketError::Mismatched { open, open_position, close, close_position } => write!(
+                f,
+                "{:?} at position {} does not close {:?} at position {}",
+                close, close_position, open, open_position
+            ),
+            BracketError::Unclosed { open, position } => {
+                write!(f, "{:?} at position {} is never closed", open, position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BracketError {}
+
+/// Checks that `()`, `[]` and `{}` in `text` are balanced and properly
+/// nested. Other characters are ignored.
+///
+/// # Errors
+///
+/// Reports the first bracket that breaks the nesting.
+pub fn check_brackets(text: &str) -> Result<(), BracketError> {
+    let mut open: Stack<(char, usize)> = Stack::new();
+    for (position, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' => open.push((c, position)),
+            ')' | ']' | '}' => {
+                let (opener, open_position) = open.pop().ok_or(BracketError::UnexpectedClose { close: c, position })?;
+                let expected = match opener {
+                    '(' => ')',
+                    '[' => ']',
+                    _ => '}',
+                };
+                if c != expected {
+                    return Err(BracketError::Mismatched {
+                        open: opener,
+                        open_position,
+                        close: c,
+                        close_position: position,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    match open.pop() {
+        Some((open, position)) => Err(BracketError::Unclosed { open, position }),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,135 @@
+//! A small arithmetic expression engine.
+//!
+//! `tokenizer` splits infix source into tokens, `shunting_yard` reorders
+//! them into reverse Polish notation and `rpn` evaluates the result on a
+//! `Stack`. `Expression` ties the three together so an expression can be
+//! parsed once and evaluated against many sets of variables.
+
+pub mod brackets;
+pub mod rpn;
+pub mod shunting_yard;
+pub mod tokenizer;
+
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expression_parse_and_evaluate() {
+        let expression = Expression::parse("2 * (x + 1) ^ 2 - max(x, 10) / 5").unwrap();
+        let variables = HashMap::from([(String::from("x"), 3.0)]);
+        assert_eq!(expression.evaluate(&variables), Ok(30.0));
+        assert_eq!(expression.to_string(), "2 x 1 + 2 ^ * x 10 max(2) 5 / -");
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        let none = HashMap::new();
+        assert_eq!(evaluate("1 / (2 - 2)", &none), Err(ExprError::DivisionByZero));
+        assert_eq!(evaluate("y + 1", &none), Err(ExprError::UnknownIdentifier(String::from("y"))));
+        assert_eq!(evaluate("(1 + 2", &none), Err(ExprError::MismatchedParenthesis { position: 0 }));
+        assert_eq!(evaluate("1 + 2)", &none), Err(ExprError::MismatchedParenthesis { position: 5 }));
+        assert_eq!(
+            ExprError::MismatchedParenthesis { position: 5 }.to_string(),
+            "mismatched parenthesis at position 5"
+        );
+    }
+}
+
+/// An error raised while tokenizing, parsing or evaluating an expression.
+///
+/// Positions are byte offsets into the source.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    UnexpectedCharacter { position: usize, found: char },
+    InvalidNumber { position: usize },
+    /// A token that cannot follow the previous one, e.g. `1 2` or `* 3`.
+    UnexpectedToken { position: usize },
+    UnexpectedEnd,
+    MismatchedParenthesis { position: usize },
+    UnknownIdentifier(String),
+    UnknownFunction(String),
+    WrongArity { function: String, found: usize },
+    DivisionByZero,
+    /// An RPN sequence that leaves the evaluation stack short or with extra values.
+    MalformedRpn,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprError::UnexpectedCharacter { position, found } => {
+                write!(f, "unexpected character {:?} at position {}", found, position)
+            }
+            ExprError::InvalidNumber { position } => write!(f, "invalid number at position {}", position),
+            ExprError::UnexpectedToken { position } => write!(f, "unexpected token at position {}", position),
+            ExprError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ExprError::MismatchedParenthesis { position } => {
+                write!(f, "mismatched parenthesis at position {}", position)
+            }
+            ExprError::UnknownIdentifier(name) => write!(f, "unknown identifier {:?}", name),
+            ExprError::UnknownFunction(name) => write!(f, "unknown function {:?}", name),
+            ExprError::WrongArity { function, found } => {
+                write!(f, "{} does not take {} argument(s)", function, found)
+            }
+            ExprError::DivisionByZero => write!(f, "division by zero"),
+            ExprError::MalformedRpn => write!(f, "malformed RPN sequence"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// A parsed expression in reverse Polish notation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    rpn: Vec<rpn::RpnToken>,
+}
+
+impl Expression {
+    /// Parses infix `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first tokenizer or syntax error.
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        let tokens = tokenizer::tokenize(source)?;
+        let rpn = shunting_yard::to_rpn(&tokens)?;
+        Ok(Expression { rpn })
+    }
+
+    pub fn rpn(&self) -> &[rpn::RpnToken] {
+        &self.rpn
+    }
+
+    /// Evaluates the expression with the given variable values.
+    ///
+    /// # Errors
+    ///
+    /// Fails on unknown variables or functions, wrong argument counts and
+    /// division by zero.
+    pub fn evaluate(&self, variables: &HashMap<String, f64>) -> Result<f64, ExprError> {
+        rpn::evaluate(&self.rpn, variables)
+    }
+}
+
+/// Renders the RPN form, tokens separated by spaces.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, token) in self.rpn.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", token)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses and evaluates `source` in one go.
+pub fn evaluate(source: &str, variables: &HashMap<String, f64>) -> Result<f64, ExprError> {
+    Expression::parse(source)?.evaluate(variables)
+}
@@ -0,0 +1,149 @@
+use crate::data_structures::stack::Stack;
+use crate::expression::ExprError;
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(value: f64) -> RpnToken {
+        RpnToken::Number(value)
+    }
+
+    #[test]
+    fn test_evaluate_rpn() {
+        let rpn = [number(3.0), number(4.0), number(2.0), RpnToken::Operator(Operator::Mul), RpnToken::Operator(Operator::Add)];
+        assert_eq!(evaluate(&rpn, &HashMap::new()), Ok(11.0));
+        let rpn = [number(2.0), number(2.0), RpnToken::Operator(Operator::Pow), RpnToken::Operator(Operator::Neg)];
+        assert_eq!(evaluate(&rpn, &HashMap::new()), Ok(-4.0));
+    }
+
+    #[test]
+    fn test_evaluate_variables_and_functions() {
+        let variables = HashMap::from([(String::from("rate"), 0.5)]);
+        let rpn = [
+            RpnToken::Variable(String::from("rate")),
+            number(-3.0),
+            number(9.0),
+            RpnToken::Call { function: String::from("sqrt"), args: 1 },
+            RpnToken::Call { function: String::from("min"), args: 3 },
+            RpnToken::Call { function: String::from("abs"), args: 1 },
+        ];
+        assert_eq!(evaluate(&rpn, &variables), Ok(3.0));
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        let none = HashMap::new();
+        let div = [number(1.0), number(0.0), RpnToken::Operator(Operator::Div)];
+        assert_eq!(evaluate(&div, &none), Err(ExprError::DivisionByZero));
+        let call = [RpnToken::Call { function: String::from("max"), args: 0 }];
+        assert_eq!(evaluate(&call, &none), Err(ExprError::WrongArity { function: String::from("max"), found: 0 }));
+        let call = [number(1.0), RpnToken::Call { function: String::from("tan"), args: 1 }];
+        assert_eq!(evaluate(&call, &none), Err(ExprError::UnknownFunction(String::from("tan"))));
+        assert_eq!(evaluate(&[RpnToken::Operator(Operator::Add)], &none), Err(ExprError::MalformedRpn));
+        assert_eq!(evaluate(&[number(1.0), number(2.0)], &none), Err(ExprError::MalformedRpn));
+        assert_eq!(evaluate(&[], &none), Err(ExprError::MalformedRpn));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    /// Unary minus.
+    Neg,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RpnToken {
+    Number(f64),
+    Variable(String),
+    Operator(Operator),
+    /// Calls `function` with the top `args` values, the deepest being the first argument.
+    Call { function: String, args: usize },
+}
+
+impl fmt::Display for RpnToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RpnToken::Number(value) => write!(f, "{}", value),
+            RpnToken::Variable(name) => write!(f, "{}", name),
+            RpnToken::Operator(operator) => {
+                let symbol = match operator {
+                    Operator::Add => "+",
+                    Operator::Sub => "-",
+                    Operator::Mul => "*",
+                    Operator::Div => "/",
+                    Operator::Pow => "^",
+                    Operator::Neg => "neg",
+                };
+                write!(f, "{}", symbol)
+            }
+            RpnToken::Call { function, args } => write!(f, "{}({})", function, args),
+        }
+    }
+}
+
+/// Applies a built-in function: `min` and `max` take one or more arguments,
+/// `abs` and `sqrt` exactly one.
+fn call(function: &str, args: &[f64]) -> Result<f64, ExprError> {
+    let wrong_arity = || ExprError::WrongArity { function: function.to_string(), found: args.len() };
+    match (function, args) {
+        ("min" | "max", []) => Err(wrong_arity()),
+        ("min", _) => Ok(args.iter().copied().fold(f64::INFINITY, f64::min)),
+        ("max", _) => Ok(args.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+        ("abs", &[x]) => Ok(x.abs()),
+        ("sqrt", &[x]) => Ok(x.sqrt()),
+        ("abs" | "sqrt", _) => Err(wrong_arity()),
+        _ => Err(ExprError::UnknownFunction(function.to_string())),
+    }
+}
+
+/// Evaluates an RPN sequence on a `Stack`, looking variables up in `variables`.
+///
+/// # Errors
+///
+/// Fails on unknown variables or functions, wrong argument counts, division
+/// by zero, and sequences that do not leave exactly one value.
+pub fn evaluate(rpn: &[RpnToken], variables: &HashMap<String, f64>) -> Result<f64, ExprError> {
+    let mut values: Stack<f64> = Stack::new();
+    for token in rpn {
+        let value = match token {
+            RpnToken::Number(value) => *value,
+            RpnToken::Variable(name) => *variables
+                .get(name)
+                .ok_or_else(|| ExprError::UnknownIdentifier(name.clone()))?,
+            RpnToken::Operator(Operator::Neg) => -values.pop().ok_or(ExprError::MalformedRpn)?,
+            RpnToken::Operator(operator) => {
+                let b = values.pop().ok_or(ExprError::MalformedRpn)?;
+                let a = values.pop().ok_or(ExprError::MalformedRpn)?;
+                match operator {
+                    Operator::Add => a + b,
+                    Operator::Sub => a - b,
+                    Operator::Mul => a * b,
+                    Operator::Div if b == 0.0 => return Err(ExprError::DivisionByZero),
+                    Operator::Div => a / b,
+                    Operator::Pow => a.powf(b),
+                    Operator::Neg => unreachable!("unary minus is handled above"),
+                }
+            }
+            RpnToken::Call { function, args } => {
+                if *args > values.length() {
+                    return Err(ExprError::MalformedRpn);
+                }
+                let mut arguments: Vec<f64> = (0..*args).filter_map(|_| values.pop()).collect();
+                arguments.reverse();
+                call(function, &arguments)?
+            }
+        };
+        values.push(value);
+    }
+    match (values.pop(), values.is_empty()) {
+        (Some(value), true) => Ok(value),
+        _ => Err(ExprError::MalformedRpn),
+    }
+}
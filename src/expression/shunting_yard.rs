@@ -0,0 +1,190 @@
+use crate::data_structures::stack::Stack;
+use crate::expression::rpn::{Operator, RpnToken};
+use crate::expression::tokenizer::{Token, TokenKind};
+use crate::expression::ExprError;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::tokenizer::tokenize;
+
+    fn rpn(source: &str) -> Result<String, ExprError> {
+        let tokens = tokenize(source)?;
+        let rpn = to_rpn(&tokens)?;
+        Ok(rpn.iter().map(|token| token.to_string()).collect::<Vec<_>>().join(" "))
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(rpn("1 + 2 * 3").unwrap(), "1 2 3 * +");
+        assert_eq!(rpn("(1 + 2) * 3").unwrap(), "1 2 + 3 *");
+        assert_eq!(rpn("8 - 4 - 2").unwrap(), "8 4 - 2 -");
+        assert_eq!(rpn("2 ^ 3 ^ 2").unwrap(), "2 3 2 ^ ^");
+    }
+
+    #[test]
+    fn test_unary_minus() {
+        assert_eq!(rpn("-2 ^ 2").unwrap(), "2 2 ^ neg");
+        assert_eq!(rpn("-a * b").unwrap(), "a neg b *");
+        assert_eq!(rpn("2 ^ -1").unwrap(), "2 1 neg ^");
+        assert_eq!(rpn("3 - -x").unwrap(), "3 x neg -");
+    }
+
+    #[test]
+    fn test_function_calls() {
+        assert_eq!(rpn("max(1, min(2, 3), x)").unwrap(), "1 2 3 min(2) x max(3)");
+        assert_eq!(rpn("f() + 1").unwrap(), "f(0) 1 +");
+        assert_eq!(rpn("abs(-(1 - 2))").unwrap(), "1 2 - neg abs(1)");
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(rpn("1 2"), Err(ExprError::UnexpectedToken { position: 2 }));
+        assert_eq!(rpn("* 3"), Err(ExprError::UnexpectedToken { position: 0 }));
+        assert_eq!(rpn("1 +"), Err(ExprError::UnexpectedEnd));
+        assert_eq!(rpn(""), Err(ExprError::UnexpectedEnd));
+        assert_eq!(rpn("1, 2"), Err(ExprError::UnexpectedToken { position: 1 }));
+        assert_eq!(rpn("()"), Err(ExprError::UnexpectedToken { position: 1 }));
+        assert_eq!(rpn("max(1,)"), Err(ExprError::UnexpectedToken { position: 6 }));
+        assert_eq!(rpn("((1)"), Err(ExprError::MismatchedParenthesis { position: 0 }));
+        assert_eq!(rpn("1)"), Err(ExprError::MismatchedParenthesis { position: 1 }));
+    }
+}
+
+/// An entry on the operator stack.
+enum Pending {
+    Operator(Operator),
+    /// An open parenthesis, belonging to a call of `function` if it has one.
+    Paren { position: usize, function: Option<String> },
+}
+
+fn precedence(operator: Operator) -> u8 {
+    match operator {
+        Operator::Add | Operator::Sub => 1,
+        Operator::Mul | Operator::Div => 2,
+        Operator::Neg => 3,
+        Operator::Pow => 4,
+    }
+}
+
+/// Converts infix tokens to reverse Polish notation with Dijkstra's
+/// shunting-yard algorithm.
+///
+/// `^` is right-associative and binds tighter than unary minus, so `-2 ^ 2`
+/// is `-(2 ^ 2)`. An identifier directly followed by `(` is a function call;
+/// its argument count is recorded in the emitted `RpnToken::Call`.
+///
+/// # Errors
+///
+/// Fails on mismatched parentheses, misplaced tokens and incomplete input.
+pub fn to_rpn(tokens: &[Token]) -> Result<Vec<RpnToken>, ExprError> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut pending: Stack<Pending> = Stack::new();
+    // Arguments completed so far for each open call, innermost on top
+    let mut arg_counts: Stack<usize> = Stack::new();
+    let mut expect_operand = true;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let Token { kind, position } = &tokens[i];
+        let position = *position;
+        let unexpected = ExprError::UnexpectedToken { position };
+        match kind {
+            TokenKind::Number(_) | TokenKind::Identifier(_) | TokenKind::LeftParen if !expect_operand => {
+                return Err(unexpected);
+            }
+            TokenKind::Number(value) => {
+                output.push(RpnToken::Number(*value));
+                expect_operand = false;
+            }
+            TokenKind::Identifier(name) => match tokens.get(i + 1) {
+                Some(Token { kind: TokenKind::LeftParen, position }) => {
+                    pending.push(Pending::Paren { position: *position, function: Some(name.clone()) });
+                    arg_counts.push(0);
+                    i += 1;
+                }
+                _ => {
+                    output.push(RpnToken::Variable(name.clone()));
+                    expect_operand = false;
+                }
+            },
+            TokenKind::LeftParen => pending.push(Pending::Paren { position, function: None }),
+            TokenKind::Comma => {
+                if expect_operand {
+                    return Err(unexpected);
+                }
+                pop_operators(&mut pending, &mut output);
+                match pending.peek() {
+                    Some(Pending::Paren { function: Some(_), .. }) => {
+                        *arg_counts.peek_mut().expect("every call has an argument count") += 1;
+                    }
+                    _ => return Err(unexpected),
+                }
+                expect_operand = true;
+            }
+            TokenKind::RightParen => {
+                let empty_call = i > 0
+                    && tokens[i - 1].kind == TokenKind::LeftParen
+                    && matches!(pending.peek(), Some(Pending::Paren { function: Some(_), .. }));
+                if expect_operand && !empty_call {
+                    return Err(unexpected);
+                }
+                pop_operators(&mut pending, &mut output);
+                match pending.pop() {
+                    Some(Pending::Paren { function: Some(function), .. }) => {
+                        let completed = arg_counts.pop().expect("every call has an argument count");
+                        let args = if empty_call { completed } else { completed + 1 };
+                        output.push(RpnToken::Call { function, args });
+                    }
+                    Some(Pending::Paren { function: None, .. }) => {}
+                    _ => return Err(ExprError::MismatchedParenthesis { position }),
+                }
+                expect_operand = false;
+            }
+            TokenKind::Minus if expect_operand => pending.push(Pending::Operator(Operator::Neg)),
+            TokenKind::Plus | TokenKind::Minus | TokenKind::Star | TokenKind::Slash | TokenKind::Caret => {
+                if expect_operand {
+                    return Err(unexpected);
+                }
+                let operator = match kind {
+                    TokenKind::Plus => Operator::Add,
+                    TokenKind::Minus => Operator::Sub,
+                    TokenKind::Star => Operator::Mul,
+                    TokenKind::Slash => Operator::Div,
+                    _ => Operator::Pow,
+                };
+                // Pop operators that bind tighter, or equally tight when left-associative
+                while let Some(&Pending::Operator(top)) = pending.peek() {
+                    let tighter = precedence(top) > precedence(operator)
+                        || (precedence(top) == precedence(operator) && operator != Operator::Pow);
+                    if !tighter {
+                        break;
+                    }
+                    output.push(RpnToken::Operator(top));
+                    pending.pop();
+                }
+                pending.push(Pending::Operator(operator));
+                expect_operand = true;
+            }
+        }
+        i += 1;
+    }
+
+    if expect_operand {
+        return Err(ExprError::UnexpectedEnd);
+    }
+    while let Some(entry) = pending.pop() {
+        match entry {
+            Pending::Operator(operator) => output.push(RpnToken::Operator(operator)),
+            Pending::Paren { position, .. } => return Err(ExprError::MismatchedParenthesis { position }),
+        }
+    }
+    Ok(output)
+}
+
+/// Moves operators to the output down to the nearest open parenthesis.
+fn pop_operators(pending: &mut Stack<Pending>, output: &mut Vec<RpnToken>) {
+    while let Some(&Pending::Operator(operator)) = pending.peek() {
+        output.push(RpnToken::Operator(operator));
+        pending.pop();
+    }
+}
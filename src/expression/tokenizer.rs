@@ -0,0 +1,120 @@
+use crate::expression::ExprError;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source).unwrap().into_iter().map(|token| token.kind).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            kinds("max(x_1, 2.5)^-3"),
+            vec![
+                TokenKind::Identifier(String::from("max")),
+                TokenKind::LeftParen,
+                TokenKind::Identifier(String::from("x_1")),
+                TokenKind::Comma,
+                TokenKind::Number(2.5),
+                TokenKind::RightParen,
+                TokenKind::Caret,
+                TokenKind::Minus,
+                TokenKind::Number(3.0),
+            ]
+        );
+        assert!(kinds("  ").is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_positions() {
+        let tokens = tokenize("a +  12").unwrap();
+        let positions: Vec<usize> = tokens.iter().map(|token| token.position).collect();
+        assert_eq!(positions, vec![0, 2, 5]);
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        assert_eq!(tokenize("1 # 2"), Err(ExprError::UnexpectedCharacter { position: 2, found: '#' }));
+        assert_eq!(tokenize("x + 1.2.3"), Err(ExprError::InvalidNumber { position: 4 }));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Number(f64),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+/// A token and the byte offset where it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub position: usize,
+}
+
+/// Splits infix source into tokens, skipping whitespace.
+///
+/// Numbers are decimal with an optional fractional part; identifiers start
+/// with a letter or `_`. Whether a `-` is unary is left to the parser.
+///
+/// # Errors
+///
+/// Fails on characters that start no token and on malformed numbers.
+pub fn tokenize(source: &str) -> Result<Vec<Token>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(position, c)) = chars.peek() {
+        let kind = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = position;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let value = source[position..end].parse().map_err(|_| ExprError::InvalidNumber { position })?;
+                tokens.push(Token { kind: TokenKind::Number(value), position });
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = position;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token { kind: TokenKind::Identifier(source[position..end].to_string()), position });
+                continue;
+            }
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '^' => TokenKind::Caret,
+            '(' => TokenKind::LeftParen,
+            ')' => TokenKind::RightParen,
+            ',' => TokenKind::Comma,
+            found => return Err(ExprError::UnexpectedCharacter { position, found }),
+        };
+        chars.next();
+        tokens.push(Token { kind, position });
+    }
+    Ok(tokens)
+}
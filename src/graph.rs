@@ -0,0 +1,108 @@
+//! Graph abstraction traits.
+//!
+//! The traits are implemented for *references* to the graph representations
+//! (`&WeightedAdjacencyList`, `&WeightedAdjacencyMatrix`, `&CsrGraph`), so the
+//! algorithms take the graph by value as a cheap `Copy` handle and one
+//! implementation serves every representation.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// The node and edge weight types of a graph.
+pub trait GraphBase {
+    type NodeId: Copy + PartialEq;
+    type EdgeWeight;
+}
+
+/// A graph that can enumerate the outgoing neighbors of a node.
+pub trait IntoNeighbors: GraphBase + Copy {
+    /// Iterates over the targets of the outgoing edges of `node`.
+    /// Yields nothing for a node that is not part of the graph.
+    fn neighbors(self, node: Self::NodeId) -> impl Iterator<Item = Self::NodeId>;
+}
+
+/// A graph that can enumerate all of its nodes.
+pub trait IntoNodeIdentifiers: GraphBase + Copy {
+    fn node_identifiers(self) -> impl Iterator<Item = Self::NodeId>;
+}
+
+/// A reference to a single edge.
+pub trait EdgeRef: Copy {
+    type NodeId;
+    type Weight;
+    fn source(&self) -> Self::NodeId;
+    fn target(&self) -> Self::NodeId;
+    fn weight(&self) -> &Self::Weight;
+}
+
+impl<N: Copy, W> EdgeRef for (N, N, &W) {
+    type NodeId = N;
+    type Weight = W;
+
+    fn source(&self) -> N {
+        self.0
+    }
+
+    fn target(&self) -> N {
+        self.1
+    }
+
+    fn weight(&self) -> &W {
+        self.2
+    }
+}
+
+/// A graph that can enumerate the outgoing edges of a node together with their weights.
+pub trait IntoEdges: IntoNeighbors {
+    type EdgeRef: EdgeRef<NodeId = Self::NodeId, Weight = Self::EdgeWeight>;
+
+    /// Iterates over the outgoing edges of `node`.
+    /// Yields nothing for a node that is not part of the graph.
+    fn edges(self, node: Self::NodeId) -> impl Iterator<Item = Self::EdgeRef>;
+}
+
+/// A graph whose nodes map to dense indices `0..node_bound()`, so per-node
+/// data can live in a `Vec`.
+pub trait NodeIndexable: GraphBase + Copy {
+    /// Returns one more than the largest node index.
+    fn node_bound(self) -> usize;
+    fn to_index(self, node: Self::NodeId) -> usize;
+    #[allow(clippy::wrong_self_convention)]
+    fn from_index(self, index: usize) -> Self::NodeId;
+}
+
+/// A set of visited nodes.
+pub trait VisitMap<N> {
+    /// Marks `node` as visited, returning `true` if it was not visited before.
+    fn visit(&mut self, node: N) -> bool;
+    fn is_visited(&self, node: &N) -> bool;
+}
+
+impl VisitMap<usize> for Vec<bool> {
+    fn visit(&mut self, node: usize) -> bool {
+        if node >= self.len() {
+            self.resize(node + 1, false);
+        }
+        !std::mem::replace(&mut self[node], true)
+    }
+
+    fn is_visited(&self, node: &usize) -> bool {
+        self.get(*node).copied().unwrap_or(false)
+    }
+}
+
+impl<N: Hash + Eq> VisitMap<N> for HashSet<N> {
+    fn visit(&mut self, node: N) -> bool {
+        self.insert(node)
+    }
+
+    fn is_visited(&self, node: &N) -> bool {
+        self.contains(node)
+    }
+}
+
+/// A graph that can create an empty visit map sized for its nodes.
+pub trait Visitable: GraphBase + Copy {
+    type Map: VisitMap<Self::NodeId>;
+    fn visit_map(self) -> Self::Map;
+}
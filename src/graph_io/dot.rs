@@ -0,0 +1,421 @@
+use super::{parse_weight, Cursor, ParseError, Position};
+use crate::data_structures::weighted_adj_list::WeightedAdjacencyList;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structures::csr_graph::CsrGraph;
+    use crate::dijkstras::dijkstras_shortest_path;
+
+    fn edge(graph: &WeightedAdjacencyList<String, u32>, from: &str, to: &str) -> Option<u32> {
+        graph.get_weight(&from.to_string(), &to.to_string()).copied()
+    }
+
+    #[test]
+    fn test_parse_digraph() {
+        let input = r#"
+            // the primeagen class graph, partially
+            digraph G {
+                rankdir = LR;
+                node [shape=circle];
+                0 -> 1 [weight=3];
+                0 -> 2 [label="1", weight=1]
+                1 -> 4 -> 5 [weight=2];
+                "isolated node";
+                /* block
+                   comment */
+                # preprocessor-style comment
+            }
+        "#;
+        let graph: WeightedAdjacencyList<String, u32> = parse_dot(input).unwrap();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(edge(&graph, "0", "1"), Some(3));
+        assert_eq!(edge(&graph, "0", "2"), Some(1));
+        assert_eq!(edge(&graph, "1", "4"), Some(2));
+        assert_eq!(edge(&graph, "4", "5"), Some(2));
+        assert_eq!(edge(&graph, "1", "0"), None);
+        assert!(graph.get_neighbors(&"isolated node".to_string()).is_some());
+    }
+
+    #[test]
+    fn test_parse_undirected_graph_adds_both_directions() {
+        let graph: WeightedAdjacencyList<String, u32> = parse_dot("strict graph { a -- b [label=7] }").unwrap();
+        assert_eq!(edge(&graph, "a", "b"), Some(7));
+        assert_eq!(edge(&graph, "b", "a"), Some(7));
+    }
+
+    #[test]
+    fn test_missing_weight_uses_default() {
+        let graph: WeightedAdjacencyList<String, u32> = parse_dot("digraph { a -> b }").unwrap();
+        assert_eq!(edge(&graph, "a", "b"), Some(0));
+    }
+
+    #[test]
+    fn test_parse_errors_carry_position() {
+        let err = parse_dot::<u32>("digraph {\n  a -> ;\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+        let err = parse_dot::<u32>("digraph {\n  a -- b\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        let err = parse_dot::<u32>("digraph {\n  a -> b [weight=heavy]\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 18));
+        let err = parse_dot::<u32>("digraph { \"a -> b }").unwrap_err();
+        assert_eq!(err.message, "unterminated string");
+    }
+
+    #[test]
+    fn test_parsed_graph_runs_dijkstras() {
+        let input = "digraph { 0 -> 1 [weight=3]; 0 -> 2 [weight=1]; 1 -> 4 [weight=1]; 2 -> 3 [weight=7]; 3 -> 4 [weight=5]; 4 -> 5 [weight=2]; 3 -> 6 [weight=1]; 6 -> 5 [weight=1] }";
+        let graph: WeightedAdjacencyList<String, u32> = parse_dot(input).unwrap();
+        let csr = CsrGraph::from(&graph);
+        let source = csr.index_of(&"0".to_string()).unwrap();
+        let sink = csr.index_of(&"5".to_string()).unwrap();
+        let path: Vec<&str> = dijkstras_shortest_path(source, sink, &csr)
+            .unwrap()
+            .into_iter()
+            .map(|i| csr.vertex(i).unwrap().as_str())
+            .collect();
+        assert_eq!(path, vec!["0", "1", "4", "5"]);
+    }
+
+    #[test]
+    fn test_dot_round_trip() {
+        let mut graph: WeightedAdjacencyList<String, i32> = WeightedAdjacencyList::new();
+        graph.add_edge("a".to_string(), "b c".to_string(), -4);
+        graph.add_edge("b c".to_string(), "quote\"d".to_string(), 9);
+        graph.add_edge("quote\"d".to_string(), "a".to_string(), 0);
+        graph.add_vertex("lonely".to_string());
+        let text = write_dot(&graph);
+        let parsed: WeightedAdjacencyList<String, i32> = parse_dot(&text).unwrap();
+        assert_eq!(parsed, graph);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    DirectedEdge,
+    UndirectedEdge,
+}
+
+/// A token paired with its start position.
+type Spanned = (Token, Position);
+
+/// An attribute `key=value` pair with the position of its value.
+type Attribute = (String, String, Position);
+
+struct Lexer<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl Lexer<'_> {
+    /// Returns the next token with its start position, or `None` at end of input.
+    fn next_token(&mut self) -> Result<Option<Spanned>, ParseError> {
+        self.skip_trivia()?;
+        let position = self.cursor.position();
+        let Some(c) = self.cursor.peek() else {
+            return Ok(None);
+        };
+        let token = match c {
+            '{' | '}' | '[' | ']' | '=' | ';' | ',' => {
+                self.cursor.bump();
+                match c {
+                    '{' => Token::LBrace,
+                    '}' => Token::RBrace,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    '=' => Token::Equals,
+                    ';' => Token::Semicolon,
+                    _ => Token::Comma,
+                }
+            }
+            '-' if self.is_edge_op() => {
+                self.cursor.bump();
+                match self.cursor.bump() {
+                    Some('>') => Token::DirectedEdge,
+                    _ => Token::UndirectedEdge,
+                }
+            }
+            '"' => Token::Id(self.quoted()?),
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => Token::Id(self.bare()),
+            c => return Err(self.cursor.error(format!("unexpected character '{}'", c))),
+        };
+        Ok(Some((token, position)))
+    }
+
+    fn is_edge_op(&self) -> bool {
+        let mut ahead = self.cursor.chars.clone();
+        ahead.next();
+        matches!(ahead.next(), Some('>') | Some('-'))
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        let mut line_start = self.cursor.column == 1;
+        while let Some(c) = self.cursor.peek() {
+            if c.is_whitespace() {
+                self.cursor.bump();
+                line_start |= c == '\n';
+            } else if c == '#' && line_start {
+                self.skip_line();
+            } else if c == '/' {
+                let mut ahead = self.cursor.chars.clone();
+                ahead.next();
+                match ahead.next() {
+                    Some('/') => self.skip_line(),
+                    Some('*') => {
+                        let (line, column) = self.cursor.position();
+                        self.cursor.bump();
+                        self.cursor.bump();
+                        loop {
+                            match self.cursor.bump() {
+                                None => return Err(ParseError::new(line, column, "unterminated comment")),
+                                Some('*') if self.cursor.eat('/') => break,
+                                Some(_) => {}
+                            }
+                        }
+                        line_start = false;
+                    }
+                    _ => return Ok(()),
+                }
+            } else {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.cursor.peek() {
+            if c == '\n' {
+                break;
+            }
+            self.cursor.bump();
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, ParseError> {
+        let (line, column) = self.cursor.position();
+        self.cursor.bump();
+        let mut value = String::new();
+        loop {
+            match self.cursor.bump() {
+                None => return Err(ParseError::new(line, column, "unterminated string")),
+                Some('"') => return Ok(value),
+                Some('\\') if self.cursor.eat('"') => value.push('"'),
+                Some('\\') if self.cursor.eat('\\') => value.push('\\'),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn bare(&mut self) -> String {
+        let mut value = String::new();
+        while let Some(c) = self.cursor.peek() {
+            let continues_number = c == '-' && value.is_empty();
+            if c.is_alphanumeric() || c == '_' || c == '.' || continues_number {
+                value.push(c);
+                self.cursor.bump();
+            } else {
+                break;
+            }
+        }
+        value
+    }
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<Spanned>,
+    end: Position,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        let end = input.lines().enumerate().last().map_or((1, 1), |(i, line)| {
+            (i + 1, line.chars().count() + 1)
+        });
+        Parser { lexer: Lexer { cursor: Cursor::new(input) }, peeked: None, end }
+    }
+
+    fn peek(&mut self) -> Result<Option<&Token>, ParseError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+        Ok(self.peeked.as_ref().map(|(token, _)| token))
+    }
+
+    fn next(&mut self) -> Result<Spanned, ParseError> {
+        self.peek()?;
+        self.peeked
+            .take()
+            .ok_or_else(|| ParseError::new(self.end.0, self.end.1, "unexpected end of input"))
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<Position, ParseError> {
+        let (token, position) = self.next()?;
+        if token == expected {
+            Ok(position)
+        } else {
+            Err(ParseError::new(position.0, position.1, format!("expected {}", what)))
+        }
+    }
+
+    fn id(&mut self) -> Result<(String, Position), ParseError> {
+        match self.next()? {
+            (Token::Id(id), position) => Ok((id, position)),
+            (_, (line, column)) => Err(ParseError::new(line, column, "expected an identifier")),
+        }
+    }
+
+    /// Parses `[key=value, ...]` lists, returning each value with its position.
+    fn attributes(&mut self) -> Result<Vec<Attribute>, ParseError> {
+        let mut attributes = Vec::new();
+        while self.peek()? == Some(&Token::LBracket) {
+            self.next()?;
+            loop {
+                if self.peek()? == Some(&Token::RBracket) {
+                    self.next()?;
+                    break;
+                }
+                let (key, _) = self.id()?;
+                self.expect(Token::Equals, "'='")?;
+                let (value, position) = self.id()?;
+                attributes.push((key, value, position));
+                if matches!(self.peek()?, Some(Token::Comma) | Some(Token::Semicolon)) {
+                    self.next()?;
+                }
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+/// Parses a Graphviz DOT document.
+///
+/// Supports `graph`/`digraph` (optionally `strict`), node, edge and attribute
+/// statements, edge chains such as `a -> b -> c`, quoted identifiers and
+/// comments. The edge weight is read from the `weight` attribute, falling
+/// back to `label`, and defaults to `W::default()`. Undirected edges are added
+/// in both directions. Subgraphs and ports are not supported.
+pub fn parse_dot<W>(input: &str) -> Result<WeightedAdjacencyList<String, W>, ParseError>
+where
+    W: FromStr + Default + Clone,
+{
+    let mut parser = Parser::new(input);
+    let mut graph = WeightedAdjacencyList::new();
+
+    let (mut keyword, mut position) = parser.id()?;
+    if keyword == "strict" {
+        (keyword, position) = parser.id()?;
+    }
+    let directed = match keyword.as_str() {
+        "digraph" => true,
+        "graph" => false,
+        _ => return Err(ParseError::new(position.0, position.1, "expected 'graph' or 'digraph'")),
+    };
+    if let Some(Token::Id(_)) = parser.peek()? {
+        parser.next()?;
+    }
+    parser.expect(Token::LBrace, "'{'")?;
+
+    loop {
+        let (token, (line, column)) = parser.next()?;
+        let first = match token {
+            Token::RBrace => break,
+            Token::Semicolon => continue,
+            Token::Id(id) => id,
+            _ => return Err(ParseError::new(line, column, "expected a statement")),
+        };
+        if first == "subgraph" {
+            return Err(ParseError::new(line, column, "subgraphs are not supported"));
+        }
+        match parser.peek()? {
+            // graph attribute: `key = value`
+            Some(Token::Equals) => {
+                parser.next()?;
+                parser.id()?;
+            }
+            // edge statement, possibly a chain
+            Some(Token::DirectedEdge) | Some(Token::UndirectedEdge) => {
+                let mut chain = vec![first];
+                while let Some(Token::DirectedEdge) | Some(Token::UndirectedEdge) = parser.peek()? {
+                    let (op, (line, column)) = parser.next()?;
+                    if (op == Token::DirectedEdge) != directed {
+                        let message = if directed { "expected '->' in a digraph" } else { "expected '--' in a graph" };
+                        return Err(ParseError::new(line, column, message));
+                    }
+                    chain.push(parser.id()?.0);
+                }
+                let weight: W = edge_weight(&parser.attributes()?)?;
+                for pair in chain.windows(2) {
+                    graph.add_edge(pair[0].clone(), pair[1].clone(), weight.clone());
+                    if !directed {
+                        graph.add_edge(pair[1].clone(), pair[0].clone(), weight.clone());
+                    }
+                }
+            }
+            // node statement, or a `graph`/`node`/`edge` default attribute statement
+            _ => {
+                parser.attributes()?;
+                if !matches!(first.as_str(), "graph" | "node" | "edge") {
+                    graph.add_vertex(first);
+                }
+            }
+        }
+    }
+    if let Some((_, (line, column))) = parser.lexer.next_token()? {
+        return Err(ParseError::new(line, column, "unexpected input after graph"));
+    }
+    Ok(graph)
+}
+
+fn edge_weight<W>(attributes: &[Attribute]) -> Result<W, ParseError>
+where
+    W: FromStr + Default,
+{
+    let find = |name: &str| attributes.iter().find(|(key, _, _)| key == name);
+    match find("weight").or_else(|| find("label")) {
+        Some((_, value, position)) => parse_weight(value, *position),
+        None => Ok(W::default()),
+    }
+}
+
+/// Writes the graph as a DOT `digraph` with a `weight` attribute on every edge.
+/// Isolated vertices are written as node statements.
+pub fn write_dot<T, W>(graph: &WeightedAdjacencyList<T, W>) -> String
+where
+    T: Hash + Eq + Clone + Ord + Display,
+    W: Display,
+{
+    let mut out = String::from("digraph {\n");
+    let mut vertices: Vec<&T> = graph.vertices().collect();
+    vertices.sort();
+    for vertex in vertices {
+        out.push_str(&format!("    {};\n", quote_id(&vertex.to_string())));
+    }
+    let mut edges: Vec<_> = graph.all_edges().collect();
+    edges.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    for (from, to, weight) in edges {
+        out.push_str(&format!(
+            "    {} -> {} [weight={}];\n",
+            quote_id(&from.to_string()),
+            quote_id(&to.to_string()),
+            quote_id(&weight.to_string()),
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Wraps an identifier in double quotes, escaping quotes and backslashes.
+pub(crate) fn quote_id(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,202 @@
+use super::{parse_weight, Cursor, ParseError, Position};
+use crate::data_structures::weighted_adj_list::WeightedAdjacencyList;
+use std::fmt::Display;
+use std::mem;
+use std::str::FromStr;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> WeightedAdjacencyList<String, i64> {
+        let mut graph = WeightedAdjacencyList::new();
+        graph.add_edge("a".to_string(), "b".to_string(), 3);
+        graph.add_edge("b".to_string(), "c, d".to_string(), -2);
+        graph.add_edge("c, d".to_string(), "say \"hi\"".to_string(), 7);
+        graph
+    }
+
+    #[test]
+    fn test_parse_csv_with_header() {
+        let input = "from,to,weight\na,b,3\nb, c ,4\n";
+        let graph: WeightedAdjacencyList<String, u32> = parse_edge_list(input, EdgeListFormat::CSV).unwrap();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.get_weight(&"b".to_string(), &"c".to_string()), Some(&4));
+    }
+
+    #[test]
+    fn test_parse_tsv_without_header() {
+        let format = EdgeListFormat { has_header: false, ..EdgeListFormat::TSV };
+        let graph: WeightedAdjacencyList<String, f64> = parse_edge_list("x\ty\t0.5\n", format).unwrap();
+        assert_eq!(graph.get_weight(&"x".to_string(), &"y".to_string()), Some(&0.5));
+    }
+
+    #[test]
+    fn test_parse_errors_carry_position() {
+        let err = parse_edge_list::<u32>("from,to,weight\na,b\n", EdgeListFormat::CSV).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = parse_edge_list::<u32>("from,to,weight\na,b,x\n", EdgeListFormat::CSV).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        let err = parse_edge_list::<u32>("from,to,weight\n\"a,b,1\n", EdgeListFormat::CSV).unwrap_err();
+        assert_eq!(err.message, "unterminated quoted field");
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let graph = sample();
+        let text = write_edge_list(&graph, EdgeListFormat::CSV);
+        let parsed: WeightedAdjacencyList<String, i64> = parse_edge_list(&text, EdgeListFormat::CSV).unwrap();
+        assert_eq!(parsed, graph);
+    }
+
+    #[test]
+    fn test_quoted_fields_may_span_lines() {
+        let input = "from,to,weight\n\"two\nlines\",b,1\nb,c,x\n";
+        let err = parse_edge_list::<u32>(input, EdgeListFormat::CSV).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 5));
+        let mut graph = sample();
+        graph.add_edge("two\nlines".to_string(), "\"\n\"".to_string(), 1);
+        let text = write_edge_list(&graph, EdgeListFormat::CSV);
+        let parsed: WeightedAdjacencyList<String, i64> = parse_edge_list(&text, EdgeListFormat::CSV).unwrap();
+        assert_eq!(parsed, graph);
+    }
+
+    #[test]
+    fn test_tsv_round_trip() {
+        let graph = sample();
+        let text = write_edge_list(&graph, EdgeListFormat::TSV);
+        assert!(text.starts_with("from\tto\tweight\na\tb\t3\n"));
+        let parsed: WeightedAdjacencyList<String, i64> = parse_edge_list(&text, EdgeListFormat::TSV).unwrap();
+        assert_eq!(parsed, graph);
+    }
+}
+
+/// Layout of a weighted edge list file with one `from, to, weight` record per
+/// line, except where a quoted field spans lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeListFormat {
+    pub delimiter: char,
+    /// Whether the first line is a header to skip on reading and emit on writing.
+    pub has_header: bool,
+}
+
+impl EdgeListFormat {
+    pub const CSV: EdgeListFormat = EdgeListFormat { delimiter: ',', has_header: true };
+    pub const TSV: EdgeListFormat = EdgeListFormat { delimiter: '\t', has_header: true };
+}
+
+/// Parses a delimited edge list. Fields may be wrapped in double quotes, with
+/// `""` standing for a literal quote, and a quoted field may span lines;
+/// unquoted fields are trimmed. Blank lines are ignored.
+pub fn parse_edge_list<W>(input: &str, format: EdgeListFormat) -> Result<WeightedAdjacencyList<String, W>, ParseError>
+where
+    W: FromStr,
+{
+    let mut graph = WeightedAdjacencyList::new();
+    let mut cursor = Cursor::new(input);
+    let mut skip_header = format.has_header;
+    while cursor.peek().is_some() {
+        let (line_number, _) = cursor.position();
+        let fields = read_record(&mut cursor, format.delimiter)?;
+        let blank = fields.len() == 1 && fields[0].0.is_empty();
+        if mem::take(&mut skip_header) || blank {
+            continue;
+        }
+        if fields.len() != 3 {
+            return Err(ParseError::new(
+                line_number,
+                1,
+                format!("expected 3 fields, found {}", fields.len()),
+            ));
+        }
+        let mut fields = fields.into_iter();
+        let (from, _) = fields.next().unwrap();
+        let (to, _) = fields.next().unwrap();
+        let (weight, position) = fields.next().unwrap();
+        let weight = parse_weight(&weight, position)?;
+        graph.add_edge(from, to, weight);
+    }
+    Ok(graph)
+}
+
+/// Reads one record through the line break that ends it, returning each field
+/// with the position it starts at.
+fn read_record(cursor: &mut Cursor, delimiter: char) -> Result<Vec<(String, Position)>, ParseError> {
+    let ends_field = |c: char| c == delimiter || c == '\n';
+    let mut fields = Vec::new();
+    loop {
+        let start = cursor.position();
+        while cursor.peek().is_some_and(|c| !ends_field(c) && c.is_whitespace()) {
+            cursor.bump();
+        }
+        let mut field = String::new();
+        if cursor.peek() == Some('"') {
+            let (line, column) = cursor.position();
+            cursor.bump();
+            loop {
+                match cursor.bump() {
+                    None => return Err(ParseError::new(line, column, "unterminated quoted field")),
+                    Some('"') if cursor.eat('"') => field.push('"'),
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                }
+            }
+            while let Some(c) = cursor.peek().filter(|&c| !ends_field(c)) {
+                if !c.is_whitespace() {
+                    return Err(cursor.error("unexpected character after quoted field"));
+                }
+                cursor.bump();
+            }
+        } else {
+            while let Some(c) = cursor.peek().filter(|&c| !ends_field(c)) {
+                field.push(c);
+                cursor.bump();
+            }
+            field = field.trim().to_string();
+        }
+        fields.push((field, start));
+        // Consume the delimiter or line break that ended the field
+        match cursor.bump() {
+            Some(c) if c == delimiter => {}
+            _ => return Ok(fields),
+        }
+    }
+}
+
+/// Writes every edge of the graph as a delimited record, sorted by endpoints.
+pub fn write_edge_list<T, W>(graph: &WeightedAdjacencyList<T, W>, format: EdgeListFormat) -> String
+where
+    T: std::hash::Hash + Eq + Clone + Ord + Display,
+    W: Display,
+{
+    let mut edges: Vec<_> = graph.all_edges().collect();
+    edges.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    let mut out = String::new();
+    if format.has_header {
+        out.push_str(&["from", "to", "weight"].join(&format.delimiter.to_string()));
+        out.push('\n');
+    }
+    for (from, to, weight) in edges {
+        let record = [from.to_string(), to.to_string(), weight.to_string()]
+            .iter()
+            .map(|field| quote_field(field, format.delimiter))
+            .collect::<Vec<_>>()
+            .join(&format.delimiter.to_string());
+        out.push_str(&record);
+        out.push('\n');
+    }
+    out
+}
+
+/// Quotes a field when it would not survive being read back verbatim.
+fn quote_field(field: &str, delimiter: char) -> String {
+    let needs_quotes = field.is_empty()
+        || field.contains(delimiter)
+        || field.contains('"')
+        || field.contains('\n')
+        || field.trim() != field;
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
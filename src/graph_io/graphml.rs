@@ -0,0 +1,477 @@
+use super::{parse_weight, Cursor, ParseError, Position};
+use crate::data_structures::weighted_adj_list::WeightedAdjacencyList;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(graph: &WeightedAdjacencyList<String, f64>, from: &str, to: &str) -> Option<f64> {
+        graph.get_weight(&from.to_string(), &to.to_string()).copied()
+    }
+
+    #[test]
+    fn test_parse_graphml() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- exported by some tool -->
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="color" attr.type="string"/>
+  <key id="d1" for="edge" attr.name="weight" attr.type="double">
+    <default>1.0</default>
+  </key>
+  <graph id="G" edgedefault="undirected">
+    <node id="n0"><data key="d0">green</data></node>
+    <node id="n1"/>
+    <node id="n2"/>
+    <node id="a &amp; b"/>
+    <edge source="n0" target="n1"><data key="d1">2.5</data></edge>
+    <edge source="n1" target="n2"/>
+    <edge source="n2" target="a &amp; b" directed="true"><data key="d1"><![CDATA[4]]></data></edge>
+  </graph>
+</graphml>
+"#;
+        let graph: WeightedAdjacencyList<String, f64> = parse_graphml(input).unwrap();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(edge(&graph, "n0", "n1"), Some(2.5));
+        assert_eq!(edge(&graph, "n1", "n0"), Some(2.5));
+        assert_eq!(edge(&graph, "n1", "n2"), Some(1.0));
+        assert_eq!(edge(&graph, "n2", "a & b"), Some(4.0));
+        assert_eq!(edge(&graph, "a & b", "n2"), None);
+    }
+
+    #[test]
+    fn test_parse_errors_carry_position() {
+        let err = parse_graphml::<u32>("<graphml>\n  <graph>\n  </graphml>").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.message, "expected </graph>, found </graphml>");
+        let err = parse_graphml::<u32>("<graphml><graph><edge source=\"a\"/></graph></graphml>").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 17));
+        let err = parse_graphml::<u32>("<graphml>\n<key id=\"w\" for=\"edge\" attr.name=\"weight\"/>\n<graph><edge source=\"a\" target=\"b\"><data key=\"w\">x</data></edge></graph></graphml>").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 50));
+        let err = parse_graphml::<u32>("<graphml><graph id=\"G></graph></graphml>").unwrap_err();
+        assert_eq!(err.message, "unterminated attribute value");
+    }
+
+    #[test]
+    fn test_graphml_round_trip() {
+        let mut graph: WeightedAdjacencyList<String, f64> = WeightedAdjacencyList::new();
+        graph.add_edge("<a>".to_string(), "b \"quoted\"".to_string(), 1.5);
+        graph.add_edge("b \"quoted\"".to_string(), "<a>".to_string(), -3.0);
+        graph.add_edge("c&d".to_string(), "<a>".to_string(), 0.0);
+        graph.add_vertex("lonely".to_string());
+        let text = write_graphml(&graph);
+        assert!(text.contains("attr.type=\"double\""));
+        let parsed: WeightedAdjacencyList<String, f64> = parse_graphml(&text).unwrap();
+        assert_eq!(parsed, graph);
+    }
+
+    #[test]
+    fn test_integer_weights_declare_integer_type() {
+        let mut graph: WeightedAdjacencyList<String, i32> = WeightedAdjacencyList::new();
+        graph.add_edge("a".to_string(), "b".to_string(), -7);
+        let text = write_graphml(&graph);
+        assert!(text.contains("attr.name=\"weight\" attr.type=\"int\""));
+        assert_eq!(parse_graphml::<i32>(&text).unwrap(), graph);
+        let mut graph: WeightedAdjacencyList<String, u64> = WeightedAdjacencyList::new();
+        graph.add_edge("a".to_string(), "b".to_string(), u64::MAX);
+        let text = write_graphml(&graph);
+        assert!(text.contains("attr.type=\"long\""));
+        assert_eq!(parse_graphml::<u64>(&text).unwrap(), graph);
+    }
+}
+
+#[derive(Debug)]
+enum Event {
+    Start { name: String, attributes: Vec<(String, String)>, self_closing: bool },
+    End { name: String },
+    Text(String),
+}
+
+/// A minimal XML reader covering the subset GraphML files use: elements,
+/// attributes, text, CDATA, comments, processing instructions and the
+/// predefined entities.
+struct XmlReader<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl XmlReader<'_> {
+    fn next_event(&mut self) -> Result<Option<(Event, Position)>, ParseError> {
+        loop {
+            let position = self.cursor.position();
+            match self.cursor.peek() {
+                None => return Ok(None),
+                Some('<') => {
+                    self.cursor.bump();
+                    if self.cursor.eat('?') {
+                        self.skip_until("?>", position)?;
+                    } else if self.cursor.eat('!') {
+                        if self.cursor.eat('[') {
+                            self.expect_literal("CDATA[")?;
+                            let text = self.skip_until("]]>", position)?;
+                            return Ok(Some((Event::Text(text), position)));
+                        } else if self.cursor.eat('-') {
+                            self.expect_literal("-")?;
+                            self.skip_until("-->", position)?;
+                        } else {
+                            self.skip_until(">", position)?;
+                        }
+                    } else if self.cursor.eat('/') {
+                        let name = self.name()?;
+                        self.skip_whitespace();
+                        if !self.cursor.eat('>') {
+                            return Err(self.cursor.error("expected '>'"));
+                        }
+                        return Ok(Some((Event::End { name }, position)));
+                    } else {
+                        return self.start_tag().map(|event| Some((event, position)));
+                    }
+                }
+                Some(_) => {
+                    let text = self.text()?;
+                    if !text.trim().is_empty() {
+                        return Ok(Some((Event::Text(text), position)));
+                    }
+                }
+            }
+        }
+    }
+
+    fn start_tag(&mut self) -> Result<Event, ParseError> {
+        let name = self.name()?;
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.cursor.eat('>') {
+                return Ok(Event::Start { name, attributes, self_closing: false });
+            }
+            if self.cursor.eat('/') {
+                if !self.cursor.eat('>') {
+                    return Err(self.cursor.error("expected '>'"));
+                }
+                return Ok(Event::Start { name, attributes, self_closing: true });
+            }
+            let key = self.name()?;
+            self.skip_whitespace();
+            if !self.cursor.eat('=') {
+                return Err(self.cursor.error("expected '='"));
+            }
+            self.skip_whitespace();
+            let (line, column) = self.cursor.position();
+            let quote = match self.cursor.bump() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err(ParseError::new(line, column, "expected a quoted attribute value")),
+            };
+            let mut raw = String::new();
+            loop {
+                match self.cursor.bump() {
+                    None => return Err(ParseError::new(line, column, "unterminated attribute value")),
+                    Some('<') => return Err(ParseError::new(line, column, "unterminated attribute value")),
+                    Some(c) if c == quote => break,
+                    Some(c) => raw.push(c),
+                }
+            }
+            attributes.push((key, decode_entities(&raw, (line, column + 1))?));
+        }
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        let mut name = String::new();
+        while let Some(c) = self.cursor.peek() {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':') {
+                name.push(c);
+                self.cursor.bump();
+            } else {
+                break;
+            }
+        }
+        if name.is_empty() {
+            return Err(self.cursor.error("expected a name"));
+        }
+        Ok(name)
+    }
+
+    fn text(&mut self) -> Result<String, ParseError> {
+        let position = self.cursor.position();
+        let mut raw = String::new();
+        while let Some(c) = self.cursor.peek() {
+            if c == '<' {
+                break;
+            }
+            raw.push(c);
+            self.cursor.bump();
+        }
+        decode_entities(&raw, position)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.cursor.peek().is_some_and(char::is_whitespace) {
+            self.cursor.bump();
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        for expected in literal.chars() {
+            if !self.cursor.eat(expected) {
+                return Err(self.cursor.error(format!("expected '{}'", literal)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes input up to and including `terminator`, returning what came before it.
+    fn skip_until(&mut self, terminator: &str, (line, column): Position) -> Result<String, ParseError> {
+        let mut skipped = String::new();
+        while !skipped.ends_with(terminator) {
+            match self.cursor.bump() {
+                Some(c) => skipped.push(c),
+                None => return Err(ParseError::new(line, column, format!("expected '{}'", terminator))),
+            }
+        }
+        skipped.truncate(skipped.len() - terminator.len());
+        Ok(skipped)
+    }
+}
+
+fn decode_entities(raw: &str, (line, column): Position) -> Result<String, ParseError> {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find(';') else {
+            return Err(ParseError::new(line, column, "unterminated entity"));
+        };
+        let entity = &rest[start + 1..start + end];
+        let decoded = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32)
+                .ok_or_else(|| ParseError::new(line, column, format!("unknown entity '&{};'", entity)))?,
+        };
+        out.push(decoded);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+struct PendingEdge {
+    source: String,
+    target: String,
+    directed: bool,
+    weight: Option<(String, Position)>,
+}
+
+/// Parses a GraphML document.
+///
+/// The edge weight is the `<data>` value for the key whose `attr.name` is
+/// `weight`, falling back to that key's `<default>` and then `W::default()`.
+/// Edges of an undirected graph (or with `directed="false"`) are added in both
+/// directions. Nested graphs and hyperedges are not supported.
+pub fn parse_graphml<W>(input: &str) -> Result<WeightedAdjacencyList<String, W>, ParseError>
+where
+    W: FromStr + Default + Clone,
+{
+    let mut reader = XmlReader { cursor: Cursor::new(input) };
+    let mut graph = WeightedAdjacencyList::new();
+    let mut open: Vec<String> = Vec::new();
+    let mut weight_key: Option<String> = None;
+    let mut key_defaults: HashMap<String, (String, Position)> = HashMap::new();
+    let mut current_key: Option<String> = None;
+    let mut data_key: Option<String> = None;
+    let mut edge_default_directed = true;
+    let mut edge: Option<PendingEdge> = None;
+
+    while let Some((event, (line, column))) = reader.next_event()? {
+        match event {
+            Event::Start { name, attributes, self_closing } => {
+                let required = |attr: &str| {
+                    attribute(&attributes, attr).map(str::to_string).ok_or_else(|| {
+                        ParseError::new(line, column, format!("<{}> is missing the '{}' attribute", name, attr))
+                    })
+                };
+                match name.as_str() {
+                    "key" => {
+                        let id = required("id")?;
+                        let domain = attribute(&attributes, "for").unwrap_or("all");
+                        if attribute(&attributes, "attr.name") == Some("weight") && matches!(domain, "edge" | "all") {
+                            weight_key = Some(id.clone());
+                        }
+                        current_key = Some(id);
+                    }
+                    "graph" => {
+                        if open.iter().any(|open| open == "graph") {
+                            return Err(ParseError::new(line, column, "nested graphs are not supported"));
+                        }
+                        edge_default_directed = attribute(&attributes, "edgedefault") != Some("undirected");
+                    }
+                    "node" => graph.add_vertex(required("id")?),
+                    "edge" => {
+                        let directed = match attribute(&attributes, "directed") {
+                            Some("true") => true,
+                            Some("false") => false,
+                            _ => edge_default_directed,
+                        };
+                        edge = Some(PendingEdge {
+                            source: required("source")?,
+                            target: required("target")?,
+                            directed,
+                            weight: None,
+                        });
+                    }
+                    "data" => data_key = Some(required("key")?),
+                    "hyperedge" => return Err(ParseError::new(line, column, "hyperedges are not supported")),
+                    _ => {}
+                }
+                if self_closing {
+                    close_element(&name, &mut edge, &mut graph, &weight_key, &key_defaults)?;
+                    if name == "data" {
+                        data_key = None;
+                    }
+                } else {
+                    open.push(name);
+                }
+            }
+            Event::End { name } => {
+                match open.pop() {
+                    Some(expected) if expected == name => {}
+                    Some(expected) => {
+                        return Err(ParseError::new(line, column, format!("expected </{}>, found </{}>", expected, name)))
+                    }
+                    None => return Err(ParseError::new(line, column, format!("unexpected </{}>", name))),
+                }
+                match name.as_str() {
+                    "key" => current_key = None,
+                    "data" => data_key = None,
+                    _ => {}
+                }
+                close_element(&name, &mut edge, &mut graph, &weight_key, &key_defaults)?;
+            }
+            Event::Text(text) => match open.last().map(String::as_str) {
+                Some("default") => {
+                    if let Some(key) = &current_key {
+                        key_defaults.insert(key.clone(), (text, (line, column)));
+                    }
+                }
+                Some("data") => {
+                    if let (Some(pending), Some(key)) = (edge.as_mut(), &data_key) {
+                        if Some(key) == weight_key.as_ref() {
+                            pending.weight = Some((text, (line, column)));
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    if let Some(name) = open.pop() {
+        return Err(reader.cursor.error(format!("unclosed <{}>", name)));
+    }
+    Ok(graph)
+}
+
+/// Adds a finished `<edge>` element to the graph.
+fn close_element<W>(
+    name: &str,
+    edge: &mut Option<PendingEdge>,
+    graph: &mut WeightedAdjacencyList<String, W>,
+    weight_key: &Option<String>,
+    key_defaults: &HashMap<String, (String, Position)>,
+) -> Result<(), ParseError>
+where
+    W: FromStr + Default + Clone,
+{
+    if name != "edge" {
+        return Ok(());
+    }
+    let Some(pending) = edge.take() else {
+        return Ok(());
+    };
+    let text = pending
+        .weight
+        .as_ref()
+        .or_else(|| weight_key.as_ref().and_then(|key| key_defaults.get(key)));
+    let weight: W = match text {
+        Some((text, position)) => parse_weight(text, *position)?,
+        None => W::default(),
+    };
+    if !pending.directed {
+        graph.add_edge(pending.target.clone(), pending.source.clone(), weight.clone());
+    }
+    graph.add_edge(pending.source, pending.target, weight);
+    Ok(())
+}
+
+/// A weight type with a matching GraphML `attr.type`.
+pub trait GraphMlType {
+    /// One of GraphML's `int`, `long`, `float` or `double`.
+    const ATTR_TYPE: &'static str;
+}
+
+macro_rules! impl_graphml_type {
+    ($attr_type:literal: $($t:ty),*) => {
+        $( impl GraphMlType for $t { const ATTR_TYPE: &'static str = $attr_type; } )*
+    };
+}
+
+impl_graphml_type!("int": i8, i16, i32, u8, u16);
+// `long` is GraphML's widest integer type, so it also declares the types
+// whose range exceeds it
+impl_graphml_type!("long": i64, i128, isize, u32, u64, u128, usize);
+impl_graphml_type!("float": f32);
+impl_graphml_type!("double": f64);
+
+/// Writes the graph as a directed GraphML document with a `weight` edge key
+/// declared with the weight type's `attr.type`.
+pub fn write_graphml<T, W>(graph: &WeightedAdjacencyList<T, W>) -> String
+where
+    T: Hash + Eq + Clone + Ord + Display,
+    W: Display + GraphMlType,
+{
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str(&format!(
+        "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"{}\"/>\n",
+        W::ATTR_TYPE
+    ));
+    out.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+    let mut vertices: Vec<&T> = graph.vertices().collect();
+    vertices.sort();
+    for vertex in vertices {
+        out.push_str(&format!("    <node id=\"{}\"/>\n", escape(&vertex.to_string())));
+    }
+    let mut edges: Vec<_> = graph.all_edges().collect();
+    edges.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    for (from, to, weight) in edges {
+        out.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"><data key=\"weight\">{}</data></edge>\n",
+            escape(&from.to_string()),
+            escape(&to.to_string()),
+            escape(&weight.to_string()),
+        ));
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
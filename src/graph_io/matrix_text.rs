@@ -0,0 +1,92 @@
+use super::{parse_weight, ParseError};
+use crate::data_structures::weighted_adj_matrix::WeightedAdjacencyMatrix;
+use crate::numeric::Numeric;
+use std::str::FromStr;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_matrix() {
+        let matrix: WeightedAdjacencyMatrix<u32> = parse_matrix("1 2 -\n\n4 - 6\n").unwrap();
+        assert_eq!(matrix.shape(), (2, 3));
+        assert_eq!(matrix.get_row(0), Some(&[Some(1), Some(2), None][..]));
+        assert_eq!(matrix.get_row(1), Some(&[Some(4), None, Some(6)][..]));
+    }
+
+    #[test]
+    fn test_parse_matrix_reports_position() {
+        let err = parse_matrix::<u32>("1 2\n3  x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        let err = parse_matrix::<u32>("1 2\n3\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected 2 columns, found 1");
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix = WeightedAdjacencyMatrix::from_options(vec![
+            vec![None, Some(-1.5f64)],
+            vec![Some(0.0), Some(2.25)],
+        ]).unwrap();
+        let text = write_matrix(&matrix);
+        assert_eq!(parse_matrix::<f64>(&text).unwrap(), matrix);
+    }
+
+    #[test]
+    fn test_empty_matrix_round_trip() {
+        for (rows, cols) in [(0, 0), (3, 0)] {
+            let matrix: WeightedAdjacencyMatrix<u8> = WeightedAdjacencyMatrix::empty(rows, cols);
+            assert_eq!(parse_matrix::<u8>(&write_matrix(&matrix)).unwrap(), matrix);
+        }
+    }
+}
+
+/// Parses the whitespace separated format printed by
+/// `WeightedAdjacencyMatrix::display_matrix`: one row per line, `-` for an
+/// absent cell. Blank lines are ignored unless every line is blank; then
+/// each one is an empty row, so an n×0 matrix reads back as printed.
+pub fn parse_matrix<W>(input: &str) -> Result<WeightedAdjacencyMatrix<W>, ParseError>
+where
+    W: Numeric + FromStr,
+{
+    let mut rows: Vec<Vec<Option<W>>> = Vec::new();
+    let mut blank_lines = 0;
+    for (line_index, line) in input.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut row = Vec::new();
+        let mut column = 1;
+        for token in line.split(|c: char| c.is_whitespace()) {
+            if !token.is_empty() {
+                row.push(match token {
+                    "-" => None,
+                    _ => Some(parse_weight(token, (line_number, column))?),
+                });
+            }
+            column += token.chars().count() + 1;
+        }
+        if row.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        if let Some(first) = rows.first() {
+            if first.len() != row.len() {
+                return Err(ParseError::new(
+                    line_number,
+                    1,
+                    format!("expected {} columns, found {}", first.len(), row.len()),
+                ));
+            }
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        rows.resize_with(blank_lines, Vec::new);
+    }
+    WeightedAdjacencyMatrix::from_options(rows).map_err(|err| ParseError::new(1, 1, err.to_string()))
+}
+
+/// Writes a matrix in the format read by `parse_matrix`.
+pub fn write_matrix<W: Numeric>(matrix: &WeightedAdjacencyMatrix<W>) -> String {
+    matrix.to_string()
+}
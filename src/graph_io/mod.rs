@@ -0,0 +1,96 @@
+//! Readers and writers for common graph file formats.
+//!
+//! Named-vertex formats (DOT, GraphML and edge lists) read into a
+//! `WeightedAdjacencyList<String, W>`; convert it to a `CsrGraph` to run the
+//! index-based algorithms on it. The matrix text format reads into a
+//! `WeightedAdjacencyMatrix<W>`. Writers emit vertices and edges in sorted
+//! order so their output is deterministic. `render` draws algorithm results
+//! such as shortest paths on top of a graph as a Graphviz document.
+
+pub mod dot;
+pub mod edge_list;
+pub mod graphml;
+pub mod matrix_text;
+pub mod render;
+
+use std::fmt;
+
+/// An error encountered while parsing a graph file.
+///
+/// `line` and `column` are 1-based and point at the offending input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError { line, column, message: message.into() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A 1-based `(line, column)` pair.
+type Position = (usize, usize);
+
+/// A character cursor that tracks the line and column of its position.
+struct Cursor<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Cursor { chars: input.chars().peekable(), line: 1, column: 1 }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Consumes `expected` if it is the next character.
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn position(&self) -> Position {
+        (self.line, self.column)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, self.column, message)
+    }
+}
+
+/// Parses a weight, reporting failures at the given position.
+fn parse_weight<W: std::str::FromStr>(text: &str, (line, column): Position) -> Result<W, ParseError> {
+    text.trim()
+        .parse()
+        .map_err(|_| ParseError::new(line, column, format!("invalid weight '{}'", text)))
+}
@@ -0,0 +1,286 @@
+use super::dot::quote_id;
+use crate::dijkstras::ShortestPaths;
+use crate::graph::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
+use crate::numeric::Numeric;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structures::csr_graph::CsrGraph;
+    use crate::data_structures::weighted_adj_list::WeightedAdjacencyList;
+    use crate::dijkstras::{dijkstras, dijkstras_shortest_path};
+    use crate::graph_traversal::breadth_first_search;
+
+    fn sample() -> WeightedAdjacencyList<usize, u32> {
+        let mut graph = WeightedAdjacencyList::new();
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 1, 2);
+        graph.add_edge(1, 3, 5);
+        graph
+    }
+
+    #[test]
+    fn test_render_plain_graph() {
+        let graph = sample();
+        let dot = render_dot(&graph, &DotOverlay::new());
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    \"0\";\n"));
+        assert!(dot.contains("    \"2\" -> \"1\" [label=\"2\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_render_highlighted_path() {
+        let graph = sample();
+        let path = dijkstras_shortest_path(0, 3, &graph).unwrap();
+        let overlay = DotOverlay::new().with_path(&path).with_title("0 to 3");
+        let dot = render_dot(&graph, &overlay);
+        assert!(dot.contains("    label=\"0 to 3\";\n"));
+        assert!(dot.contains("    \"0\" -> \"2\" [label=\"1\", color=\"red\", penwidth=2];\n"));
+        assert!(dot.contains("    \"2\" -> \"1\" [label=\"2\", color=\"red\", penwidth=2];\n"));
+        assert!(dot.contains("    \"0\" -> \"1\" [label=\"4\"];\n"));
+        assert!(dot.contains("    \"3\" [color=\"red\", penwidth=2];\n"));
+    }
+
+    #[test]
+    fn test_render_shortest_path_tree_with_distances() {
+        let graph = sample();
+        let overlay = DotOverlay::from_shortest_paths(&dijkstras(&graph, 0));
+        let dot = render_dot(&graph, &overlay);
+        assert!(dot.contains("    \"1\" [label=\"1\\nd=3\"];\n"));
+        assert!(dot.contains("    \"2\" -> \"1\" [label=\"2\", color=\"red\", penwidth=2];\n"));
+        assert!(dot.contains("    \"0\" -> \"1\" [label=\"4\"];\n"));
+    }
+
+    #[test]
+    fn test_render_traversal_order_and_colouring() {
+        let graph = CsrGraph::from_edges(vec![("x", "y", 1u8), ("y", "z", 1)]);
+        let order = breadth_first_search(&graph, 0);
+        let overlay = DotOverlay::new()
+            .with_visit_order(&order)
+            .with_partition(vec![(0, 0), (1, 1), (2, 0)])
+            .with_node_names((0..3).map(|i| (i, graph.vertex(i).unwrap().to_string())));
+        let dot = render_dot(&graph, &overlay);
+        assert!(dot.contains("    \"1\" [label=\"y\\n#1\", style=filled, fillcolor=\"palegreen\"];\n"));
+        assert!(dot.contains("    \"2\" [label=\"z\\n#2\", style=filled, fillcolor=\"lightblue\"];\n"));
+    }
+
+    #[test]
+    fn test_render_escapes_labels() {
+        let mut graph = WeightedAdjacencyList::new();
+        graph.add_edge("a\"b", "c", 1u8);
+        let overlay = DotOverlay::new().with_node_label("c", "line\none");
+        let dot = render_dot(&graph, &overlay);
+        assert!(dot.contains("    \"a\\\"b\" -> \"c\" [label=\"1\"];\n"));
+        assert!(dot.contains("    \"c\" [label=\"c\\nline\\none\"];\n"));
+    }
+}
+
+/// Fill colours assigned to the groups of a vertex partition, in order.
+const PALETTE: [&str; 8] = [
+    "lightblue", "palegreen", "lightpink", "khaki", "plum", "lightsalmon", "lightcyan", "wheat",
+];
+
+/// Visual annotations drawn on top of a graph by `render_dot`.
+///
+/// Highlighted nodes and edges are drawn in the highlight colour (red by
+/// default) with a thicker pen; node colours become fill colours. A node is
+/// shown by its name (its id unless renamed) with any labels on the lines below.
+pub struct DotOverlay<N> {
+    title: Option<String>,
+    highlight_color: String,
+    highlighted_nodes: HashSet<N>,
+    highlighted_edges: HashSet<(N, N)>,
+    node_colors: HashMap<N, String>,
+    node_names: HashMap<N, String>,
+    node_labels: HashMap<N, Vec<String>>,
+}
+
+impl<N: Hash + Eq + Copy + Display> DotOverlay<N> {
+    pub fn new() -> Self {
+        DotOverlay {
+            title: None,
+            highlight_color: "red".to_string(),
+            highlighted_nodes: HashSet::new(),
+            highlighted_edges: HashSet::new(),
+            node_colors: HashMap::new(),
+            node_names: HashMap::new(),
+            node_labels: HashMap::new(),
+        }
+    }
+
+    /// Labels every reachable node with its distance and highlights the
+    /// shortest-path tree.
+    pub fn from_shortest_paths<G>(paths: &ShortestPaths<G>) -> Self
+    where
+        G: IntoEdges<NodeId = N> + NodeIndexable,
+        G::EdgeWeight: Numeric,
+    {
+        let mut overlay = DotOverlay::new();
+        for (node, dist) in paths.distances() {
+            overlay = overlay.with_node_label(node, format!("d={}", dist));
+            if let Some(prev) = paths.predecessor(node) {
+                overlay.highlighted_edges.insert((prev, node));
+            }
+        }
+        overlay.highlighted_nodes.insert(paths.source());
+        overlay
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the colour used for highlighted nodes and edges.
+    pub fn with_highlight_color(mut self, color: impl Into<String>) -> Self {
+        self.highlight_color = color.into();
+        self
+    }
+
+    /// Highlights the nodes of a path and the edges between consecutive nodes,
+    /// e.g. the result of `dijkstras_shortest_path`.
+    pub fn with_path(mut self, path: &[N]) -> Self {
+        self.highlighted_nodes.extend(path.iter().copied());
+        self.highlighted_edges
+            .extend(path.windows(2).map(|pair| (pair[0], pair[1])));
+        self
+    }
+
+    /// Highlights a set of `(from, to)` edges, e.g. a spanning tree.
+    pub fn with_edges(mut self, edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        self.highlighted_edges.extend(edges);
+        self
+    }
+
+    /// Highlights a set of nodes.
+    pub fn with_nodes(mut self, nodes: impl IntoIterator<Item = N>) -> Self {
+        self.highlighted_nodes.extend(nodes);
+        self
+    }
+
+    /// Labels each node with its position in a traversal order, e.g. the
+    /// result of `breadth_first_search`.
+    pub fn with_visit_order(mut self, order: &[N]) -> Self {
+        for (i, &node) in order.iter().enumerate() {
+            self = self.with_node_label(node, format!("#{}", i));
+        }
+        self
+    }
+
+    pub fn with_node_color(mut self, node: N, color: impl Into<String>) -> Self {
+        self.node_colors.insert(node, color.into());
+        self
+    }
+
+    /// Fills the nodes of each group, e.g. a connected component, with a
+    /// distinct colour. Groups are numbered from zero and the palette repeats
+    /// after eight groups.
+    pub fn with_partition(mut self, groups: impl IntoIterator<Item = (N, usize)>) -> Self {
+        for (node, group) in groups {
+            self.node_colors
+                .insert(node, PALETTE[group % PALETTE.len()].to_string());
+        }
+        self
+    }
+
+    /// Shows each node under the given name instead of its id, e.g. the vertex
+    /// ids behind the indices of a `CsrGraph`.
+    pub fn with_node_names<L: Into<String>>(mut self, names: impl IntoIterator<Item = (N, L)>) -> Self {
+        self.node_names
+            .extend(names.into_iter().map(|(node, name)| (node, name.into())));
+        self
+    }
+
+    /// Adds a line of text below a node's name. Multiple labels stack in the
+    /// order they were added.
+    pub fn with_node_label(mut self, node: N, label: impl Into<String>) -> Self {
+        self.node_labels.entry(node).or_default().push(label.into());
+        self
+    }
+
+    /// Adds a line of text to the label of each node.
+    pub fn with_node_labels<L: Into<String>>(mut self, labels: impl IntoIterator<Item = (N, L)>) -> Self {
+        for (node, label) in labels {
+            self = self.with_node_label(node, label);
+        }
+        self
+    }
+
+    fn node_attributes(&self, node: N) -> Vec<String> {
+        let mut attributes = Vec::new();
+        let name = self.node_names.get(&node);
+        let lines = self.node_labels.get(&node);
+        if name.is_some() || lines.is_some() {
+            let mut text = vec![name.cloned().unwrap_or_else(|| node.to_string())];
+            text.extend(lines.into_iter().flatten().cloned());
+            attributes.push(format!("label={}", escape_label(&text.join("\n"))));
+        }
+        if let Some(color) = self.node_colors.get(&node) {
+            attributes.push("style=filled".to_string());
+            attributes.push(format!("fillcolor={}", quote_id(color)));
+        }
+        if self.highlighted_nodes.contains(&node) {
+            attributes.push(format!("color={}", quote_id(&self.highlight_color)));
+            attributes.push("penwidth=2".to_string());
+        }
+        attributes
+    }
+}
+
+impl<N: Hash + Eq + Copy + Display> Default for DotOverlay<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders a graph and an overlay as a Graphviz `digraph`, labelling each
+/// edge with its weight. Nodes and edges are written in sorted order.
+pub fn render_dot<G>(graph: G, overlay: &DotOverlay<G::NodeId>) -> String
+where
+    G: IntoNodeIdentifiers + IntoEdges,
+    G::NodeId: Hash + Eq + Ord + Display,
+    G::EdgeWeight: Display,
+{
+    let mut out = String::from("digraph {\n");
+    if let Some(title) = &overlay.title {
+        out.push_str(&format!("    label={};\n", escape_label(title)));
+    }
+    let mut nodes: Vec<G::NodeId> = graph.node_identifiers().collect();
+    nodes.sort();
+    for &node in &nodes {
+        let attributes = overlay.node_attributes(node);
+        out.push_str(&format!("    {}", quote_id(&node.to_string())));
+        if !attributes.is_empty() {
+            out.push_str(&format!(" [{}]", attributes.join(", ")));
+        }
+        out.push_str(";\n");
+    }
+    for &node in &nodes {
+        let mut edges: Vec<G::EdgeRef> = graph.edges(node).collect();
+        edges.sort_by_key(|edge| edge.target());
+        for edge in edges {
+            let mut attributes = vec![format!("label={}", quote_id(&edge.weight().to_string()))];
+            if overlay.highlighted_edges.contains(&(edge.source(), edge.target())) {
+                attributes.push(format!("color={}", quote_id(&overlay.highlight_color)));
+                attributes.push("penwidth=2".to_string());
+            }
+            out.push_str(&format!(
+                "    {} -> {} [{}];\n",
+                quote_id(&edge.source().to_string()),
+                quote_id(&edge.target().to_string()),
+                attributes.join(", "),
+            ));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Quotes a label, turning newlines into DOT line breaks.
+fn escape_label(label: &str) -> String {
+    quote_id(label).replace('\n', "\\n")
+}
@@ -0,0 +1,116 @@
+use crate::data_structures::{queue::Queue, stack::Stack};
+use crate::graph::{IntoNeighbors, VisitMap, Visitable};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structures::csr_graph::CsrGraph;
+    use crate::data_structures::weighted_adj_list::WeightedAdjacencyList;
+    use crate::data_structures::weighted_adj_matrix::WeightedAdjacencyMatrix;
+
+    //  0 -> 1 -> 3
+    //  |         ^
+    //  v         |
+    //  2 --------+    4 (unreachable)
+    fn edges() -> Vec<(usize, usize, u32)> {
+        vec![(0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1)]
+    }
+
+    #[test]
+    fn test_breadth_first_search_on_csr() {
+        let graph = CsrGraph::from_parts(0..5, edges());
+        assert_eq!(breadth_first_search(&graph, 0), vec![0, 1, 2, 3]);
+        assert_eq!(breadth_first_search(&graph, 4), vec![4]);
+    }
+
+    #[test]
+    fn test_depth_first_search_on_csr() {
+        let graph = CsrGraph::from_parts(0..5, edges());
+        assert_eq!(depth_first_search(&graph, 0), vec![0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn test_traversals_on_adjacency_list() {
+        let mut graph = WeightedAdjacencyList::new();
+        for (from, to, weight) in edges() {
+            graph.add_edge(from, to, weight);
+        }
+        let mut bfs = breadth_first_search(&graph, 0);
+        assert_eq!(bfs[0], 0);
+        assert_eq!(bfs[3], 3);
+        bfs.sort();
+        assert_eq!(bfs, vec![0, 1, 2, 3]);
+        let mut dfs = depth_first_search(&graph, 0);
+        dfs.sort();
+        assert_eq!(dfs, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_traversals_on_adjacency_matrix() {
+        let matrix = WeightedAdjacencyMatrix::from_options(vec![
+            vec![None, Some(1u8), Some(1)],
+            vec![None, None, None],
+            vec![Some(1), None, None],
+        ]).unwrap();
+        assert_eq!(breadth_first_search(&matrix, 2), vec![2, 0, 1]);
+        assert_eq!(depth_first_search(&matrix, 1), vec![1]);
+    }
+
+    #[test]
+    fn test_traversals_on_non_index_vertices() {
+        let mut graph = WeightedAdjacencyList::new();
+        graph.add_edge("a", "b", ());
+        graph.add_edge("b", "c", ());
+        assert_eq!(breadth_first_search(&graph, "a"), vec!["a", "b", "c"]);
+        assert_eq!(depth_first_search(&graph, "a"), vec!["a", "b", "c"]);
+    }
+}
+
+/// Returns the vertices reachable from `source` in breadth-first order.
+pub fn breadth_first_search<G>(graph: G, source: G::NodeId) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + Visitable,
+{
+    let mut seen = graph.visit_map();
+    let mut order = Vec::new();
+    let mut queue = Queue::new();
+    seen.visit(source);
+    queue.enqueue(source);
+
+    while let Some(curr) = queue.dequeue() {
+        order.push(curr);
+        for to in graph.neighbors(curr) {
+            if seen.visit(to) {
+                queue.enqueue(to);
+            }
+        }
+    }
+    order
+}
+
+/// Returns the vertices reachable from `source` in depth-first preorder.
+///
+/// Neighbors are explored in the order the graph yields them.
+pub fn depth_first_search<G>(graph: G, source: G::NodeId) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + Visitable,
+{
+    let mut seen = graph.visit_map();
+    let mut order = Vec::new();
+    let mut stack = Stack::new();
+    stack.push(source);
+
+    while let Some(curr) = stack.pop() {
+        if !seen.visit(curr) {
+            continue;
+        }
+        order.push(curr);
+        // Push in reverse so the first neighbor is explored first
+        let adjs: Vec<G::NodeId> = graph.neighbors(curr).collect();
+        for &to in adjs.iter().rev() {
+            if !seen.is_visited(&to) {
+                stack.push(to);
+            }
+        }
+    }
+    order
+}
@@ -0,0 +1,203 @@
+use super::{home_slot, TableStats};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::tests::Identity;
+
+    #[test]
+    fn test_map_operations() {
+        let mut map = ChainedHashMap::new();
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("b", 2), None);
+        assert_eq!(map.insert("a", 10), Some(1));
+        *map.get_mut("b").unwrap() += 1;
+        assert_eq!((map.get("a"), map.get("b"), map.get("c")), (Some(&10), Some(&3), None));
+        assert_eq!(map.remove("a"), Some(10));
+        assert_eq!(map.remove("a"), None);
+        assert_eq!(map.len(), 1);
+        assert!(map.contains_key("b"));
+    }
+
+    #[test]
+    fn test_colliding_keys_share_a_chain() {
+        let mut map = ChainedHashMap::with_hasher(Identity::default());
+        // 8 buckets: 3, 11 and 19 all land in bucket 3
+        for key in [3u64, 11, 19, 4] {
+            map.insert(key, key);
+        }
+        let stats = map.stats();
+        assert_eq!((stats.slots, stats.max_probe_length), (8, 3));
+        assert_eq!(stats.mean_probe_length, 7.0 / 4.0);
+        assert_eq!(map.remove(&11), Some(11));
+        assert_eq!((map.get(&3), map.get(&19)), (Some(&3), Some(&19)));
+    }
+
+    #[test]
+    fn test_grows_past_load_factor_one() {
+        let mut map = ChainedHashMap::with_hasher(Identity::default());
+        map.extend((0..8u64).map(|k| (k, ())));
+        assert_eq!(map.stats().slots, 8);
+        map.insert(8, ());
+        let stats = map.stats();
+        assert_eq!((stats.slots, stats.len, stats.max_probe_length), (16, 9, 1));
+    }
+}
+
+const MIN_BUCKETS: usize = 8;
+
+/// A hash map resolving collisions by separate chaining: each bucket holds a
+/// vector of the entries hashing to it.
+///
+/// The bucket count doubles once there are more entries than buckets, so
+/// chains stay short on average. Removal simply drops the entry from its
+/// chain, leaving nothing behind.
+pub struct ChainedHashMap<K, V, S = RandomState> {
+    buckets: Vec<Vec<(K, V)>>,
+    len: usize,
+    hasher: S,
+}
+
+impl<K, V> ChainedHashMap<K, V> {
+    pub fn new() -> Self {
+        ChainedHashMap::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> ChainedHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        ChainedHashMap { buckets: Vec::new(), len: 0, hasher }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.len = 0;
+    }
+
+    /// Iterates the entries in bucket order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.buckets.iter().flatten().map(|(key, value)| (key, value))
+    }
+
+    /// Load and chain lengths; an entry's probe length is its position in
+    /// its chain, counting from 1.
+    pub fn stats(&self) -> TableStats {
+        let lengths = self.buckets.iter().flat_map(|chain| 1..=chain.len());
+        TableStats::from_probe_lengths(self.buckets.len(), 0, lengths)
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> ChainedHashMap<K, V, S> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(mem::replace(existing, value));
+        }
+        if self.len >= self.buckets.len() {
+            self.resize((self.buckets.len() * 2).max(MIN_BUCKETS));
+        }
+        let bucket = self.bucket(&key);
+        self.buckets[bucket].push((key, value));
+        self.len += 1;
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        let chain = &self.buckets[self.bucket(key)];
+        chain.iter().find(|(k, _)| k.borrow() == key).map(|(_, value)| value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        let bucket = self.bucket(key);
+        self.buckets[bucket].iter_mut().find(|(k, _)| k.borrow() == key).map(|(_, value)| value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        let bucket = self.bucket(key);
+        let chain = &mut self.buckets[bucket];
+        let index = chain.iter().position(|(k, _)| k.borrow() == key)?;
+        self.len -= 1;
+        Some(chain.swap_remove(index).1)
+    }
+
+    fn bucket<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        home_slot(self.hasher.hash_one(key), self.buckets.len())
+    }
+
+    fn resize(&mut self, buckets: usize) {
+        let old = mem::replace(&mut self.buckets, (0..buckets).map(|_| Vec::new()).collect());
+        for (key, value) in old.into_iter().flatten() {
+            let bucket = self.bucket(&key);
+            self.buckets[bucket].push((key, value));
+        }
+    }
+}
+
+impl<K, V, S: Default> Default for ChainedHashMap<K, V, S> {
+    fn default() -> Self {
+        ChainedHashMap::with_hasher(S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for ChainedHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for ChainedHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for ChainedHashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = ChainedHashMap::default();
+        map.extend(iter);
+        map
+    }
+}
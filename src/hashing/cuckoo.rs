@@ -0,0 +1,427 @@
+use super::{home_slot, TableStats};
+use crate::rng::XorShift64;
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::tests::Identity;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_map_operations() {
+        let mut map = CuckooHashMap::new();
+        assert_eq!(map.insert('k', 1), None);
+        assert_eq!(map.insert('l', 2), None);
+        assert_eq!(map.insert('k', 10), Some(1));
+        *map.get_mut(&'l').unwrap() += 1;
+        assert_eq!((map.get(&'k'), map.get(&'l'), map.get(&'m')), (Some(&10), Some(&3), None));
+        assert_eq!(map.remove(&'k'), Some(10));
+        assert_eq!(map.remove(&'k'), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_lookups_probe_at_most_two_slots() {
+        let mut map = CuckooHashMap::with_hasher(Identity::default());
+        map.extend((0..10_000u64).map(|k| (k * 64, k)));
+        let stats = map.stats();
+        assert_eq!(stats.len, 10_000);
+        assert!(stats.max_probe_length <= 2);
+        assert!(stats.load_factor <= 0.5);
+        assert!((0..10_000u64).all(|k| map.get(&(k * 64)) == Some(&k)));
+    }
+
+    /// Hashes only the low four bits, so keys collide in groups.
+    #[derive(Debug, PartialEq, Eq)]
+    struct LowBits(u64);
+
+    impl Hash for LowBits {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            (self.0 % 16).hash(state);
+        }
+    }
+
+    #[test]
+    fn test_clustered_hashes_force_rehash() {
+        let mut map = CuckooHashMap::new();
+        // Three keys per hash value but only two slots each, so every hash
+        // value stashes a key and the stash overflows whatever the hasher
+        for key in 0..48 {
+            map.insert(LowBits(key), key);
+        }
+        assert!(map.rehashes() > 0);
+        assert!((0..48).all(|key| map.get(&LowBits(key)) == Some(&key)));
+        assert_eq!(map.stats().len, 48);
+    }
+
+    /// Counts how many times any key sharing `hashes` is hashed.
+    #[derive(Debug)]
+    struct Counted {
+        key: u64,
+        hashes: Rc<Cell<usize>>,
+    }
+
+    impl PartialEq for Counted {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Counted {}
+
+    impl Hash for Counted {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.hashes.set(self.hashes.get() + 1);
+            self.key.hash(state);
+        }
+    }
+
+    #[test]
+    fn test_each_operation_hashes_once() {
+        let hashes = Rc::new(Cell::new(0));
+        let counted = |key| Counted { key, hashes: Rc::clone(&hashes) };
+        let mut map = CuckooHashMap::new();
+        // Enough inserts to grow the tables and displace entries many times
+        for key in 0..1000 {
+            map.insert(counted(key), key);
+        }
+        assert_eq!(hashes.get(), 1000);
+        assert!((0..1000).all(|key| map.get(&counted(key)) == Some(&key)));
+        assert_eq!(map.insert(counted(7), 70), Some(7));
+        assert_eq!(map.remove(&counted(8)), Some(8));
+        assert_eq!(hashes.get(), 2002);
+    }
+
+    #[test]
+    fn test_identical_hashes_overflow_into_the_stash() {
+        let mut map = CuckooHashMap::with_hasher(Identity::default());
+        // Three keys with one hash share two slots, so one must be stashed
+        for key in [LowBits(1), LowBits(17), LowBits(33)] {
+            assert_eq!(map.insert(key, ()), None);
+        }
+        assert!([1, 17, 33].iter().all(|&key| map.contains_key(&LowBits(key))));
+        assert_eq!((map.len(), map.iter().count()), (3, 3));
+        assert_eq!(map.stats().max_probe_length, 3);
+        assert_eq!(map.remove(&LowBits(17)), Some(()));
+        assert_eq!(map.remove(&LowBits(17)), None);
+        assert!(map.contains_key(&LowBits(1)) && map.contains_key(&LowBits(33)));
+    }
+
+    #[test]
+    fn test_many_identical_hashes_stay_reachable() {
+        let mut map = CuckooHashMap::new();
+        for key in (0..200).map(|k| k * 16) {
+            map.insert(LowBits(key), key);
+        }
+        assert_eq!(map.len(), 200);
+        assert!((0..200).map(|k| k * 16).all(|key| map.get(&LowBits(key)) == Some(&key)));
+        // Rebuilds stay cheap: the tables never grow past what 200 entries need
+        assert!(map.stats().slots <= 512);
+    }
+}
+
+const MIN_SLOTS: usize = 8;
+// Displacement chains longer than this are treated as cycles
+const MAX_KICKS: usize = 64;
+// Entries the stash holds before the tables are rebuilt to take them back
+const STASH_SIZE: usize = 4;
+// Fresh hash functions tried per rebuild before settling for a fuller stash
+const MAX_REBUILD_ATTEMPTS: usize = 4;
+
+struct Entry<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+/// A hash map where each key may live in exactly one of two slots, one per
+/// table, chosen by two different hash functions.
+///
+/// Lookups and removals inspect two slots plus a small stash. An insert
+/// whose slots are both taken evicts one occupant to its alternative slot,
+/// which may evict another, and so on; if that chain runs too long the entry
+/// left over goes to the stash. Once the stash overflows, the tables are
+/// rebuilt under fresh hash functions, a bounded number of times. The tables
+/// also double once they are half full.
+///
+/// Both hash functions derive from a single hash of the key from the
+/// `BuildHasher`, mixed with a per-table seed. That hash is stored with
+/// each entry, so evictions and rebuilds never hash a key again. Keys whose
+/// whole hash collides can never be separated, so they stay in the stash, which then
+/// degrades to a linear scan rather than failing.
+pub struct CuckooHashMap<K, V, S = RandomState> {
+    tables: [Vec<Option<Entry<K, V>>>; 2],
+    stash: Vec<Entry<K, V>>,
+    // Stash length that triggers a rebuild
+    stash_limit: usize,
+    seeds: [u64; 2],
+    len: usize,
+    rehashes: usize,
+    rng: XorShift64,
+    hasher: S,
+}
+
+impl<K, V> CuckooHashMap<K, V> {
+    pub fn new() -> Self {
+        CuckooHashMap::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> CuckooHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        let mut rng = XorShift64::new(0xC0C0);
+        let seeds = [rng.next_u64(), rng.next_u64()];
+        CuckooHashMap {
+            tables: [Vec::new(), Vec::new()],
+            stash: Vec::new(),
+            stash_limit: STASH_SIZE,
+            seeds,
+            len: 0,
+            rehashes: 0,
+            rng,
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many times the tables were rebuilt under fresh hash functions
+    /// because entries could not be placed.
+    pub fn rehashes(&self) -> usize {
+        self.rehashes
+    }
+
+    pub fn clear(&mut self) {
+        self.tables = [Vec::new(), Vec::new()];
+        self.stash.clear();
+        self.stash_limit = STASH_SIZE;
+        self.len = 0;
+    }
+
+    /// Iterates the entries of the first table, then the second, then the
+    /// stash.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.tables.iter().flatten().flatten().chain(&self.stash).map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Load over both tables; entries in the first table have probe length
+    /// 1, those in the second 2 and stashed ones 2 plus their stash position.
+    pub fn stats(&self) -> TableStats {
+        let lengths = self.tables.iter().enumerate().flat_map(|(table, slots)| {
+            slots.iter().flatten().map(move |_| table + 1)
+        });
+        let lengths = lengths.chain((0..self.stash.len()).map(|index| index + 3));
+        TableStats::from_probe_lengths(self.tables[0].len() * 2, 0, lengths)
+    }
+
+    fn slots(&self) -> usize {
+        self.tables[0].len()
+    }
+
+    /// The slot an entry with `hash` may occupy in `table`.
+    fn position(&self, table: usize, hash: u64) -> usize {
+        home_slot(mix(hash ^ self.seeds[table]), self.slots())
+    }
+
+    fn value_mut(&mut self, location: Location) -> &mut V {
+        match location {
+            Location::Table(table, index) => &mut self.tables[table][index].as_mut().expect("occupied slot").value,
+            Location::Stash(index) => &mut self.stash[index].value,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> CuckooHashMap<K, V, S> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let hash = self.hasher.hash_one(&key);
+        if let Some(location) = self.find_hashed(hash, &key) {
+            return Some(mem::replace(self.value_mut(location), value));
+        }
+        let entry = Entry { hash, key, value };
+        // Half of the two tables' slots
+        if self.len + 1 > self.slots() {
+            self.rebuild((self.slots() * 2).max(MIN_SLOTS), Some(entry));
+        } else if let Err(homeless) = self.place(entry) {
+            self.stash.push(homeless);
+            if self.stash.len() > self.stash_limit {
+                self.rehashes += 1;
+                self.rebuild(self.slots(), None);
+            }
+        }
+        self.len += 1;
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.find(key)? {
+            Location::Table(table, index) => self.tables[table][index].as_ref().map(|entry| &entry.value),
+            Location::Stash(index) => Some(&self.stash[index].value),
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let location = self.find(key)?;
+        Some(self.value_mut(location))
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let location = self.find(key)?;
+        self.len -= 1;
+        match location {
+            Location::Table(table, index) => self.tables[table][index].take().map(|entry| entry.value),
+            Location::Stash(index) => Some(self.stash.swap_remove(index).value),
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<Location>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_hashed(self.hasher.hash_one(key), key)
+    }
+
+    fn find_hashed<Q>(&self, hash: u64, key: &Q) -> Option<Location>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if self.slots() == 0 {
+            return None;
+        }
+        let matches = |entry: &Entry<K, V>| entry.hash == hash && entry.key.borrow() == key;
+        let in_tables = (0..2).map(|table| (table, self.position(table, hash))).find(|&(table, index)| {
+            self.tables[table][index].as_ref().is_some_and(matches)
+        });
+        match in_tables {
+            Some((table, index)) => Some(Location::Table(table, index)),
+            None => self.stash.iter().position(matches).map(Location::Stash),
+        }
+    }
+
+    /// Places an absent entry, evicting occupants to their other slot.
+    /// Hands back whichever entry is left without a slot if the chain of
+    /// evictions runs past `MAX_KICKS`.
+    fn place(&mut self, mut entry: Entry<K, V>) -> Result<(), Entry<K, V>> {
+        for table in 0..2 {
+            let index = self.position(table, entry.hash);
+            if self.tables[table][index].is_none() {
+                self.tables[table][index] = Some(entry);
+                return Ok(());
+            }
+        }
+        let mut table = 0;
+        for _ in 0..MAX_KICKS {
+            let index = self.position(table, entry.hash);
+            match self.tables[table][index].replace(entry) {
+                None => return Ok(()),
+                Some(evicted) => entry = evicted,
+            }
+            table ^= 1;
+        }
+        Err(entry)
+    }
+
+    /// Rebuilds both tables with `slots` slots each and places every
+    /// stored entry plus `pending`. Tries up to `MAX_REBUILD_ATTEMPTS` sets
+    /// of fresh hash functions to fit all but `STASH_SIZE` of them; whatever
+    /// is still left over after the last attempt stays in the stash.
+    fn rebuild(&mut self, slots: usize, pending: Option<Entry<K, V>>) {
+        let tables = mem::take(&mut self.tables);
+        let mut entries: Vec<Entry<K, V>> =
+            tables.into_iter().flatten().flatten().chain(mem::take(&mut self.stash)).chain(pending).collect();
+        for attempt in 1..=MAX_REBUILD_ATTEMPTS {
+            self.seeds = [self.rng.next_u64(), self.rng.next_u64()];
+            self.tables = [(0..slots).map(|_| None).collect(), (0..slots).map(|_| None).collect()];
+            let mut homeless = Vec::new();
+            for entry in entries {
+                if let Err(entry) = self.place(entry) {
+                    homeless.push(entry);
+                }
+            }
+            if homeless.len() <= STASH_SIZE || attempt == MAX_REBUILD_ATTEMPTS {
+                // Entries no hash functions could place share their whole
+                // hash; letting the stash grow keeps inserting them from
+                // triggering a rebuild every time
+                self.stash_limit = STASH_SIZE.max(2 * homeless.len());
+                self.stash = homeless;
+                return;
+            }
+            self.rehashes += 1;
+            let tables = mem::take(&mut self.tables);
+            entries = tables.into_iter().flatten().flatten().chain(homeless).collect();
+        }
+    }
+}
+
+enum Location {
+    Table(usize, usize),
+    Stash(usize),
+}
+
+/// The splitmix64 finalizer, spreading every input bit over the output.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+impl<K, V, S: Default> Default for CuckooHashMap<K, V, S> {
+    fn default() -> Self {
+        CuckooHashMap::with_hasher(S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for CuckooHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for CuckooHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for CuckooHashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = CuckooHashMap::default();
+        map.extend(iter);
+        map
+    }
+}
@@ -0,0 +1,223 @@
+//! Hash maps with different collision-resolution strategies.
+//!
+//! Every map takes a pluggable `BuildHasher` (std's `RandomState` by
+//! default), grows automatically once it passes its maximum load factor and
+//! reports a `TableStats` snapshot of its load and probe lengths. `chaining`
+//! keeps a vector per bucket; `open_addressing` probes linearly or
+//! quadratically and leaves tombstones behind removals; `robin_hood` evens
+//! out probe lengths and deletes by shifting entries back; `cuckoo` gives
+//! every key two possible slots and evicts to make room.
+//!
+//! Table sizes are powers of two, so a hash picks its home slot with a mask.
+
+use std::fmt;
+
+pub mod chaining;
+pub mod cuckoo;
+pub mod open_addressing;
+pub mod robin_hood;
+
+#[cfg(test)]
+mod tests {
+    use super::chaining::ChainedHashMap;
+    use super::cuckoo::CuckooHashMap;
+    use super::open_addressing::{OpenAddressingHashMap, Probing};
+    use super::robin_hood::RobinHoodHashMap;
+    use super::*;
+    use crate::rng::XorShift64;
+    use std::collections::HashMap;
+    use std::hash::{BuildHasherDefault, DefaultHasher, Hasher};
+    use std::time::Instant;
+
+    /// Hashes a `u64` key to itself, so tests can place keys in chosen slots.
+    #[derive(Default)]
+    pub(super) struct IdentityHasher(u64);
+
+    impl Hasher for IdentityHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = self.0 << 8 | u64::from(byte);
+            }
+        }
+
+        fn write_u64(&mut self, n: u64) {
+            self.0 = n;
+        }
+    }
+
+    pub(super) type Identity = BuildHasherDefault<IdentityHasher>;
+
+    type Fixed = BuildHasherDefault<DefaultHasher>;
+
+    #[test]
+    fn test_maps_match_std_hash_map() {
+        let mut rng = XorShift64::new(0x4A5);
+        let mut chained = ChainedHashMap::with_hasher(Fixed::default());
+        let mut linear = OpenAddressingHashMap::with_probing_and_hasher(Probing::Linear, Fixed::default());
+        let mut quadratic = OpenAddressingHashMap::with_probing_and_hasher(Probing::Quadratic, Fixed::default());
+        let mut robin_hood = RobinHoodHashMap::with_hasher(Fixed::default());
+        let mut cuckoo = CuckooHashMap::with_hasher(Fixed::default());
+        let mut expected = HashMap::new();
+        for step in 0..5000 {
+            let key = rng.below(800);
+            if rng.below(3) < 2 {
+                let old = expected.insert(key, step);
+                assert_eq!(chained.insert(key, step), old);
+                assert_eq!(linear.insert(key, step), old);
+                assert_eq!(quadratic.insert(key, step), old);
+                assert_eq!(robin_hood.insert(key, step), old);
+                assert_eq!(cuckoo.insert(key, step), old);
+            } else {
+                let old = expected.remove(&key);
+                assert_eq!(chained.remove(&key), old);
+                assert_eq!(linear.remove(&key), old);
+                assert_eq!(quadratic.remove(&key), old);
+                assert_eq!(robin_hood.remove(&key), old);
+                assert_eq!(cuckoo.remove(&key), old);
+            }
+            let probe = rng.below(900);
+            let found = expected.get(&probe);
+            assert_eq!(chained.get(&probe), found);
+            assert_eq!(linear.get(&probe), found);
+            assert_eq!(quadratic.get(&probe), found);
+            assert_eq!(robin_hood.get(&probe), found);
+            assert_eq!(cuckoo.get(&probe), found);
+        }
+
+        let sorted = |mut entries: Vec<(u64, usize)>| {
+            entries.sort_unstable();
+            entries
+        };
+        let all = sorted(expected.iter().map(|(&k, &v)| (k, v)).collect());
+        assert_eq!(sorted(chained.iter().map(|(&k, &v)| (k, v)).collect()), all);
+        assert_eq!(sorted(linear.iter().map(|(&k, &v)| (k, v)).collect()), all);
+        assert_eq!(sorted(quadratic.iter().map(|(&k, &v)| (k, v)).collect()), all);
+        assert_eq!(sorted(robin_hood.iter().map(|(&k, &v)| (k, v)).collect()), all);
+        assert_eq!(sorted(cuckoo.iter().map(|(&k, &v)| (k, v)).collect()), all);
+        for stats in [chained.stats(), linear.stats(), quadratic.stats(), robin_hood.stats(), cuckoo.stats()] {
+            assert_eq!(stats.len, expected.len());
+            assert!(stats.mean_probe_length >= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_robin_hood_shortens_the_longest_probe() {
+        let mut rng = XorShift64::new(0x11);
+        let keys: Vec<u64> = (0..20_000).map(|_| rng.next_u64()).collect();
+        let linear: OpenAddressingHashMap<u64, (), Fixed> =
+            keys.iter().map(|&k| (k, ())).collect();
+        let robin_hood: RobinHoodHashMap<u64, (), Fixed> = keys.iter().map(|&k| (k, ())).collect();
+        let (linear, robin_hood) = (linear.stats(), robin_hood.stats());
+        assert_eq!(linear.slots, robin_hood.slots);
+        // Robin Hood moves the same total displacement around, so the mean
+        // matches linear probing while the worst case shrinks
+        assert!((linear.mean_probe_length - robin_hood.mean_probe_length).abs() < 1e-9);
+        assert!(robin_hood.max_probe_length < linear.max_probe_length);
+    }
+
+    #[test]
+    fn test_stats_display() {
+        let stats = TableStats::from_probe_lengths(8, 2, [1, 1, 2].into_iter());
+        assert_eq!(stats.len, 3);
+        assert_eq!(stats.to_string(), "3 entries in 8 slots (load 0.38, 2 tombstones), probe length mean 1.33 max 2");
+    }
+
+    fn bench_map<M>(
+        name: &str,
+        keys: &[u64],
+        map: &mut M,
+        insert: fn(&mut M, u64) -> Option<u64>,
+        contains: fn(&M, u64) -> bool,
+    ) {
+        let start = Instant::now();
+        for &key in keys {
+            insert(map, key);
+        }
+        let build = start.elapsed();
+        // The keys are even, so probing with every other key plus one misses
+        let start = Instant::now();
+        let hits = keys.iter().enumerate().filter(|&(i, &key)| contains(map, key + (i % 2) as u64)).count();
+        println!("{name:<12} insert {build:>12?}  lookup {:>12?}  ({hits} hits)", start.elapsed());
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_collision_strategies() {
+        const KEYS: usize = 500_000;
+        let mut rng = XorShift64::new(0xBA5E);
+        let keys: Vec<u64> = (0..KEYS).map(|_| rng.next_u64() >> 1 << 1).collect();
+
+        bench_map("std", &keys, &mut HashMap::new(), |m, k| m.insert(k, k), |m, k| m.contains_key(&k));
+        let mut map = ChainedHashMap::new();
+        bench_map("chaining", &keys, &mut map, |m, k| m.insert(k, k), |m, k| m.contains_key(&k));
+        println!("             {}", map.stats());
+        let mut map = OpenAddressingHashMap::with_probing(Probing::Linear);
+        bench_map("linear", &keys, &mut map, |m, k| m.insert(k, k), |m, k| m.contains_key(&k));
+        println!("             {}", map.stats());
+        let mut map = OpenAddressingHashMap::with_probing(Probing::Quadratic);
+        bench_map("quadratic", &keys, &mut map, |m, k| m.insert(k, k), |m, k| m.contains_key(&k));
+        println!("             {}", map.stats());
+        let mut map = RobinHoodHashMap::new();
+        bench_map("robin hood", &keys, &mut map, |m, k| m.insert(k, k), |m, k| m.contains_key(&k));
+        println!("             {}", map.stats());
+        let mut map = CuckooHashMap::new();
+        bench_map("cuckoo", &keys, &mut map, |m, k| m.insert(k, k), |m, k| m.contains_key(&k));
+        println!("             {}", map.stats());
+    }
+}
+
+/// A snapshot of how full a hash table is and how far lookups travel.
+///
+/// The probe length of an entry is the number of slots (or chain links) a
+/// successful lookup for it inspects, so 1 means it sits in its home slot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableStats {
+    pub len: usize,
+    /// Slots in an open-addressing table, or buckets for chaining.
+    pub slots: usize,
+    /// Removed entries still occupying slots, for tables that keep them.
+    pub tombstones: usize,
+    /// `len / slots`.
+    pub load_factor: f64,
+    pub mean_probe_length: f64,
+    pub max_probe_length: usize,
+}
+
+impl TableStats {
+    /// Summarizes a table from the probe length of each of its entries.
+    fn from_probe_lengths(slots: usize, tombstones: usize, probe_lengths: impl Iterator<Item = usize>) -> Self {
+        let (mut len, mut total, mut max) = (0, 0, 0);
+        for length in probe_lengths {
+            len += 1;
+            total += length;
+            max = max.max(length);
+        }
+        TableStats {
+            len,
+            slots,
+            tombstones,
+            load_factor: if slots == 0 { 0.0 } else { len as f64 / slots as f64 },
+            mean_probe_length: if len == 0 { 0.0 } else { total as f64 / len as f64 },
+            max_probe_length: max,
+        }
+    }
+}
+
+impl fmt::Display for TableStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} entries in {} slots (load {:.2}, {} tombstones), probe length mean {:.2} max {}",
+            self.len, self.slots, self.load_factor, self.tombstones, self.mean_probe_length, self.max_probe_length
+        )
+    }
+}
+
+/// The slot for `hash` in a power-of-two table of `slots` slots.
+fn home_slot(hash: u64, slots: usize) -> usize {
+    hash as usize & (slots - 1)
+}
@@ -0,0 +1,314 @@
+use super::{home_slot, TableStats};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::tests::Identity;
+
+    #[test]
+    fn test_map_operations() {
+        for probing in [Probing::Linear, Probing::Quadratic] {
+            let mut map = OpenAddressingHashMap::with_probing(probing);
+            assert_eq!(map.insert("x", 1), None);
+            assert_eq!(map.insert("y", 2), None);
+            assert_eq!(map.insert("x", 10), Some(1));
+            *map.get_mut("y").unwrap() += 1;
+            assert_eq!((map.get("x"), map.get("y"), map.get("z")), (Some(&10), Some(&3), None));
+            assert_eq!(map.remove("x"), Some(10));
+            assert_eq!(map.remove("x"), None);
+            assert_eq!((map.len(), map.probing()), (1, probing));
+        }
+    }
+
+    #[test]
+    fn test_probe_sequences() {
+        // Keys 1, 9 and 17 all start at slot 1 of 8
+        let keys = [1u64, 9, 17];
+        let mut linear = OpenAddressingHashMap::with_probing_and_hasher(Probing::Linear, Identity::default());
+        let mut quadratic = OpenAddressingHashMap::with_probing_and_hasher(Probing::Quadratic, Identity::default());
+        linear.extend(keys.map(|k| (k, ())));
+        quadratic.extend(keys.map(|k| (k, ())));
+        // Linear fills slots 1, 2, 3; quadratic jumps by 1 then 2 to 1, 2, 4
+        assert_eq!(linear.iter().map(|(&k, _)| k).collect::<Vec<_>>(), vec![1, 9, 17]);
+        // Slot 3 is taken under linear probing only
+        linear.insert(3, ());
+        quadratic.insert(3, ());
+        assert_eq!(linear.stats().max_probe_length, 3);
+        assert_eq!((linear.stats().mean_probe_length, quadratic.stats().mean_probe_length), (2.0, 1.75));
+    }
+
+    #[test]
+    fn test_tombstones_keep_probe_chains_intact() {
+        let mut map = OpenAddressingHashMap::with_probing_and_hasher(Probing::Linear, Identity::default());
+        map.extend([(1u64, 'a'), (17, 'b'), (33, 'c')]);
+        assert_eq!(map.remove(&17), Some('b'));
+        assert_eq!(map.stats().tombstones, 1);
+        // 33 is still reachable past the tombstone in 17's old slot
+        assert_eq!(map.get(&33), Some(&'c'));
+        // and the tombstone is reused by the next insert along the chain
+        map.insert(49, 'd');
+        assert_eq!(map.stats().tombstones, 0);
+        assert_eq!(map.get(&49), Some(&'d'));
+    }
+
+    #[test]
+    fn test_rebuild_clears_tombstones() {
+        let mut map = OpenAddressingHashMap::with_probing_and_hasher(Probing::Quadratic, Identity::default());
+        for key in 0..1000u64 {
+            map.insert(key, ());
+            map.remove(&key);
+        }
+        let stats = map.stats();
+        // Churn alone never grows the table past its minimum size
+        assert_eq!((stats.len, stats.slots), (0, 8));
+        assert!(stats.tombstones * 4 <= stats.slots * 3);
+        map.reserve(100);
+        assert_eq!(map.stats().slots, 256);
+    }
+}
+
+const MIN_SLOTS: usize = 8;
+
+/// How [`OpenAddressingHashMap`] picks the next slot after a collision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Probing {
+    /// Try the following slots one by one. Cache friendly, but runs of
+    /// occupied slots merge into long clusters.
+    Linear,
+    /// Jump 1, 2, 3, ... slots further each time (offsets are the
+    /// triangular numbers), which breaks up clusters and still visits every
+    /// slot of a power-of-two table.
+    Quadratic,
+}
+
+impl Probing {
+    /// Offset from the home slot of the `attempt`-th probe.
+    fn offset(self, attempt: usize) -> usize {
+        match self {
+            Probing::Linear => attempt,
+            Probing::Quadratic => attempt * (attempt + 1) / 2,
+        }
+    }
+}
+
+enum Slot<K, V> {
+    Empty,
+    // A removed entry: lookups must probe past it, inserts may reuse it
+    Tombstone,
+    Full(K, V),
+}
+
+/// A hash map that stores entries directly in one slot array and resolves
+/// collisions by probing for another slot.
+///
+/// Removal leaves a tombstone so later entries of the same probe sequence
+/// stay reachable. Once live entries plus tombstones pass three quarters of
+/// the slots the table is rebuilt: doubled if live entries alone fill half
+/// of it, otherwise at the same size just to clear the tombstones.
+pub struct OpenAddressingHashMap<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
+    len: usize,
+    tombstones: usize,
+    probing: Probing,
+    hasher: S,
+}
+
+impl<K, V> OpenAddressingHashMap<K, V> {
+    /// A linearly probed map.
+    pub fn new() -> Self {
+        OpenAddressingHashMap::with_probing(Probing::Linear)
+    }
+
+    pub fn with_probing(probing: Probing) -> Self {
+        OpenAddressingHashMap::with_probing_and_hasher(probing, RandomState::new())
+    }
+}
+
+impl<K, V, S> OpenAddressingHashMap<K, V, S> {
+    pub fn with_probing_and_hasher(probing: Probing, hasher: S) -> Self {
+        OpenAddressingHashMap { slots: Vec::new(), len: 0, tombstones: 0, probing, hasher }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn probing(&self) -> Probing {
+        self.probing
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+        self.tombstones = 0;
+    }
+
+    /// Iterates the entries in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.slots.iter().filter_map(|slot| match slot {
+            Slot::Full(key, value) => Some((key, value)),
+            _ => None,
+        })
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> OpenAddressingHashMap<K, V, S> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(mem::replace(existing, value));
+        }
+        if (self.len + self.tombstones + 1) * 4 > self.slots.len() * 3 {
+            let slots = if (self.len + 1) * 2 > self.slots.len() { self.slots.len() * 2 } else { self.slots.len() };
+            self.resize(slots.max(MIN_SLOTS));
+        }
+        // The key is absent, so the first free slot along its sequence is
+        // where it belongs
+        let index = self
+            .probe_sequence(&key)
+            .find(|&index| !matches!(self.slots[index], Slot::Full(..)))
+            .expect("the table always has a free slot");
+        if let Slot::Tombstone = mem::replace(&mut self.slots[index], Slot::Full(key, value)) {
+            self.tombstones -= 1;
+        }
+        self.len += 1;
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match &self.slots[self.find(key)?.0] {
+            Slot::Full(_, value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (index, _) = self.find(key)?;
+        match &mut self.slots[index] {
+            Slot::Full(_, value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Removes `key`, leaving a tombstone in its slot.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (index, _) = self.find(key)?;
+        match mem::replace(&mut self.slots[index], Slot::Tombstone) {
+            Slot::Full(_, value) => {
+                self.len -= 1;
+                self.tombstones += 1;
+                Some(value)
+            }
+            _ => unreachable!("find only returns full slots"),
+        }
+    }
+
+    /// Grows the table so that `additional` more entries fit without
+    /// rebuilding it.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len + self.tombstones + additional;
+        if needed * 4 > self.slots.len() * 3 {
+            self.resize((needed * 4).div_ceil(3).next_power_of_two().max(MIN_SLOTS));
+        }
+    }
+
+    /// Load, tombstones and the number of slots each lookup inspects.
+    pub fn stats(&self) -> TableStats {
+        let lengths = self.iter().map(|(key, _)| self.find(key).expect("stored keys are found").1);
+        TableStats::from_probe_lengths(self.slots.len(), self.tombstones, lengths)
+    }
+
+    /// The slots `key` may occupy, in the order they are tried.
+    fn probe_sequence<Q: Hash + ?Sized>(&self, key: &Q) -> impl Iterator<Item = usize> {
+        let slots = self.slots.len();
+        let home = home_slot(self.hasher.hash_one(key), slots.max(1));
+        let probing = self.probing;
+        (0..slots).map(move |attempt| (home + probing.offset(attempt)) & (slots - 1))
+    }
+
+    /// The slot holding `key` and the number of slots probed to reach it.
+    fn find<Q>(&self, key: &Q) -> Option<(usize, usize)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        for (attempt, index) in self.probe_sequence(key).enumerate() {
+            match &self.slots[index] {
+                Slot::Empty => return None,
+                Slot::Full(k, _) if k.borrow() == key => return Some((index, attempt + 1)),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn resize(&mut self, slots: usize) {
+        let old = mem::replace(&mut self.slots, (0..slots).map(|_| Slot::Empty).collect());
+        self.tombstones = 0;
+        for slot in old {
+            if let Slot::Full(key, value) = slot {
+                let index = self
+                    .probe_sequence(&key)
+                    .find(|&index| matches!(self.slots[index], Slot::Empty))
+                    .expect("the new table has room");
+                self.slots[index] = Slot::Full(key, value);
+            }
+        }
+    }
+}
+
+impl<K, V, S: Default> Default for OpenAddressingHashMap<K, V, S> {
+    fn default() -> Self {
+        OpenAddressingHashMap::with_probing_and_hasher(Probing::Linear, S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for OpenAddressingHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for OpenAddressingHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for OpenAddressingHashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OpenAddressingHashMap::default();
+        map.extend(iter);
+        map
+    }
+}
@@ -0,0 +1,282 @@
+use super::{home_slot, TableStats};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::tests::Identity;
+
+    #[test]
+    fn test_map_operations() {
+        let mut map = RobinHoodHashMap::new();
+        assert_eq!(map.insert(String::from("p"), 1), None);
+        assert_eq!(map.insert(String::from("q"), 2), None);
+        assert_eq!(map.insert(String::from("p"), 10), Some(1));
+        *map.get_mut("q").unwrap() += 1;
+        assert_eq!((map.get("p"), map.get("q"), map.get("r")), (Some(&10), Some(&3), None));
+        assert_eq!(map.remove("p"), Some(10));
+        assert_eq!(map.remove("p"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_displaces_richer_entries() {
+        let mut map = RobinHoodHashMap::with_hasher(Identity::default());
+        // 2 and 3 sit in their home slots; 1, 9 and 17 all want slot 1
+        map.extend([(2u64, ()), (3, ()), (1, ()), (9, ()), (17, ())]);
+        let keys: Vec<u64> = map.iter().map(|(&k, _)| k).collect();
+        // The poorer cluster at slot 1 pushes 2 and 3 further along
+        assert_eq!(keys, vec![1, 9, 17, 2, 3]);
+        let stats = map.stats();
+        assert_eq!((stats.max_probe_length, stats.mean_probe_length), (3, 2.4));
+    }
+
+    #[test]
+    fn test_backward_shift_deletion() {
+        let mut map = RobinHoodHashMap::with_hasher(Identity::default());
+        map.extend([(1u64, 'a'), (9, 'b'), (17, 'c'), (2, 'd')]);
+        assert_eq!(map.remove(&1), Some('a'));
+        // Everything after slot 1 moved back one slot: no tombstones
+        let keys: Vec<u64> = map.iter().map(|(&k, _)| k).collect();
+        assert_eq!(keys, vec![9, 17, 2]);
+        let stats = map.stats();
+        assert_eq!((stats.tombstones, stats.max_probe_length), (0, 2));
+        // Entries already at home stop the shift
+        map.insert(4, 'e');
+        assert_eq!(map.remove(&2), Some('d'));
+        assert_eq!(map.iter().map(|(&k, _)| k).collect::<Vec<_>>(), vec![9, 17, 4]);
+    }
+
+    #[test]
+    fn test_grows_at_load_factor() {
+        let mut map = RobinHoodHashMap::with_hasher(Identity::default());
+        map.extend((0..7u64).map(|k| (k, ())));
+        assert_eq!(map.stats().slots, 8);
+        map.insert(7, ());
+        assert_eq!(map.stats().slots, 16);
+        assert!((0..8).all(|k| map.contains_key(&k)));
+    }
+}
+
+const MIN_SLOTS: usize = 8;
+
+struct Entry<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+/// A linearly probed hash map using Robin Hood insertion and backward-shift
+/// deletion.
+///
+/// An inserted entry takes the slot of any entry closer to its own home
+/// than the newcomer is, and that entry continues probing instead. This
+/// evens out probe lengths and lets a lookup stop as soon as it meets an
+/// entry nearer home than it would be. Removal shifts the following
+/// displaced entries back one slot, so no tombstones are needed. The table
+/// doubles once it is 7/8 full.
+pub struct RobinHoodHashMap<K, V, S = RandomState> {
+    slots: Vec<Option<Entry<K, V>>>,
+    len: usize,
+    hasher: S,
+}
+
+impl<K, V> RobinHoodHashMap<K, V> {
+    pub fn new() -> Self {
+        RobinHoodHashMap::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> RobinHoodHashMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        RobinHoodHashMap { slots: Vec::new(), len: 0, hasher }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+    }
+
+    /// Iterates the entries in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.slots.iter().flatten().map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Load and probe lengths; an entry's probe length is its distance
+    /// from its home slot plus one.
+    pub fn stats(&self) -> TableStats {
+        let lengths = (0..self.slots.len())
+            .filter_map(|index| self.slots[index].as_ref().map(|entry| self.distance(entry.hash, index) + 1));
+        TableStats::from_probe_lengths(self.slots.len(), 0, lengths)
+    }
+
+    /// How many slots past its home slot an entry with `hash` sits at `index`.
+    fn distance(&self, hash: u64, index: usize) -> usize {
+        index.wrapping_sub(home_slot(hash, self.slots.len())) & (self.slots.len() - 1)
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    /// Places an entry known to be absent, displacing richer entries.
+    fn place(&mut self, mut entry: Entry<K, V>) {
+        let mask = self.mask();
+        let mut index = home_slot(entry.hash, self.slots.len());
+        let mut distance = 0;
+        loop {
+            let resident = match &mut self.slots[index] {
+                None => {
+                    self.slots[index] = Some(entry);
+                    return;
+                }
+                Some(resident) => resident,
+            };
+            let resident_distance = index.wrapping_sub(home_slot(resident.hash, mask + 1)) & mask;
+            if resident_distance < distance {
+                mem::swap(resident, &mut entry);
+                distance = resident_distance;
+            }
+            index = (index + 1) & mask;
+            distance += 1;
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> RobinHoodHashMap<K, V, S> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(mem::replace(existing, value));
+        }
+        if (self.len + 1) * 8 > self.slots.len() * 7 {
+            self.resize((self.slots.len() * 2).max(MIN_SLOTS));
+        }
+        let hash = self.hasher.hash_one(&key);
+        self.place(Entry { hash, key, value });
+        self.len += 1;
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_ref().map(|entry| &entry.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_mut().map(|entry| &mut entry.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Removes `key`, then shifts the displaced entries after it back by
+    /// one slot until reaching an empty slot or an entry at home.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut hole = self.find(key)?;
+        let removed = self.slots[hole].take().expect("find returns occupied slots");
+        let mask = self.mask();
+        loop {
+            let next = (hole + 1) & mask;
+            match &self.slots[next] {
+                Some(entry) if self.distance(entry.hash, next) > 0 => {
+                    self.slots[hole] = self.slots[next].take();
+                    hole = next;
+                }
+                _ => break,
+            }
+        }
+        self.len -= 1;
+        Some(removed.value)
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.slots.is_empty() {
+            return None;
+        }
+        let hash = self.hasher.hash_one(key);
+        let mut index = home_slot(hash, self.slots.len());
+        let mut distance = 0;
+        while let Some(entry) = &self.slots[index] {
+            // An entry nearer its home than we are means `key` would have
+            // displaced it, so `key` is absent
+            if self.distance(entry.hash, index) < distance {
+                return None;
+            }
+            if entry.hash == hash && entry.key.borrow() == key {
+                return Some(index);
+            }
+            index = (index + 1) & self.mask();
+            distance += 1;
+        }
+        None
+    }
+
+    fn resize(&mut self, slots: usize) {
+        let old = mem::replace(&mut self.slots, (0..slots).map(|_| None).collect());
+        for entry in old.into_iter().flatten() {
+            self.place(entry);
+        }
+    }
+}
+
+impl<K, V, S: Default> Default for RobinHoodHashMap<K, V, S> {
+    fn default() -> Self {
+        RobinHoodHashMap::with_hasher(S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for RobinHoodHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for RobinHoodHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for RobinHoodHashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = RobinHoodHashMap::default();
+        map.extend(iter);
+        map
+    }
+}
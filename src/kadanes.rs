@@ -1,23 +1,394 @@
-use std::cmp;
+use crate::data_structures::weighted_adj_matrix::WeightedAdjacencyMatrix;
+use crate::numeric::Signed;
+use std::fmt;
+use std::ops::Range;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub fn kadanes(arr: &[i32]) -> (i32, Vec<i32>) {
-    let mut max_so_far = i32::MIN;
-    let mut max_ending_here = 0;
-    let mut start_index = 0;
-    let mut end_index = 0;
-    let mut temp_start_index = 0;
+    #[test]
+    fn test_kadanes_classic() {
+        let a = [-3, -2, -3, 4, -1, -2, 1, 5, -3];
+        assert_eq!(kadanes(&a), Ok(Subarray { sum: 7, range: 3..8 }));
+    }
+
+    #[test]
+    fn test_kadanes_all_negative() {
+        let a = [-8i64, -3, -6, -2, -5, -4];
+        assert_eq!(kadanes(&a), Ok(Subarray { sum: -2, range: 3..4 }));
+        assert_eq!(max_subarray(&a, EmptySubarray::Allowed), Ok(Subarray { sum: 0, range: 0..0 }));
+    }
+
+    #[test]
+    fn test_kadanes_empty_slice() {
+        let a: [i32; 0] = [];
+        assert_eq!(kadanes(&a), Err(KadaneError::Empty));
+        assert_eq!(max_subarray(&a, EmptySubarray::Allowed), Ok(Subarray { sum: 0, range: 0..0 }));
+    }
+
+    #[test]
+    fn test_kadanes_overflow_is_reported() {
+        let a = [i32::MAX, 1];
+        assert_eq!(kadanes(&a), Err(KadaneError::Overflow));
+        let wide: Vec<i64> = a.iter().map(|&x| x as i64).collect();
+        assert_eq!(kadanes(&wide), Ok(Subarray { sum: i32::MAX as i64 + 1, range: 0..2 }));
+    }
+
+    #[test]
+    fn test_kadanes_floats() {
+        let a = [1.5, -0.5, 2.0, -10.0, 3.0];
+        assert_eq!(kadanes(&a), Ok(Subarray { sum: 3.0, range: 0..3 }));
+    }
+
+    #[test]
+    fn test_min_subarray() {
+        let a = [3, -4, 2, -3, -1, 7, -5];
+        assert_eq!(min_subarray(&a, EmptySubarray::Disallowed), Ok(Subarray { sum: -6, range: 1..5 }));
+        let positive = [1, 2, 3];
+        assert_eq!(min_subarray(&positive, EmptySubarray::Allowed), Ok(Subarray { sum: 0, range: 0..0 }));
+        assert_eq!(min_subarray(&positive, EmptySubarray::Disallowed), Ok(Subarray { sum: 1, range: 0..1 }));
+    }
+
+    #[test]
+    fn test_max_circular_subarray_wraps() {
+        let a = [5, -3, 5];
+        let best = max_circular_subarray(&a, EmptySubarray::Disallowed).unwrap();
+        assert_eq!(best, Subarray { sum: 10, range: 2..4 });
+        let indices: Vec<usize> = best.range.map(|i| i % a.len()).collect();
+        assert_eq!(indices, vec![2, 0]);
+    }
+
+    #[test]
+    fn test_max_circular_subarray_without_wrap() {
+        let a = [-2, 4, 5, -10];
+        assert_eq!(max_circular_subarray(&a, EmptySubarray::Disallowed), Ok(Subarray { sum: 9, range: 1..3 }));
+        let negative = [-3, -1, -2];
+        assert_eq!(max_circular_subarray(&negative, EmptySubarray::Disallowed), Ok(Subarray { sum: -1, range: 1..2 }));
+        assert_eq!(max_circular_subarray(&negative, EmptySubarray::Allowed), Ok(Subarray { sum: 0, range: 0..0 }));
+    }
+
+    #[test]
+    fn test_top_k_subarrays() {
+        let a = [4, -10, 3, 3, -10, 5, -1, -20, 2];
+        let top = top_k_subarrays(&a, 3).unwrap();
+        assert_eq!(
+            top,
+            vec![
+                Subarray { sum: 6, range: 2..4 },
+                Subarray { sum: 5, range: 5..6 },
+                Subarray { sum: 4, range: 0..1 },
+            ]
+        );
+        assert_eq!(top_k_subarrays(&[1, -5, 2], 5).unwrap().len(), 3);
+        assert_eq!(top_k_subarrays::<i32>(&[], 2), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_max_sum_submatrix() {
+        let matrix = WeightedAdjacencyMatrix::new(vec![
+            vec![1, 2, -1, -4, -20],
+            vec![-8, -3, 4, 2, 1],
+            vec![3, 8, 10, 1, 3],
+            vec![-4, -1, 1, 7, -6],
+        ]).unwrap();
+        let best = max_sum_submatrix(&matrix).unwrap();
+        assert_eq!(best, Submatrix { sum: 29, rows: 1..4, cols: 1..4 });
+    }
+
+    #[test]
+    fn test_max_sum_submatrix_tall_and_sparse() {
+        let matrix = WeightedAdjacencyMatrix::from_options(vec![
+            vec![Some(-1i64), Some(-1)],
+            vec![Some(5), None],
+            vec![None, Some(4)],
+            vec![Some(-9), Some(-9)],
+            vec![Some(2), Some(-1)],
+        ]).unwrap();
+        let best = max_sum_submatrix(&matrix).unwrap();
+        assert_eq!(best, Submatrix { sum: 9, rows: 1..3, cols: 0..2 });
+    }
+
+    #[test]
+    fn test_max_sum_submatrix_empty() {
+        let matrix: WeightedAdjacencyMatrix<i32> = WeightedAdjacencyMatrix::empty(0, 3);
+        assert_eq!(max_sum_submatrix(&matrix), Err(KadaneError::Empty));
+    }
+
+    #[test]
+    fn test_max_subarray_tracker_matches_batch() {
+        let a = [-3, -2, -3, 4, -1, -2, 1, 5, -3];
+        let mut tracker = MaxSubarrayTracker::new();
+        assert_eq!(tracker.best(), None);
+        tracker.consume(a.iter().copied()).unwrap();
+        assert_eq!(tracker.len(), a.len());
+        assert_eq!(tracker.best(), kadanes(&a).ok().as_ref());
+        assert_eq!(tracker.current(), Some(Subarray { sum: 4, range: 3..9 }));
+        tracker.push(10).unwrap();
+        assert_eq!(tracker.best(), Some(&Subarray { sum: 14, range: 3..10 }));
+    }
+
+    #[test]
+    fn test_max_subarray_tracker_overflow_keeps_state() {
+        let mut tracker = MaxSubarrayTracker::new();
+        tracker.push(i8::MAX).unwrap();
+        assert_eq!(tracker.push(1), Err(KadaneError::Overflow));
+        assert_eq!(tracker.len(), 1);
+        assert_eq!(tracker.best(), Some(&Subarray { sum: i8::MAX, range: 0..1 }));
+    }
+}
+
+/// Whether the empty subarray (sum zero) is an acceptable answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptySubarray {
+    Allowed,
+    Disallowed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KadaneError {
+    /// The input is empty and the empty subarray is not allowed.
+    Empty,
+    /// A subarray sum does not fit in the element type; retry with a wider type.
+    Overflow,
+}
+
+impl fmt::Display for KadaneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KadaneError::Empty => write!(f, "no non-empty subarray in an empty input"),
+            KadaneError::Overflow => write!(f, "subarray sum overflows the element type"),
+        }
+    }
+}
+
+impl std::error::Error for KadaneError {}
+
+/// A contiguous subarray given by its index range and the sum of its elements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subarray<T> {
+    pub sum: T,
+    pub range: Range<usize>,
+}
+
+/// Finds the non-empty contiguous subarray with the largest sum.
+///
+/// Ties are broken in favour of the earliest, then shortest, subarray.
+pub fn kadanes<T: Signed>(arr: &[T]) -> Result<Subarray<T>, KadaneError> {
+    max_subarray(arr, EmptySubarray::Disallowed)
+}
 
+/// Finds the contiguous subarray with the largest sum.
+pub fn max_subarray<T: Signed>(arr: &[T], empty: EmptySubarray) -> Result<Subarray<T>, KadaneError> {
+    extreme_subarray(arr, empty, |a, b| a > b)
+}
+
+/// Finds the contiguous subarray with the smallest sum.
+pub fn min_subarray<T: Signed>(arr: &[T], empty: EmptySubarray) -> Result<Subarray<T>, KadaneError> {
+    extreme_subarray(arr, empty, |a, b| a < b)
+}
+
+/// Kadane's algorithm parameterised by the comparison that defines a better sum.
+fn extreme_subarray<T, F>(arr: &[T], empty: EmptySubarray, better: F) -> Result<Subarray<T>, KadaneError>
+where
+    T: Signed,
+    F: Fn(T, T) -> bool,
+{
+    let mut best = match empty {
+        EmptySubarray::Allowed => Some(Subarray { sum: T::zero(), range: 0..0 }),
+        EmptySubarray::Disallowed => None,
+    };
+    let mut current: Option<(T, usize)> = None;
     for (i, &num) in arr.iter().enumerate() {
-        max_ending_here = cmp::max(max_ending_here + num, num);
-        if max_ending_here == num {
-            temp_start_index = i;
+        step(&mut current, &mut best, i, num, &better)?;
+    }
+    best.ok_or(KadaneError::Empty)
+}
+
+/// Advances Kadane's algorithm by the element `num` at index `i`.
+///
+/// `current` is the running subarray ending just before `i` as `(sum, start)`
+/// and `best` the best subarray found so far.
+fn step<T, F>(
+    current: &mut Option<(T, usize)>,
+    best: &mut Option<Subarray<T>>,
+    i: usize,
+    num: T,
+    better: F,
+) -> Result<(), KadaneError>
+where
+    T: Signed,
+    F: Fn(T, T) -> bool,
+{
+    // Extend the running subarray only while it still improves on starting afresh
+    let (sum, start) = match *current {
+        Some((sum, start)) if better(sum, T::zero()) => {
+            (sum.checked_add(num).ok_or(KadaneError::Overflow)?, start)
+        }
+        _ => (num, i),
+    };
+    *current = Some((sum, start));
+    if best.as_ref().is_none_or(|b| better(sum, b.sum)) {
+        *best = Some(Subarray { sum, range: start..i + 1 });
+    }
+    Ok(())
+}
+
+/// Finds the largest-sum subarray when the input wraps around end to start.
+///
+/// A wrapping result has `range.end > arr.len()`; its elements are at indices
+/// `range.map(|i| i % arr.len())`.
+pub fn max_circular_subarray<T: Signed>(arr: &[T], empty: EmptySubarray) -> Result<Subarray<T>, KadaneError> {
+    let straight = max_subarray(arr, empty)?;
+    if arr.is_empty() {
+        return Ok(straight);
+    }
+    // The best wrapping subarray is the complement of the smallest inner subarray
+    let total = arr
+        .iter()
+        .try_fold(T::zero(), |acc, &x| acc.checked_add(x))
+        .ok_or(KadaneError::Overflow)?;
+    let inner = min_subarray(arr, EmptySubarray::Disallowed)?;
+    let wrapped = Subarray {
+        sum: total.checked_sub(inner.sum).ok_or(KadaneError::Overflow)?,
+        range: inner.range.end..inner.range.start + arr.len(),
+    };
+    if !wrapped.range.is_empty() && wrapped.sum > straight.sum {
+        Ok(wrapped)
+    } else {
+        Ok(straight)
+    }
+}
+
+/// Returns up to `k` non-overlapping subarrays, greedily taking the largest-sum
+/// subarray of what is left each time, in the order they were taken.
+pub fn top_k_subarrays<T: Signed>(arr: &[T], k: usize) -> Result<Vec<Subarray<T>>, KadaneError> {
+    let mut segments: Vec<Range<usize>> = Vec::new();
+    segments.push(0..arr.len());
+    let mut taken = Vec::new();
+    while taken.len() < k {
+        let mut best: Option<(usize, Subarray<T>)> = None;
+        for (s, segment) in segments.iter().enumerate() {
+            if segment.is_empty() {
+                continue;
+            }
+            let found = kadanes(&arr[segment.clone()])?;
+            if best.as_ref().is_none_or(|(_, b)| found.sum > b.sum) {
+                let range = segment.start + found.range.start..segment.start + found.range.end;
+                best = Some((s, Subarray { sum: found.sum, range }));
+            }
         }
-        if max_so_far < max_ending_here {
-            max_so_far = max_ending_here;
-            start_index = temp_start_index;
-            end_index = i;
+        let Some((s, subarray)) = best else {
+            break;
+        };
+        let segment = segments.swap_remove(s);
+        segments.push(segment.start..subarray.range.start);
+        segments.push(subarray.range.end..segment.end);
+        // Keep segments in index order so ties resolve to the earliest subarray
+        segments.sort_by_key(|segment| segment.start);
+        taken.push(subarray);
+    }
+    Ok(taken)
+}
+
+/// A rectangular region given by its row and column ranges and the sum of its cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submatrix<T> {
+    pub sum: T,
+    pub rows: Range<usize>,
+    pub cols: Range<usize>,
+}
+
+/// Finds the non-empty rectangle with the largest sum, counting absent cells as zero.
+///
+/// Fixes every pair of bounds along the shorter dimension, compresses the band
+/// between them into one line of sums and runs Kadane's algorithm on it, for
+/// O(n²·m) time with n the shorter and m the longer dimension.
+pub fn max_sum_submatrix<T: Signed>(matrix: &WeightedAdjacencyMatrix<T>) -> Result<Submatrix<T>, KadaneError> {
+    let (rows, cols) = matrix.shape();
+    let transposed = rows > cols;
+    let (outer, inner) = if transposed { (cols, rows) } else { (rows, cols) };
+    let cell = |o: usize, i: usize| {
+        let value = if transposed { matrix.get_value(i, o) } else { matrix.get_value(o, i) };
+        value.unwrap_or_else(T::zero)
+    };
+
+    let mut best: Option<Submatrix<T>> = None;
+    for first in 0..outer {
+        let mut band = vec![T::zero(); inner];
+        for last in first..outer {
+            for (i, sum) in band.iter_mut().enumerate() {
+                *sum = sum.checked_add(cell(last, i)).ok_or(KadaneError::Overflow)?;
+            }
+            let found = kadanes(&band)?;
+            if best.as_ref().is_none_or(|b| found.sum > b.sum) {
+                let (band_range, line_range) = (first..last + 1, found.range);
+                let (rows, cols) = if transposed { (line_range, band_range) } else { (band_range, line_range) };
+                best = Some(Submatrix { sum: found.sum, rows, cols });
+            }
         }
     }
+    best.ok_or(KadaneError::Empty)
+}
+
+/// Tracks the maximum-sum subarray of a stream without buffering it.
+///
+/// Each `push` is O(1); `best` reports the best window seen so far as an
+/// index range into the stream.
+#[derive(Debug, Clone)]
+pub struct MaxSubarrayTracker<T> {
+    seen: usize,
+    current: Option<(T, usize)>,
+    best: Option<Subarray<T>>,
+}
+
+impl<T: Signed> MaxSubarrayTracker<T> {
+    pub fn new() -> Self {
+        MaxSubarrayTracker { seen: 0, current: None, best: None }
+    }
+
+    /// Consumes the next value of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KadaneError::Overflow` if the running sum overflows; the
+    /// tracker is left as it was before the call.
+    pub fn push(&mut self, value: T) -> Result<(), KadaneError> {
+        let (mut current, mut best) = (self.current, self.best.clone());
+        step(&mut current, &mut best, self.seen, value, |a, b| a > b)?;
+        self.current = current;
+        self.best = best;
+        self.seen += 1;
+        Ok(())
+    }
+
+    /// Consumes every value of an iterator, stopping at the first overflow.
+    pub fn consume<I: IntoIterator<Item = T>>(&mut self, values: I) -> Result<(), KadaneError> {
+        values.into_iter().try_for_each(|value| self.push(value))
+    }
 
-    (max_so_far, arr[start_index..=end_index].to_vec())
+    /// Returns the best window so far, or `None` before the first value.
+    pub fn best(&self) -> Option<&Subarray<T>> {
+        self.best.as_ref()
+    }
+
+    /// Returns the best window ending at the latest value.
+    pub fn current(&self) -> Option<Subarray<T>> {
+        self.current
+            .map(|(sum, start)| Subarray { sum, range: start..self.seen })
+    }
+
+    /// Returns the number of values consumed.
+    pub fn len(&self) -> usize {
+        self.seen
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen == 0
+    }
+}
+
+impl<T: Signed> Default for MaxSubarrayTracker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -7,7 +7,24 @@ mod bubble_sort;
 mod quick_sort;
 mod dijkstras;
 mod kadanes;
+mod dp;
+mod diff;
+mod string_search;
+mod suffix;
+mod expression;
+mod monotonic_stack;
+mod cycle_detection;
+mod metrics;
+mod cache;
+mod hashing;
+mod trees;
+mod tries;
 mod data_structures;
+mod numeric;
+mod graph;
+mod graph_traversal;
+mod graph_io;
+mod rng;
 fn linear_search_demo(){
     println!("-------------------");
     println!("LINEAR SEARCH DEMO:");
@@ -93,11 +110,11 @@ fn weighted_adj_matrix_demo(){
     println!("-------------------");
     println!("WEIGHTED ADJACENCY MATRIX DEMO:");
     println!("-------------------");
-    let mut matrix = data_structures::weighted_adj_matrix::WeightedAdjacencyMatrix::new(vec![
+    let mut matrix = data_structures::weighted_adj_matrix::WeightedAdjacencyMatrix::<u64>::new(vec![
         vec![1, 2, 3],
         vec![4, 5, 6],
         vec![7, 8, 9],
-    ]);
+    ]).expect("rows have equal length");
     // Display the matrix before modification
     println!("Matrix before modification:");
     matrix.display_matrix();
@@ -109,12 +126,24 @@ fn weighted_adj_matrix_demo(){
     // Display the matrix after modification
     println!("Matrix after modification:");
     matrix.display_matrix();
+    // Demo of matrix multiplication
+    match &matrix * &matrix.transpose() {
+        Ok(product) => {
+            println!("Matrix multiplied by its transpose:");
+            product.display_matrix();
+        }
+        Err(err) => println!("Error: {}", err),
+    }
 }
 fn kadanes_demo(){
     let a = [-3, -2, -3, 4, -1, -2, 1, 5, -3];
-    let (max_sum, max_subarray) = kadanes::kadanes(&a);
-    println!("Maximum contiguous sum is {}", max_sum);
-    println!("Subarray contributing to maximum sum: {:?}", max_subarray);
+    match kadanes::kadanes(&a) {
+        Ok(max_subarray) => {
+            println!("Maximum contiguous sum is {}", max_subarray.sum);
+            println!("Subarray contributing to maximum sum: {:?}", &a[max_subarray.range]);
+        }
+        Err(err) => println!("Error: {}", err),
+    }
 }
 fn main() {
     println!("");
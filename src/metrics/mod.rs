@@ -0,0 +1,7 @@
+//! Rolling telemetry aggregates and rate limiters built on `RingBuffer`.
+//!
+//! Time is passed in explicitly as `Instant`s rather than read from the
+//! clock, so callers decide what "now" is and tests are deterministic.
+
+pub mod rate_limiter;
+pub mod rolling_window;
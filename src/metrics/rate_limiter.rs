@@ -0,0 +1,139 @@
+use crate::data_structures::ring_buffer::{OverflowPolicy, RingBuffer};
+use std::time::{Duration, Instant};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(base: Instant, millis: u64) -> Instant {
+        base + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_sliding_window_limiter() {
+        let base = Instant::now();
+        let mut limiter = SlidingWindowLimiter::new(2, Duration::from_secs(1));
+        assert!(limiter.try_acquire(at(base, 0)));
+        assert!(limiter.try_acquire(at(base, 300)));
+        assert!(!limiter.try_acquire(at(base, 500)));
+        assert_eq!(limiter.retry_after(at(base, 500)), Some(Duration::from_millis(500)));
+        // The first hit leaves the window at 1000ms
+        assert!(limiter.try_acquire(at(base, 1000)));
+        assert!(!limiter.try_acquire(at(base, 1200)));
+        assert!(limiter.try_acquire(at(base, 1300)));
+        assert_eq!(limiter.remaining(at(base, 2200)), 1);
+        assert_eq!(limiter.retry_after(at(base, 2200)), None);
+        assert_eq!(limiter.remaining(at(base, 2300)), 2);
+    }
+
+    #[test]
+    fn test_sliding_window_limiter_with_zero_limit() {
+        let mut limiter = SlidingWindowLimiter::new(0, Duration::from_secs(1));
+        assert!(!limiter.try_acquire(Instant::now()));
+        assert_eq!(limiter.retry_after(Instant::now()), None);
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let base = Instant::now();
+        let mut bucket = TokenBucket::new(3.0, 2.0, base);
+        assert!(bucket.try_acquire(at(base, 0), 3.0));
+        assert!(!bucket.try_acquire(at(base, 0), 1.0));
+        // Two tokens per second: one token after 500ms
+        assert!(bucket.try_acquire(at(base, 500), 1.0));
+        assert!(!bucket.try_acquire(at(base, 600), 1.0));
+        // Refills stop at capacity
+        assert_eq!(bucket.available(at(base, 10_000)), 3.0);
+        assert!(!bucket.try_acquire(at(base, 10_000), 4.0));
+        assert!(bucket.try_acquire(at(base, 10_000), 3.0));
+    }
+}
+
+/// Allows at most `limit` acquisitions in any window of length `window`.
+///
+/// Keeps the timestamps of the last `limit` successful acquisitions in a
+/// `RingBuffer` that rejects when full; a new acquisition succeeds once the
+/// oldest of them has left the window. Exact, using O(limit) memory.
+#[derive(Debug)]
+pub struct SlidingWindowLimiter {
+    hits: RingBuffer<Instant>,
+    window: Duration,
+}
+
+impl SlidingWindowLimiter {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        SlidingWindowLimiter { hits: RingBuffer::with_policy(limit, OverflowPolicy::Reject), window }
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some(&oldest) = self.hits.peek() {
+            if now.duration_since(oldest) < self.window {
+                break;
+            }
+            self.hits.pop();
+        }
+    }
+
+    /// Records an acquisition at `now` if the limit allows it.
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        self.expire(now);
+        self.hits.push(now).is_none()
+    }
+
+    /// Returns how many acquisitions would succeed at `now`.
+    pub fn remaining(&mut self, now: Instant) -> usize {
+        self.expire(now);
+        self.hits.capacity() - self.hits.len()
+    }
+
+    /// Returns how long after `now` the next acquisition can succeed, or
+    /// `None` if one can succeed now or never will (a zero limit).
+    pub fn retry_after(&mut self, now: Instant) -> Option<Duration> {
+        self.expire(now);
+        if !self.hits.is_full() {
+            return None;
+        }
+        let oldest = *self.hits.peek()?;
+        Some(self.window - now.duration_since(oldest))
+    }
+}
+
+/// A token bucket: holds up to `capacity` tokens, refilled continuously at
+/// `refill_per_second`, and each acquisition spends tokens. Allows bursts of
+/// up to `capacity` while capping the long-run rate.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    pub fn new(capacity: f64, refill_per_second: f64, now: Instant) -> Self {
+        TokenBucket { capacity, refill_per_second, tokens: capacity, last_refill: now }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        // Earlier times than the last refill add nothing
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = self.last_refill.max(now);
+    }
+
+    /// Returns the tokens available at `now`.
+    pub fn available(&mut self, now: Instant) -> f64 {
+        self.refill(now);
+        self.tokens
+    }
+
+    /// Spends `tokens` at `now` if that many are available.
+    pub fn try_acquire(&mut self, now: Instant, tokens: f64) -> bool {
+        self.refill(now);
+        if tokens > self.tokens {
+            return false;
+        }
+        self.tokens -= tokens;
+        true
+    }
+}
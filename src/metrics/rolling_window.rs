@@ -0,0 +1,357 @@
+use crate::data_structures::monotonic_queue::{MaxQueue, MinQueue};
+use crate::data_structures::ring_buffer::{OverflowPolicy, RingBuffer};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(base: Instant, millis: u64) -> Instant {
+        base + Duration::from_millis(millis)
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("window is not empty");
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_sample_window_aggregates() {
+        let base = Instant::now();
+        let mut window = RollingWindow::new(Window::Samples(3));
+        assert_eq!(window.mean(), None);
+        for (i, value) in [2.0, 4.0, 4.0, 6.0].into_iter().enumerate() {
+            window.push(at(base, i as u64), value);
+        }
+        // 2.0 has been evicted
+        assert_eq!(window.len(), 3);
+        assert_close(Some(window.sum()), 14.0);
+        assert_close(window.mean(), 14.0 / 3.0);
+        assert_close(window.variance(), 8.0 / 9.0);
+        assert_close(window.sample_variance(), 4.0 / 3.0);
+        assert_eq!(window.min(), Some(4.0));
+        assert_eq!(window.max(), Some(6.0));
+    }
+
+    #[test]
+    fn test_duration_window_expires_samples() {
+        let base = Instant::now();
+        let mut window = RollingWindow::new(Window::Duration(Duration::from_secs(1)));
+        window.push(at(base, 0), 10.0);
+        window.push(at(base, 400), 1.0);
+        window.push(at(base, 900), 5.0);
+        assert_eq!(window.max(), Some(10.0));
+        window.advance(at(base, 1000));
+        assert_eq!(window.len(), 2);
+        assert_eq!(window.max(), Some(5.0));
+        assert_eq!(window.min(), Some(1.0));
+        window.advance(at(base, 1400));
+        assert_eq!(window.min(), Some(5.0));
+        window.advance(at(base, 5000));
+        assert!(window.is_empty());
+        assert_eq!(window.variance(), None);
+        assert_eq!(window.sum(), 0.0);
+    }
+
+    #[test]
+    fn test_duration_window_grows_past_initial_capacity() {
+        let base = Instant::now();
+        let mut window = RollingWindow::new(Window::Duration(Duration::from_secs(60)));
+        for i in 0..1000 {
+            window.push(at(base, i), i as f64);
+        }
+        assert_eq!(window.len(), 1000);
+        assert_close(window.mean(), 499.5);
+    }
+
+    #[test]
+    fn test_percentiles_are_approximate() {
+        let base = Instant::now();
+        let mut window = RollingWindow::new(Window::Samples(1000));
+        for i in 1..=1000 {
+            window.push(at(base, i), i as f64);
+        }
+        for (p, exact) in [(50.0, 500.0), (90.0, 900.0), (99.0, 990.0)] {
+            let estimate = window.percentile(p).unwrap();
+            assert!((estimate - exact).abs() / exact < 0.01, "p{} = {}", p, estimate);
+        }
+        assert_eq!(window.percentile(0.0), Some(1.0));
+        assert_eq!(window.percentile(100.0), Some(1000.0));
+        let mut negative = RollingWindow::new(Window::Samples(3));
+        for value in [-3.0, -1.0, -2.0] {
+            negative.push(base, value);
+        }
+        let median = negative.percentile(50.0).unwrap();
+        assert!((median + 2.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_zero_sample_window_stays_empty() {
+        let base = Instant::now();
+        let mut window = RollingWindow::new(Window::Samples(0));
+        window.push(base, 1.0);
+        window.push(at(base, 1), 2.0);
+        assert_eq!((window.len(), window.mean(), window.max()), (0, None, None));
+        assert_eq!(window.percentile(50.0), None);
+    }
+
+    #[test]
+    fn test_nan_is_ignored() {
+        let base = Instant::now();
+        let mut window = RollingWindow::new(Window::Samples(2));
+        window.push(base, f64::NAN);
+        window.push(base, 1.0);
+        assert_eq!(window.len(), 1);
+        assert_eq!(window.max(), Some(1.0));
+    }
+}
+
+/// Which samples a `RollingWindow` aggregates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    /// The most recent N samples.
+    Samples(usize),
+    /// Samples with a timestamp within the duration before the latest
+    /// `push` or `advance` time.
+    Duration(Duration),
+}
+
+/// An `f64` ordered with `total_cmp`, so it can sit in the monotonic queues.
+#[derive(Debug, Clone, Copy)]
+struct Total(f64);
+
+impl PartialEq for Total {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Total {}
+
+impl PartialOrd for Total {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Total {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// Mantissa bits kept per histogram bucket; relative error is below 2^-7
+const PRECISION: u32 = 7;
+const SHIFT: u32 = 52 - PRECISION;
+
+/// Maps a float to an integer that sorts in the same order.
+fn ordered_bits(value: f64) -> i64 {
+    let bits = value.to_bits() as i64;
+    bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+fn from_ordered_bits(bits: i64) -> f64 {
+    f64::from_bits((bits ^ (((bits >> 63) as u64) >> 1) as i64) as u64)
+}
+
+fn bucket_of(value: f64) -> i64 {
+    ordered_bits(value) >> SHIFT
+}
+
+/// Returns the midpoint of the values falling in `bucket`.
+fn bucket_value(bucket: i64) -> f64 {
+    let low = from_ordered_bits(bucket << SHIFT);
+    let high = from_ordered_bits(((bucket + 1) << SHIFT) - 1);
+    low + (high - low) / 2.0
+}
+
+/// Running aggregates over a window of timestamped samples.
+///
+/// Samples live in a `RingBuffer`; every aggregate is updated as samples
+/// enter and leave it. Sum, mean and variance (Welford's method, with the
+/// matching removal step) are O(1). Min and max are O(1) amortised through
+/// monotonic queues. Percentiles come from a log-linear histogram with
+/// `2^7` buckets per power of two, so an estimate is within about 1% of a
+/// value present in the window.
+///
+/// Timestamps are expected in non-decreasing order; an earlier timestamp is
+/// treated as equal to the newest one seen. NaN samples are ignored.
+#[derive(Debug)]
+pub struct RollingWindow {
+    window: Window,
+    samples: RingBuffer<(Instant, f64)>,
+    latest: Option<Instant>,
+    // Samples the running aggregates currently cover
+    count: usize,
+    sum: f64,
+    mean: f64,
+    // Sum of squared deviations from the mean
+    m2: f64,
+    minimum: MinQueue<Total>,
+    maximum: MaxQueue<Total>,
+    histogram: BTreeMap<i64, usize>,
+}
+
+impl RollingWindow {
+    pub fn new(window: Window) -> Self {
+        let samples = match window {
+            Window::Samples(n) => RingBuffer::with_policy(n, OverflowPolicy::Overwrite),
+            Window::Duration(_) => RingBuffer::with_policy(16, OverflowPolicy::Reject),
+        };
+        RollingWindow {
+            window,
+            samples,
+            latest: None,
+            count: 0,
+            sum: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            minimum: MinQueue::new(),
+            maximum: MaxQueue::new(),
+            histogram: BTreeMap::new(),
+        }
+    }
+
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Adds a sample, evicting whatever falls out of the window.
+    pub fn push(&mut self, timestamp: Instant, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        let timestamp = self.latest.map_or(timestamp, |latest| latest.max(timestamp));
+        self.advance(timestamp);
+        match self.window {
+            // Holds nothing; the ring would hand the sample straight back
+            Window::Samples(0) => return,
+            Window::Samples(_) => {}
+            Window::Duration(_) => {
+                if self.samples.is_full() {
+                    self.samples.reserve(self.samples.capacity().max(1));
+                }
+            }
+        }
+        if let Some((_, evicted)) = self.samples.push((timestamp, value)) {
+            self.remove(evicted);
+        }
+        self.add(value);
+    }
+
+    /// Moves the window's end to `now`, expiring samples of a duration window
+    /// that are `duration` or more older than `now`.
+    pub fn advance(&mut self, now: Instant) {
+        let now = self.latest.map_or(now, |latest| latest.max(now));
+        self.latest = Some(now);
+        let Window::Duration(duration) = self.window else {
+            return;
+        };
+        let Some(cutoff) = now.checked_sub(duration) else {
+            return;
+        };
+        while let Some(&(timestamp, value)) = self.samples.peek() {
+            if timestamp > cutoff {
+                break;
+            }
+            self.samples.pop();
+            self.remove(value);
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        let n = self.count as f64;
+        self.sum += value;
+        let delta = value - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (value - self.mean);
+        self.minimum.push(Total(value));
+        self.maximum.push(Total(value));
+        *self.histogram.entry(bucket_of(value)).or_default() += 1;
+    }
+
+    /// Undoes `add` for the oldest sample, which has just left the buffer.
+    fn remove(&mut self, value: f64) {
+        self.count -= 1;
+        let n = self.count as f64;
+        self.minimum.pop_front();
+        self.maximum.pop_front();
+        let bucket = bucket_of(value);
+        if let Some(count) = self.histogram.get_mut(&bucket) {
+            *count -= 1;
+            if *count == 0 {
+                self.histogram.remove(&bucket);
+            }
+        }
+        if n == 0.0 {
+            (self.sum, self.mean, self.m2) = (0.0, 0.0, 0.0);
+            return;
+        }
+        self.sum -= value;
+        let delta = value - self.mean;
+        self.mean -= delta / n;
+        self.m2 = (self.m2 - delta * (value - self.mean)).max(0.0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then_some(self.mean)
+    }
+
+    /// Returns the population variance of the window.
+    pub fn variance(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.m2 / self.len() as f64)
+    }
+
+    /// Returns the sample (Bessel-corrected) variance; needs two samples.
+    pub fn sample_variance(&self) -> Option<f64> {
+        (self.len() > 1).then(|| self.m2 / (self.len() - 1) as f64)
+    }
+
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.minimum.extreme().map(|value| value.0)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.maximum.extreme().map(|value| value.0)
+    }
+
+    /// Estimates the `p`th percentile (nearest rank, `p` in 0..=100) from the
+    /// histogram, clamped to the exact min and max. The lowest and highest
+    /// ranks return the exact min and max.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        let (min, max) = (self.min()?, self.max()?);
+        let rank = ((p.clamp(0.0, 100.0) / 100.0 * self.len() as f64).ceil() as usize).max(1);
+        if rank == 1 {
+            return Some(min);
+        }
+        if rank == self.len() {
+            return Some(max);
+        }
+        let mut seen = 0;
+        for (&bucket, &count) in &self.histogram {
+            seen += count;
+            if seen >= rank {
+                return Some(bucket_value(bucket).clamp(min, max));
+            }
+        }
+        Some(max)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::data_structures::stack::Stack;
+use std::ops::Range;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_greater_element() {
+        assert_eq!(next_greater_element(&[2, 1, 2, 4, 3]), vec![Some(3), Some(2), Some(3), None, None]);
+        assert_eq!(next_greater_element(&[5, 5, 5]), vec![None, None, None]);
+        assert!(next_greater_element::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_next_smaller_element() {
+        assert_eq!(next_smaller_element(&[4, 5, 2, 10, 8]), vec![Some(2), Some(2), None, Some(4), None]);
+    }
+
+    #[test]
+    fn test_largest_rectangle() {
+        let rectangle = largest_rectangle(&[2, 1, 5, 6, 2, 3]);
+        assert_eq!(rectangle, Some(Rectangle { area: 10, height: 5, range: 2..4 }));
+        let rectangle = largest_rectangle(&[3, 3, 3]).unwrap();
+        assert_eq!((rectangle.area, rectangle.range), (9, 0..3));
+        assert_eq!(largest_rectangle(&[]), None);
+        assert_eq!(largest_rectangle(&[0, 0]).map(|r| r.area), Some(0));
+    }
+}
+
+/// For each element, returns the index of the first later element that is
+/// strictly greater, or `None`. O(n).
+pub fn next_greater_element<T: Ord>(values: &[T]) -> Vec<Option<usize>> {
+    next_by(values, |next, current| next > current)
+}
+
+/// For each element, returns the index of the first later element that is
+/// strictly smaller, or `None`. O(n).
+pub fn next_smaller_element<T: Ord>(values: &[T]) -> Vec<Option<usize>> {
+    next_by(values, |next, current| next < current)
+}
+
+/// Keeps a stack of indices still waiting for an answer; `beats(next, current)`
+/// decides whether `next` answers `current`. The waiting values are monotonic,
+/// so each index is pushed and popped once.
+fn next_by<T>(values: &[T], beats: impl Fn(&T, &T) -> bool) -> Vec<Option<usize>> {
+    let mut answer = vec![None; values.len()];
+    let mut waiting: Stack<usize> = Stack::new();
+    for (i, value) in values.iter().enumerate() {
+        while let Some(&top) = waiting.peek() {
+            if !beats(value, &values[top]) {
+                break;
+            }
+            answer[top] = Some(i);
+            waiting.pop();
+        }
+        waiting.push(i);
+    }
+    answer
+}
+
+/// The largest rectangle under a histogram: its area, height and the bars it spans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rectangle {
+    pub area: u64,
+    pub height: u64,
+    pub range: Range<usize>,
+}
+
+/// Finds the largest axis-aligned rectangle under a histogram of unit-width
+/// bars, or `None` for an empty histogram. O(n).
+///
+/// A stack holds bars of increasing height; when a lower bar arrives, each
+/// taller bar popped off can extend no further right, and the bar below it on
+/// the stack bounds it on the left.
+pub fn largest_rectangle(heights: &[u64]) -> Option<Rectangle> {
+    let mut best: Option<Rectangle> = None;
+    let mut rising: Stack<usize> = Stack::new();
+    for i in 0..=heights.len() {
+        // A zero-height sentinel past the end flushes the stack
+        let height = heights.get(i).copied().unwrap_or(0);
+        while let Some(&top) = rising.peek() {
+            if heights[top] < height {
+                break;
+            }
+            rising.pop();
+            let start = rising.peek().map_or(0, |&below| below + 1);
+            let area = heights[top] * (i - start) as u64;
+            if best.as_ref().is_none_or(|best| area > best.area) {
+                best = Some(Rectangle { area, height: heights[top], range: start..i });
+            }
+        }
+        rising.push(i);
+    }
+    best
+}
@@ -0,0 +1,84 @@
+use std::fmt::{Debug, Display};
+
+/// Numeric operations shared by the generic algorithms and data structures.
+///
+/// Implemented for every primitive integer and floating point type. The
+/// `checked_*` methods return `None` where the result cannot be represented
+/// (integer overflow/underflow, or a non-finite float result), the
+/// `saturating_*` methods clamp to the representable range instead.
+pub trait Numeric: Copy + PartialOrd + Debug + Display {
+    /// The additive identity.
+    fn zero() -> Self;
+    /// The multiplicative identity.
+    fn one() -> Self;
+    /// The smallest representable value.
+    fn min_value() -> Self;
+    /// The largest representable value.
+    fn max_value() -> Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
+/// Marker for numeric types that can hold negative values.
+pub trait Signed: Numeric {}
+
+macro_rules! impl_numeric_int {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn zero() -> Self { 0 }
+                fn one() -> Self { 1 }
+                fn min_value() -> Self { <$t>::MIN }
+                fn max_value() -> Self { <$t>::MAX }
+                fn checked_add(self, rhs: Self) -> Option<Self> { <$t>::checked_add(self, rhs) }
+                fn checked_sub(self, rhs: Self) -> Option<Self> { <$t>::checked_sub(self, rhs) }
+                fn checked_mul(self, rhs: Self) -> Option<Self> { <$t>::checked_mul(self, rhs) }
+                fn saturating_add(self, rhs: Self) -> Self { <$t>::saturating_add(self, rhs) }
+                fn saturating_sub(self, rhs: Self) -> Self { <$t>::saturating_sub(self, rhs) }
+                fn saturating_mul(self, rhs: Self) -> Self { <$t>::saturating_mul(self, rhs) }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_numeric_float {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn zero() -> Self { 0.0 }
+                fn one() -> Self { 1.0 }
+                fn min_value() -> Self { <$t>::MIN }
+                fn max_value() -> Self { <$t>::MAX }
+                fn checked_add(self, rhs: Self) -> Option<Self> { finite(self + rhs) }
+                fn checked_sub(self, rhs: Self) -> Option<Self> { finite(self - rhs) }
+                fn checked_mul(self, rhs: Self) -> Option<Self> { finite(self * rhs) }
+                fn saturating_add(self, rhs: Self) -> Self { (self + rhs).clamp(<$t>::MIN, <$t>::MAX) }
+                fn saturating_sub(self, rhs: Self) -> Self { (self - rhs).clamp(<$t>::MIN, <$t>::MAX) }
+                fn saturating_mul(self, rhs: Self) -> Self { (self * rhs).clamp(<$t>::MIN, <$t>::MAX) }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $( impl Signed for $t {} )*
+    };
+}
+
+impl_numeric_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_numeric_float!(f32, f64);
+impl_signed!(i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Returns `Some(value)` if the float result is finite, `None` for infinities and NaN.
+fn finite<T: Into<f64> + Copy>(value: T) -> Option<T> {
+    if value.into().is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
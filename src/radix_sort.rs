@@ -1,12 +1,12 @@
 use crate::data_structures::queue::Queue;
-/// Represents a bucket containing a value and a queue of unsigned 64-bit integers.
+/// Represents a bucket containing a value and a queue of items.
 #[derive(Debug)]
-struct Bucket {
+struct Bucket<T> {
     value: u8,
-    queue: Queue<u64>,
+    queue: Queue<T>,
 }
 
-impl Bucket {
+impl<T> Bucket<T> {
     /// Creates a new Bucket instance with initial value 0 and an empty queue.
     fn new() -> Self {
         Bucket {
@@ -18,11 +18,11 @@ impl Bucket {
 
 /// Represents a collection of buckets.
 #[derive(Debug)]
-struct Buckets {
-    buckets: Vec<Bucket>,
+struct Buckets<T> {
+    buckets: Vec<Bucket<T>>,
 }
 
-impl Buckets {
+impl<T> Buckets<T> {
     /// Creates a new Buckets instance with 10 empty buckets.
     fn new() -> Self {
         let mut buckets = Vec::with_capacity(10);
@@ -36,7 +36,7 @@ impl Buckets {
     ///
     /// # Panics
     /// Panics if the specified bucket index is out of range.
-    fn enqueue(&mut self, bucket_index: usize, item: u64) {
+    fn enqueue(&mut self, bucket_index: usize, item: T) {
         if let Some(bucket) = self.buckets.get_mut(bucket_index) {
             bucket.queue.enqueue(item);
         } else {
@@ -48,7 +48,7 @@ impl Buckets {
     ///
     /// # Panics
     /// Panics if the specified bucket index is out of range.
-    fn dequeue(&mut self, bucket_index: usize) -> Option<u64> {
+    fn dequeue(&mut self, bucket_index: usize) -> Option<T> {
         if let Some(bucket) = self.buckets.get_mut(bucket_index) {
             bucket.queue.dequeue()
         } else {
@@ -72,7 +72,7 @@ impl Buckets {
     ///
     /// # Panics
     /// Panics if the specified bucket index is out of range.
-    fn peek(&self, bucket_index: usize) -> Option<&u64> {
+    fn peek(&self, bucket_index: usize) -> Option<&T> {
         if let Some(bucket) = self.buckets.get(bucket_index) {
             bucket.queue.peek()
         } else {
@@ -115,6 +115,36 @@ pub fn radix_sort(arr: Vec<u64>) -> Vec<u64>{
     return sort_vec;
 }
 
+/// Stably sorts items by an unsigned key using the same decimal radix sort.
+///
+/// Items with equal keys keep their relative order, so sorting by a minor
+/// key and then by a major key orders items by the pair.
+pub fn radix_sort_by_key<T, F>(items: Vec<T>, key: F) -> Vec<T>
+where
+    F: Fn(&T) -> u64,
+{
+    let places = items.iter().map(&key).max().map_or(0, |largest| {
+        let mut places = 1;
+        while places < 20 && largest / 10u64.pow(places) > 0 {
+            places += 1;
+        }
+        places
+    });
+    let mut buckets = Buckets::new();
+    let mut sort_vec = items;
+    for place in 0..places {
+        for item in sort_vec.drain(..) {
+            let digit = get_value_at_unit(key(&item), place as u64);
+            buckets.enqueue(digit as usize, item);
+        }
+        for j in 0..buckets.buckets.len() {
+            while let Some(item) = buckets.dequeue(j) {
+                sort_vec.push(item);
+            }
+        }
+    }
+    sort_vec
+}
 
 /// Retrieves the digit at the specified place from a given number.
 fn get_value_at_unit(num: u64, place: u64) -> u64 {
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = XorShift64::new(42);
+        let mut b = XorShift64::new(42);
+        let first: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        assert!(first.iter().all(|&x| x == b.next_u64()));
+        assert_ne!(first[0], XorShift64::new(43).next_u64());
+    }
+
+    #[test]
+    fn test_zero_seed_is_usable() {
+        let mut rng = XorShift64::new(0);
+        assert_ne!(rng.next_u64(), 0);
+    }
+
+    #[test]
+    fn test_below_stays_in_range() {
+        let mut rng = XorShift64::new(7);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            seen[rng.below(10) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert!((0..100).map(|_| rng.next_f64()).all(|x| (0.0..1.0).contains(&x)));
+    }
+}
+
+/// Marsaglia's xorshift64* generator: small, fast and reproducible from a
+/// seed. Good enough for randomized data structures and tests; not for
+/// anything that needs unpredictability.
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    /// Creates a generator from `seed`. The all-zero state is a fixed point
+    /// of xorshift, so a zero seed is replaced by a fixed odd constant.
+    pub fn new(seed: u64) -> Self {
+        XorShift64 { state: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed } }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a value in `0..bound` with negligible bias for small bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// Returns a value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
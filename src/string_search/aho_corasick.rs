@@ -0,0 +1,198 @@
+use crate::data_structures::queue::Queue;
+use crate::string_search::MatchMode;
+use std::collections::HashMap;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(automaton: &AhoCorasick, haystack: &str, mode: MatchMode) -> Vec<(usize, usize, usize)> {
+        automaton.find_iter(haystack, mode).map(|m| (m.pattern, m.start, m.end)).collect()
+    }
+
+    #[test]
+    fn test_aho_corasick_overlapping() {
+        let automaton = AhoCorasick::new(["he", "she", "his", "hers"]);
+        assert_eq!(
+            found(&automaton, "ushers", MatchMode::Overlapping),
+            vec![(1, 1, 4), (0, 2, 4), (3, 2, 6)]
+        );
+        assert_eq!(automaton.pattern_count(), 4);
+    }
+
+    #[test]
+    fn test_aho_corasick_non_overlapping() {
+        let automaton = AhoCorasick::new(["he", "she", "his", "hers"]);
+        assert_eq!(found(&automaton, "ushers", MatchMode::NonOverlapping), vec![(1, 1, 4)]);
+        let automaton = AhoCorasick::new(["ERROR", "WARN", "panic"]);
+        let log = "WARN low disk\nERROR write failed\nthread panicked\nERROR retry\n";
+        let kinds: Vec<usize> = automaton.find_iter(log, MatchMode::NonOverlapping).map(|m| m.pattern).collect();
+        assert_eq!(kinds, vec![1, 0, 2, 0]);
+    }
+
+    #[test]
+    fn test_aho_corasick_empty_pattern() {
+        let automaton = AhoCorasick::new(["", "a"]);
+        assert_eq!(
+            found(&automaton, "aa", MatchMode::Overlapping),
+            vec![(0, 0, 0), (1, 0, 1), (0, 1, 1), (1, 1, 2), (0, 2, 2)]
+        );
+        assert_eq!(found(&automaton, "aa", MatchMode::NonOverlapping), vec![(0, 0, 0), (1, 0, 1), (1, 1, 2)]);
+        assert_eq!(found(&automaton, "", MatchMode::NonOverlapping), vec![(0, 0, 0)]);
+        assert!(automaton.is_match("xyz"));
+    }
+
+    #[test]
+    fn test_aho_corasick_edge_cases() {
+        let automaton = AhoCorasick::new(["b", "a"]);
+        assert!(automaton.is_match("xa"));
+        assert!(!automaton.is_match("xyz"));
+        let none: [&str; 0] = [];
+        assert_eq!(AhoCorasick::new(none).find_iter("abc", MatchMode::Overlapping).count(), 0);
+        // Duplicate patterns are reported under each id
+        let automaton = AhoCorasick::new(["ab", "ab"]);
+        assert_eq!(found(&automaton, "ab", MatchMode::Overlapping), vec![(0, 0, 2), (1, 0, 2)]);
+    }
+}
+
+/// A match of pattern number `pattern` at `haystack[start..end]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+struct Node {
+    children: HashMap<u8, usize>,
+    fail: usize,
+    // Patterns ending at this node, longest first, including those reached
+    // through failure links
+    outputs: Vec<usize>,
+}
+
+impl Node {
+    fn new() -> Self {
+        Node { children: HashMap::new(), fail: 0, outputs: Vec::new() }
+    }
+}
+
+/// An Aho-Corasick automaton that finds many patterns in one pass over the
+/// haystack, O(n + total pattern length + matches).
+///
+/// Matches are reported in order of their end offset, longest first among
+/// matches ending at the same offset. In `NonOverlapping` mode the automaton
+/// reports the longest pattern ending at the first offset where any pattern
+/// ends, then restarts after it. An empty pattern matches at every offset,
+/// as with the single-pattern searchers, except that `NonOverlapping` mode
+/// skips it at the offset where the previous match ended.
+pub struct AhoCorasick {
+    nodes: Vec<Node>,
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let mut nodes = vec![Node::new()];
+        let mut lengths = Vec::new();
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            lengths.push(pattern.len());
+            let mut node = 0;
+            for &b in pattern {
+                node = match nodes[node].children.get(&b) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push(Node::new());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(b, child);
+                        child
+                    }
+                };
+            }
+            nodes[node].outputs.push(id);
+        }
+
+        // Breadth-first, so a node's failure target is finished before the node
+        let mut queue = Queue::new();
+        let roots: Vec<usize> = nodes[0].children.values().copied().collect();
+        for child in roots {
+            // Depth-one nodes fail to the root, so they also end its empty patterns
+            let inherited = nodes[0].outputs.clone();
+            nodes[child].outputs.extend(inherited);
+            queue.enqueue(child);
+        }
+        while let Some(node) = queue.dequeue() {
+            let children: Vec<(u8, usize)> = nodes[node].children.iter().map(|(&b, &c)| (b, c)).collect();
+            for (b, child) in children {
+                let mut fail = nodes[node].fail;
+                while fail != 0 && !nodes[fail].children.contains_key(&b) {
+                    fail = nodes[fail].fail;
+                }
+                let target = nodes[fail].children.get(&b).copied().unwrap_or(0);
+                nodes[child].fail = if target == child { 0 } else { target };
+                let inherited = nodes[nodes[child].fail].outputs.clone();
+                nodes[child].outputs.extend(inherited);
+                queue.enqueue(child);
+            }
+        }
+        AhoCorasick { nodes, lengths }
+    }
+
+    pub fn pattern_count(&self) -> usize {
+        self.lengths.len()
+    }
+
+    fn step(&self, mut node: usize, b: u8) -> usize {
+        loop {
+            if let Some(&child) = self.nodes[node].children.get(&b) {
+                return child;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    /// Iterates over the matches in `haystack`.
+    pub fn find_iter<'a, H>(&'a self, haystack: &'a H, mode: MatchMode) -> impl Iterator<Item = Match> + 'a
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        let haystack = haystack.as_ref();
+        let mut node = 0;
+        let mut pos = 0;
+        // Index of the next output of `node` to report
+        let mut pending = 0;
+        std::iter::from_fn(move || loop {
+            let outputs = &self.nodes[node].outputs;
+            if pending < outputs.len() {
+                let pattern = outputs[pending];
+                let found = Match { pattern, start: pos - self.lengths[pattern], end: pos };
+                match mode {
+                    MatchMode::Overlapping => pending += 1,
+                    MatchMode::NonOverlapping => {
+                        // Restart at the root, past its empty patterns so
+                        // they are not reported again at this offset
+                        node = 0;
+                        pending = self.nodes[0].outputs.len();
+                    }
+                }
+                return Some(found);
+            }
+            let &b = haystack.get(pos)?;
+            node = self.step(node, b);
+            pos += 1;
+            pending = 0;
+        })
+    }
+
+    /// Returns whether any pattern occurs in `haystack`.
+    pub fn is_match<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> bool {
+        self.find_iter(haystack, MatchMode::NonOverlapping).next().is_some()
+    }
+}
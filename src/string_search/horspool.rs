@@ -0,0 +1,74 @@
+use crate::string_search::{with_mode, MatchMode};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_horspool_find_iter() {
+        let horspool = Horspool::new("needle");
+        let text = "haystack needle haystack needleneedle";
+        assert_eq!(horspool.find_iter(text, MatchMode::Overlapping).collect::<Vec<_>>(), vec![9, 25, 31]);
+        assert_eq!(horspool.find("no match here"), None);
+    }
+
+    #[test]
+    fn test_horspool_modes() {
+        let horspool = Horspool::new(b"aa");
+        assert_eq!(horspool.find_iter(b"aaaa", MatchMode::Overlapping).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(horspool.find_iter(b"aaaa", MatchMode::NonOverlapping).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(horspool.find_iter(b"a", MatchMode::Overlapping).count(), 0);
+    }
+}
+
+/// Boyer-Moore-Horspool search.
+///
+/// Compares the window right to left and shifts it by the bad-character
+/// distance of its last byte, which skips most of the haystack for long
+/// patterns over a large alphabet. O(n·m) worst case.
+pub struct Horspool {
+    pattern: Vec<u8>,
+    shift: [usize; 256],
+}
+
+impl Horspool {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+        let m = pattern.len();
+        let mut shift = [m.max(1); 256];
+        for (i, &b) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            shift[b as usize] = m - 1 - i;
+        }
+        Horspool { pattern, shift }
+    }
+
+    /// Iterates over the start offsets of matches in `haystack`.
+    pub fn find_iter<'a, H>(&'a self, haystack: &'a H, mode: MatchMode) -> impl Iterator<Item = usize> + 'a
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        let haystack = haystack.as_ref();
+        let m = self.pattern.len();
+        let mut pos = 0;
+        let overlapping = std::iter::from_fn(move || {
+            if m == 0 {
+                pos += 1;
+                return (pos <= haystack.len() + 1).then_some(pos - 1);
+            }
+            while pos + m <= haystack.len() {
+                let window = &haystack[pos..pos + m];
+                let start = pos;
+                pos += self.shift[window[m - 1] as usize];
+                if window.iter().rev().eq(self.pattern.iter().rev()) {
+                    return Some(start);
+                }
+            }
+            None
+        });
+        with_mode(overlapping, m, mode)
+    }
+
+    /// Returns the start offset of the first match.
+    pub fn find<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Option<usize> {
+        self.find_iter(haystack, MatchMode::Overlapping).next()
+    }
+}
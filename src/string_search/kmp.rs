@@ -0,0 +1,103 @@
+use crate::string_search::{with_mode, MatchMode};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_function() {
+        assert_eq!(prefix_function(b"abcabcd"), vec![0, 0, 0, 1, 2, 3, 0]);
+        assert_eq!(prefix_function(b"aabaaab"), vec![0, 1, 0, 1, 2, 2, 3]);
+        assert_eq!(prefix_function(&[1, 1, 1]), vec![0, 1, 2]);
+        assert!(prefix_function::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_kmp_find_iter() {
+        let kmp = Kmp::new("ERROR");
+        let log = "INFO ok\nERROR disk full\nWARN slow\nERROR timeout\n";
+        assert_eq!(kmp.find_iter(log, MatchMode::Overlapping).collect::<Vec<_>>(), vec![8, 34]);
+        assert_eq!(kmp.find(log), Some(8));
+        assert_eq!(kmp.find("all good"), None);
+    }
+
+    #[test]
+    fn test_kmp_modes() {
+        let kmp = Kmp::new(b"aba");
+        assert_eq!(kmp.find_iter(b"ababababa", MatchMode::Overlapping).collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+        assert_eq!(kmp.find_iter(b"ababababa", MatchMode::NonOverlapping).collect::<Vec<_>>(), vec![0, 4]);
+    }
+}
+
+/// Computes the prefix function of `s`: `pi[i]` is the length of the longest
+/// proper prefix of `s[..=i]` that is also a suffix of it. O(n).
+pub fn prefix_function<T: Eq>(s: &[T]) -> Vec<usize> {
+    let mut pi = vec![0; s.len()];
+    for i in 1..s.len() {
+        let mut k = pi[i - 1];
+        while k > 0 && s[i] != s[k] {
+            k = pi[k - 1];
+        }
+        if s[i] == s[k] {
+            k += 1;
+        }
+        pi[i] = k;
+    }
+    pi
+}
+
+/// Knuth-Morris-Pratt search, O(n + m) with no backtracking in the haystack.
+pub struct Kmp {
+    pattern: Vec<u8>,
+    prefix: Vec<usize>,
+}
+
+impl Kmp {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+        let prefix = prefix_function(&pattern);
+        Kmp { pattern, prefix }
+    }
+
+    /// Returns the prefix function of the pattern.
+    pub fn prefix(&self) -> &[usize] {
+        &self.prefix
+    }
+
+    /// Iterates over the start offsets of matches in `haystack`.
+    pub fn find_iter<'a, H>(&'a self, haystack: &'a H, mode: MatchMode) -> impl Iterator<Item = usize> + 'a
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        let haystack = haystack.as_ref();
+        let m = self.pattern.len();
+        let mut i = 0;
+        let mut k = 0;
+        let overlapping = std::iter::from_fn(move || {
+            if m == 0 {
+                i += 1;
+                return (i <= haystack.len() + 1).then_some(i - 1);
+            }
+            while i < haystack.len() {
+                let c = haystack[i];
+                i += 1;
+                while k > 0 && c != self.pattern[k] {
+                    k = self.prefix[k - 1];
+                }
+                if c == self.pattern[k] {
+                    k += 1;
+                }
+                if k == m {
+                    k = self.prefix[m - 1];
+                    return Some(i - m);
+                }
+            }
+            None
+        });
+        with_mode(overlapping, m, mode)
+    }
+
+    /// Returns the start offset of the first match.
+    pub fn find<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Option<usize> {
+        self.find_iter(haystack, MatchMode::Overlapping).next()
+    }
+}
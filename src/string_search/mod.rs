@@ -0,0 +1,100 @@
+//! Exact pattern search over bytes.
+//!
+//! Each single-pattern searcher is built once from its pattern and then
+//! reports match start offsets through `find_iter`, which takes anything that
+//! is `AsRef<[u8]>` (`&str`, `&[u8]`, `Vec<u8>`, ...). Offsets are in bytes.
+//! `aho_corasick` searches for many patterns in a single pass.
+//!
+//! An empty pattern matches at every offset `0..=haystack.len()`, both for
+//! the single-pattern searchers and as one of `aho_corasick`'s patterns.
+
+pub mod aho_corasick;
+pub mod horspool;
+pub mod kmp;
+pub mod rabin_karp;
+pub mod z_algorithm;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(haystack: &[u8], pattern: &[u8], mode: MatchMode) -> Vec<usize> {
+        let overlapping = (0..=haystack.len().saturating_sub(pattern.len()))
+            .filter(|&i| haystack.len() >= pattern.len() && &haystack[i..i + pattern.len()] == pattern);
+        with_mode(overlapping, pattern.len(), mode).collect()
+    }
+
+    #[test]
+    fn test_searchers_agree_with_naive_search() {
+        let mut state: u32 = 0x9e37_79b9;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        for _ in 0..300 {
+            // A tiny alphabet makes repeated and overlapping matches likely
+            let haystack: Vec<u8> = (0..next() % 40).map(|_| b'a' + (next() % 3) as u8).collect();
+            let pattern: Vec<u8> = (0..next() % 5).map(|_| b'a' + (next() % 3) as u8).collect();
+            for mode in [MatchMode::Overlapping, MatchMode::NonOverlapping] {
+                let expected = naive(&haystack, &pattern, mode);
+                let kmp = kmp::Kmp::new(&pattern);
+                assert_eq!(kmp.find_iter(&haystack, mode).collect::<Vec<_>>(), expected);
+                let horspool = horspool::Horspool::new(&pattern);
+                assert_eq!(horspool.find_iter(&haystack, mode).collect::<Vec<_>>(), expected);
+                let rabin_karp = rabin_karp::RabinKarp::new(&pattern);
+                assert_eq!(rabin_karp.find_iter(&haystack, mode).collect::<Vec<_>>(), expected);
+                let z = z_algorithm::ZSearch::new(&pattern);
+                assert_eq!(z.find_iter(&haystack, mode).collect::<Vec<_>>(), expected);
+                let automaton = aho_corasick::AhoCorasick::new([&pattern]);
+                let starts: Vec<usize> = automaton.find_iter(&haystack, mode).map(|m| m.start).collect();
+                assert_eq!(starts, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_pattern_matches_at_every_offset() {
+        for haystack in ["", "a", "abc"] {
+            let expected: Vec<usize> = (0..=haystack.len()).collect();
+            for mode in [MatchMode::Overlapping, MatchMode::NonOverlapping] {
+                assert_eq!(kmp::Kmp::new(b"").find_iter(haystack, mode).collect::<Vec<_>>(), expected);
+                assert_eq!(horspool::Horspool::new(b"").find_iter(haystack, mode).collect::<Vec<_>>(), expected);
+                assert_eq!(rabin_karp::RabinKarp::new(b"").find_iter(haystack, mode).collect::<Vec<_>>(), expected);
+                assert_eq!(z_algorithm::ZSearch::new(b"").find_iter(haystack, mode).collect::<Vec<_>>(), expected);
+                let automaton = aho_corasick::AhoCorasick::new([""]);
+                let starts: Vec<usize> = automaton.find_iter(haystack, mode).map(|m| m.start).collect();
+                assert_eq!(starts, expected);
+            }
+        }
+    }
+}
+
+/// Whether reported matches may share bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Report every match, e.g. `aa` occurs at 0, 1 and 2 in `aaaa`.
+    Overlapping,
+    /// Scan left to right and resume after each match, e.g. `aa` occurs at 0
+    /// and 2 in `aaaa`.
+    NonOverlapping,
+}
+
+/// Turns an ascending stream of overlapping match starts into the stream
+/// requested by `mode`.
+fn with_mode(
+    matches: impl Iterator<Item = usize>,
+    pattern_len: usize,
+    mode: MatchMode,
+) -> impl Iterator<Item = usize> {
+    let mut resume = 0;
+    matches.filter(move |&start| match mode {
+        MatchMode::Overlapping => true,
+        MatchMode::NonOverlapping if start >= resume => {
+            resume = start + pattern_len;
+            true
+        }
+        MatchMode::NonOverlapping => false,
+    })
+}
@@ -0,0 +1,90 @@
+use crate::string_search::{with_mode, MatchMode};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rabin_karp_find_iter() {
+        let rabin_karp = RabinKarp::new("404");
+        let log = "GET / 200\nGET /missing 404\nGET /gone 404\n";
+        assert_eq!(rabin_karp.find_iter(log, MatchMode::Overlapping).collect::<Vec<_>>(), vec![23, 37]);
+        assert_eq!(rabin_karp.find("GET / 200"), None);
+    }
+
+    #[test]
+    fn test_rabin_karp_modes() {
+        let rabin_karp = RabinKarp::new(b"xyx");
+        let text = b"xyxyxyx";
+        assert_eq!(rabin_karp.find_iter(text, MatchMode::Overlapping).collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(rabin_karp.find_iter(text, MatchMode::NonOverlapping).collect::<Vec<_>>(), vec![0, 4]);
+    }
+
+    #[test]
+    fn test_rolling_hash_matches_direct_hash() {
+        let text = b"the quick brown fox";
+        let rabin_karp = RabinKarp::new(b"brown");
+        assert_eq!(rabin_karp.hash, hash(&text[10..15]));
+        assert_eq!(rabin_karp.find(text), Some(10));
+    }
+}
+
+const BASE: u64 = 257;
+
+/// Polynomial hash of `bytes` in base `BASE`, modulo 2^64.
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |h: u64, &b| h.wrapping_mul(BASE).wrapping_add(b as u64))
+}
+
+/// Rabin-Karp search with a rolling polynomial hash.
+///
+/// Each window's hash is updated in O(1) as it slides; windows whose hash
+/// equals the pattern's are compared byte for byte, so collisions never
+/// produce false matches. O(n + m) expected.
+pub struct RabinKarp {
+    pattern: Vec<u8>,
+    hash: u64,
+    // BASE^(m - 1), the weight of the byte leaving the window
+    high: u64,
+}
+
+impl RabinKarp {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+        let hash = hash(&pattern);
+        let high = (1..pattern.len()).fold(1u64, |h, _| h.wrapping_mul(BASE));
+        RabinKarp { pattern, hash, high }
+    }
+
+    /// Iterates over the start offsets of matches in `haystack`.
+    pub fn find_iter<'a, H>(&'a self, haystack: &'a H, mode: MatchMode) -> impl Iterator<Item = usize> + 'a
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        let haystack = haystack.as_ref();
+        let m = self.pattern.len();
+        let mut pos = 0;
+        let mut window = if m <= haystack.len() { hash(&haystack[..m]) } else { 0 };
+        let overlapping = std::iter::from_fn(move || {
+            while pos + m <= haystack.len() {
+                let start = pos;
+                let found = window == self.hash && haystack[start..start + m] == self.pattern[..];
+                pos += 1;
+                if m > 0 && pos + m <= haystack.len() {
+                    // Drop haystack[start] and append haystack[start + m]
+                    let leaving = (haystack[start] as u64).wrapping_mul(self.high);
+                    window = window.wrapping_sub(leaving).wrapping_mul(BASE).wrapping_add(haystack[start + m] as u64);
+                }
+                if found {
+                    return Some(start);
+                }
+            }
+            None
+        });
+        with_mode(overlapping, m, mode)
+    }
+
+    /// Returns the start offset of the first match.
+    pub fn find<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Option<usize> {
+        self.find_iter(haystack, MatchMode::Overlapping).next()
+    }
+}
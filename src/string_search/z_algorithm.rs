@@ -0,0 +1,89 @@
+use crate::string_search::{with_mode, MatchMode};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z_array() {
+        assert_eq!(z_array(b"aabxaab"), vec![7, 1, 0, 0, 3, 1, 0]);
+        assert_eq!(z_array(b"aaaaa"), vec![5, 4, 3, 2, 1]);
+        assert!(z_array::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_z_search_find_iter() {
+        let z = ZSearch::new("na");
+        assert_eq!(z.find_iter("banana", MatchMode::Overlapping).collect::<Vec<_>>(), vec![2, 4]);
+        let z = ZSearch::new("ana");
+        assert_eq!(z.find_iter("banana", MatchMode::Overlapping).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(z.find_iter("banana", MatchMode::NonOverlapping).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(z.find("bandana"), Some(4));
+    }
+}
+
+/// Computes the Z-array of `s`: `z[i]` is the length of the longest common
+/// prefix of `s` and `s[i..]`, with `z[0] = s.len()`. O(n).
+pub fn z_array<T: Eq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    // [left, right) is the rightmost window known to match a prefix of s
+    let (mut left, mut right) = (0, 0);
+    for i in 1..n {
+        if i < right {
+            z[i] = z[i - left].min(right - i);
+        }
+        while i + z[i] < n && s[z[i]] == s[i + z[i]] {
+            z[i] += 1;
+        }
+        if i + z[i] > right {
+            (left, right) = (i, i + z[i]);
+        }
+    }
+    z
+}
+
+/// Search with the Z-algorithm.
+///
+/// Computes the Z-array of `pattern`, a separator that matches nothing and
+/// the haystack; every haystack position whose Z-value reaches the pattern
+/// length is a match. O(n + m) time and memory.
+pub struct ZSearch {
+    pattern: Vec<u8>,
+}
+
+impl ZSearch {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        ZSearch { pattern: pattern.as_ref().to_vec() }
+    }
+
+    /// Iterates over the start offsets of matches in `haystack`.
+    pub fn find_iter<H>(&self, haystack: &H, mode: MatchMode) -> impl Iterator<Item = usize>
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        let haystack = haystack.as_ref();
+        let m = self.pattern.len();
+        // None is the separator, so no Z-value can run past the pattern
+        let combined: Vec<Option<u8>> = self
+            .pattern
+            .iter()
+            .copied()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .chain(haystack.iter().copied().map(Some))
+            .collect();
+        let z = z_array(&combined);
+        let n = haystack.len();
+        let overlapping = (0..=n).filter(move |&i| m == 0 || (i < n && z[m + 1 + i] >= m));
+        with_mode(overlapping, m, mode)
+    }
+
+    /// Returns the start offset of the first match.
+    pub fn find<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Option<usize> {
+        self.find_iter(haystack, MatchMode::Overlapping).next()
+    }
+}
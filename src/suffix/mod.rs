@@ -0,0 +1,8 @@
+//! Substring indexes over bytes.
+//!
+//! `suffix_array` sorts all suffixes of a text once so that substring
+//! queries become binary searches; `suffix_automaton` recognises every
+//! substring of a text with a linear number of states.
+
+pub mod suffix_array;
+pub mod suffix_automaton;
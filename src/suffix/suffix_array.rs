@@ -0,0 +1,225 @@
+use crate::dp::lcs::CommonSubstring;
+use crate::radix_sort::radix_sort_by_key;
+use std::ops::Range;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suffix_array_and_lcp() {
+        let sa = SuffixArray::new("banana");
+        assert_eq!(sa.suffixes(), &[5, 3, 1, 0, 4, 2]);
+        assert_eq!(sa.lcp(), &[0, 1, 3, 0, 0, 2]);
+        assert!(SuffixArray::new("").suffixes().is_empty());
+        assert_eq!(SuffixArray::new("aaaa").suffixes(), &[3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_suffix_array_matches_naive_sort() {
+        let mut state: u32 = 0x1234_5678;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        for _ in 0..100 {
+            let text: Vec<u8> = (0..next() % 60).map(|_| b'a' + (next() % 3) as u8).collect();
+            let mut expected: Vec<usize> = (0..text.len()).collect();
+            expected.sort_by_key(|&i| &text[i..]);
+            let sa = SuffixArray::new(&text);
+            assert_eq!(sa.suffixes(), &expected[..]);
+            for i in 1..text.len() {
+                let (x, y) = (&text[expected[i - 1]..], &text[expected[i]..]);
+                let common = x.iter().zip(y).take_while(|(p, q)| p == q).count();
+                assert_eq!(sa.lcp()[i], common);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_and_locate() {
+        let sa = SuffixArray::new("mississippi");
+        assert_eq!(sa.count("ss"), 2);
+        assert_eq!(sa.locate("issi"), vec![1, 4]);
+        assert_eq!(sa.locate("i"), vec![1, 4, 7, 10]);
+        assert_eq!(sa.count("spa"), 0);
+        assert_eq!(sa.count("mississippis"), 0);
+        assert_eq!(sa.count(""), 11);
+    }
+
+    #[test]
+    fn test_longest_repeated_substring() {
+        let sa = SuffixArray::new("banana");
+        assert_eq!(sa.longest_repeated_substring(), Some(1..4));
+        assert_eq!(SuffixArray::new("abc").longest_repeated_substring(), None);
+        let sa = SuffixArray::new("to be or not to be");
+        let range = sa.longest_repeated_substring().unwrap();
+        assert_eq!(&sa.text()[range], b"to be");
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        let found = longest_common_substring("xabcdey", "zzabcdq").unwrap();
+        assert_eq!(found, CommonSubstring { a: 1..5, b: 2..6 });
+        assert_eq!(longest_common_substring("abc", "xyz"), None);
+        assert_eq!(longest_common_substring("", "xyz"), None);
+        // The separator keeps matches from running across the two strings
+        let found = longest_common_substring("ab", "bab").unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found.a, 0..2);
+    }
+}
+
+/// Sorts the suffixes of `symbols` by prefix doubling.
+///
+/// Each round orders suffixes by their first `2k` symbols, using the ranks of
+/// the first and second halves as a pair key sorted with two stable
+/// `radix_sort_by_key` passes. Takes at most O(log n) rounds and stops as
+/// soon as all ranks are distinct.
+fn build<T: Copy + Into<u64>>(symbols: &[T]) -> Vec<usize> {
+    let n = symbols.len();
+    let mut sa = radix_sort_by_key((0..n).collect(), |&i| symbols[i].into());
+    // Ranks start at 1 so that 0 can stand for "past the end"
+    let mut rank = vec![0u64; n];
+    let mut classes = 0;
+    for (j, &i) in sa.iter().enumerate() {
+        if j == 0 || symbols[i].into() != symbols[sa[j - 1]].into() {
+            classes += 1;
+        }
+        rank[i] = classes;
+    }
+
+    let mut k = 1;
+    while (classes as usize) < n {
+        let second = |i: usize| if i + k < n { rank[i + k] } else { 0 };
+        sa = radix_sort_by_key(sa, |&i| second(i));
+        sa = radix_sort_by_key(sa, |&i| rank[i]);
+        let mut next = vec![0u64; n];
+        classes = 0;
+        for (j, &i) in sa.iter().enumerate() {
+            if j == 0 || (rank[i], second(i)) != (rank[sa[j - 1]], second(sa[j - 1])) {
+                classes += 1;
+            }
+            next[i] = classes;
+        }
+        rank = next;
+        k *= 2;
+    }
+    sa
+}
+
+/// Computes the LCP array with Kasai's algorithm in O(n): `lcp[i]` is the
+/// length of the longest common prefix of suffixes `sa[i - 1]` and `sa[i]`,
+/// and `lcp[0]` is 0.
+fn kasai<T: PartialEq>(symbols: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = symbols.len();
+    let mut position = vec![0; n];
+    for (j, &i) in sa.iter().enumerate() {
+        position[i] = j;
+    }
+    let mut lcp = vec![0; n];
+    let mut h: usize = 0;
+    // Visiting suffixes in text order, the common prefix shrinks by at most one per step
+    for i in 0..n {
+        if position[i] == 0 {
+            h = 0;
+            continue;
+        }
+        let j = sa[position[i] - 1];
+        while i + h < n && j + h < n && symbols[i + h] == symbols[j + h] {
+            h += 1;
+        }
+        lcp[position[i]] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+/// A suffix array with its LCP array.
+pub struct SuffixArray {
+    text: Vec<u8>,
+    sa: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl SuffixArray {
+    pub fn new(text: impl AsRef<[u8]>) -> Self {
+        let text = text.as_ref().to_vec();
+        let sa = build(&text);
+        let lcp = kasai(&text, &sa);
+        SuffixArray { text, sa, lcp }
+    }
+
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// Returns the start offsets of the suffixes in sorted order.
+    pub fn suffixes(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// Returns the LCP array aligned with `suffixes`.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// Returns the range of `suffixes` that start with `pattern`, found with
+    /// two binary searches in O(m log n).
+    pub fn find_range(&self, pattern: impl AsRef<[u8]>) -> Range<usize> {
+        let pattern = pattern.as_ref();
+        let head = |s: usize| &self.text[s..(s + pattern.len()).min(self.text.len())];
+        let start = self.sa.partition_point(|&s| head(s) < pattern);
+        let end = self.sa.partition_point(|&s| head(s) <= pattern);
+        start..end
+    }
+
+    /// Counts the occurrences of `pattern`, overlapping ones included.
+    pub fn count(&self, pattern: impl AsRef<[u8]>) -> usize {
+        self.find_range(pattern).len()
+    }
+
+    /// Returns the start offsets of all occurrences of `pattern`, ascending.
+    pub fn locate(&self, pattern: impl AsRef<[u8]>) -> Vec<usize> {
+        let mut positions = self.sa[self.find_range(pattern)].to_vec();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Returns the byte range of a longest substring occurring at least
+    /// twice, or `None` if no byte repeats.
+    pub fn longest_repeated_substring(&self) -> Option<Range<usize>> {
+        let (i, &len) = self.lcp.iter().enumerate().max_by_key(|&(i, &len)| (len, std::cmp::Reverse(i)))?;
+        (len > 0).then(|| self.sa[i]..self.sa[i] + len)
+    }
+}
+
+/// Finds a longest common substring of `a` and `b` using a suffix array over
+/// `a`, a unique separator and `b`, comparing neighbouring suffixes that come
+/// from different strings.
+///
+/// Returns `None` if the strings share no byte.
+pub fn longest_common_substring(a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> Option<CommonSubstring> {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    // Shift bytes up by one so 0 is free to be the separator
+    let symbols: Vec<u16> = a
+        .iter()
+        .map(|&x| x as u16 + 1)
+        .chain(std::iter::once(0))
+        .chain(b.iter().map(|&x| x as u16 + 1))
+        .collect();
+    let sa = build(&symbols);
+    let lcp = kasai(&symbols, &sa);
+
+    let mut best: Option<CommonSubstring> = None;
+    for i in 1..sa.len() {
+        let (x, y) = (sa[i - 1].min(sa[i]), sa[i - 1].max(sa[i]));
+        // Neighbours from different strings; the separator caps the prefix
+        if x < a.len() && y > a.len() && lcp[i] > best.as_ref().map_or(0, CommonSubstring::len) {
+            let start_b = y - a.len() - 1;
+            best = Some(CommonSubstring { a: x..x + lcp[i], b: start_b..start_b + lcp[i] });
+        }
+    }
+    best
+}
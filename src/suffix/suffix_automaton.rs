@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_distinct(text: &[u8]) -> u64 {
+        let mut seen = std::collections::HashSet::new();
+        for i in 0..text.len() {
+            for j in i + 1..=text.len() {
+                seen.insert(&text[i..j]);
+            }
+        }
+        seen.len() as u64
+    }
+
+    #[test]
+    fn test_distinct_substrings() {
+        assert_eq!(SuffixAutomaton::new("banana").distinct_substrings(), 15);
+        assert_eq!(SuffixAutomaton::new("aaa").distinct_substrings(), 3);
+        assert_eq!(SuffixAutomaton::new("abc").distinct_substrings(), 6);
+        assert_eq!(SuffixAutomaton::new("").distinct_substrings(), 0);
+        for text in ["mississippi", "abababab", "abcabcbb", "zyxwvu"] {
+            assert_eq!(SuffixAutomaton::new(text).distinct_substrings(), naive_distinct(text.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let automaton = SuffixAutomaton::new("mississippi");
+        assert!(automaton.contains("ssip"));
+        assert!(automaton.contains(""));
+        assert!(!automaton.contains("spi"));
+        assert!(automaton.state_count() <= 2 * 11);
+    }
+
+    #[test]
+    fn test_extend_incrementally() {
+        let mut automaton = SuffixAutomaton::new("ab");
+        assert!(!automaton.contains("bc"));
+        automaton.extend(b'c');
+        assert!(automaton.contains("bc"));
+        assert_eq!(automaton.distinct_substrings(), 6);
+    }
+}
+
+struct State {
+    // Length of the longest substring ending in this state
+    len: usize,
+    // Suffix link; None only for the initial state
+    link: Option<usize>,
+    next: HashMap<u8, usize>,
+}
+
+/// The minimal automaton accepting every substring of a text.
+///
+/// Built online in O(n) amortised time, one byte at a time, with at most
+/// `2n - 1` states. Each state stands for a set of substrings sharing their
+/// end positions, which makes counting distinct substrings a sum over states.
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    last: usize,
+}
+
+impl SuffixAutomaton {
+    pub fn new(text: impl AsRef<[u8]>) -> Self {
+        let mut automaton = SuffixAutomaton {
+            states: vec![State { len: 0, link: None, next: HashMap::new() }],
+            last: 0,
+        };
+        for &b in text.as_ref() {
+            automaton.extend(b);
+        }
+        automaton
+    }
+
+    /// Appends one byte to the text.
+    pub fn extend(&mut self, b: u8) {
+        let current = self.states.len();
+        self.states.push(State { len: self.states[self.last].len + 1, link: None, next: HashMap::new() });
+
+        // Add transitions on `b` until a state already has one
+        let mut p = Some(self.last);
+        while let Some(state) = p {
+            if self.states[state].next.contains_key(&b) {
+                break;
+            }
+            self.states[state].next.insert(b, current);
+            p = self.states[state].link;
+        }
+
+        self.states[current].link = Some(match p {
+            None => 0,
+            Some(p) => {
+                let q = self.states[p].next[&b];
+                if self.states[p].len + 1 == self.states[q].len {
+                    q
+                } else {
+                    // Split q so that the shorter strings get their own state
+                    let clone = self.states.len();
+                    self.states.push(State {
+                        len: self.states[p].len + 1,
+                        link: self.states[q].link,
+                        next: self.states[q].next.clone(),
+                    });
+                    let mut p = Some(p);
+                    while let Some(state) = p {
+                        if self.states[state].next.get(&b) != Some(&q) {
+                            break;
+                        }
+                        self.states[state].next.insert(b, clone);
+                        p = self.states[state].link;
+                    }
+                    self.states[q].link = Some(clone);
+                    clone
+                }
+            }
+        });
+        self.last = current;
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns whether `pattern` is a substring of the text, in O(m).
+    pub fn contains(&self, pattern: impl AsRef<[u8]>) -> bool {
+        let mut state = 0;
+        for b in pattern.as_ref() {
+            match self.states[state].next.get(b) {
+                Some(&next) => state = next,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Counts the distinct non-empty substrings of the text.
+    pub fn distinct_substrings(&self) -> u64 {
+        self.states
+            .iter()
+            .filter_map(|state| state.link.map(|link| (state.len - self.states[link].len) as u64))
+            .sum()
+    }
+}
@@ -0,0 +1,589 @@
+use super::node::{self, AvlNode, BinaryNode, BinaryNodeMut, EntryMap, InOrder, Path, Side};
+pub use super::node::Entry;
+use super::InvariantError;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+use std::ops::RangeBounds;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = AvlTreeMap::new();
+        assert_eq!(map.insert(2, "b"), None);
+        assert_eq!(map.insert(1, "a"), None);
+        assert_eq!(map.insert(2, "B"), Some("b"));
+        assert_eq!(map.get(&2), Some(&"B"));
+        *map.get_mut(&1).unwrap() = "A";
+        assert_eq!(map.remove(&1), Some("A"));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.len(), 1);
+        assert!(map.contains_key(&2) && !map.contains_key(&1));
+    }
+
+    #[test]
+    fn test_sequential_inserts_stay_balanced() {
+        let map: AvlTreeMap<u32, ()> = (0..1023).map(|k| (k, ())).collect();
+        assert_eq!(map.validate(), Ok(()));
+        // A perfectly balanced tree of 1023 nodes has height 10
+        assert_eq!(map.height(), 10);
+    }
+
+    #[test]
+    fn test_ordered_queries() {
+        let map: AvlTreeMap<i32, char> = [(10, 'a'), (20, 'b'), (30, 'c'), (40, 'd')].into_iter().collect();
+        assert_eq!(map.first(), Some((&10, &'a')));
+        assert_eq!(map.last(), Some((&40, &'d')));
+        assert_eq!(map.floor(&25), Some((&20, &'b')));
+        assert_eq!(map.floor(&20), Some((&20, &'b')));
+        assert_eq!(map.floor(&5), None);
+        assert_eq!(map.ceiling(&25), Some((&30, &'c')));
+        assert_eq!(map.ceiling(&41), None);
+        let keys = |iter: Vec<(&i32, &char)>| iter.into_iter().map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(keys(map.range(15..=30).collect()), vec![20, 30]);
+        assert_eq!(keys(map.range(..20).collect()), vec![10]);
+        assert_eq!(keys(map.range(35..).collect()), vec![40]);
+        assert_eq!(keys(map.iter().collect()), vec![10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn test_entry_inserts_survive_rotations() {
+        // Entries need neither `Clone` keys nor a second search to hand back
+        // the inserted value, even when the insert rotates the tree
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Key(u64);
+        let mut map = AvlTreeMap::new();
+        let mut rng = XorShift64::new(45);
+        let keys: Vec<u64> = (0..300).chain((300..600).rev()).chain((0..600).map(|_| rng.below(900))).collect();
+        for &k in &keys {
+            map.entry(Key(k)).or_insert_with(Vec::new).push(k);
+        }
+        assert_eq!(map.validate(), Ok(()));
+        assert_eq!(map.len(), keys.iter().collect::<std::collections::BTreeSet<_>>().len());
+        assert!(map.iter().all(|(key, seen)| seen.iter().all(|&k| k == key.0)));
+        assert_eq!(map.iter().map(|(_, seen)| seen.len()).sum::<usize>(), keys.len());
+    }
+
+    #[test]
+    fn test_entry_api() {
+        let mut counts = AvlTreeMap::new();
+        for word in "the cat saw the other cat".split(' ') {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+        assert_eq!(counts.get("the"), Some(&2));
+        assert_eq!(counts.get("saw"), Some(&1));
+        counts.entry("cat").and_modify(|n| *n *= 10).or_default();
+        assert_eq!(counts.get("cat"), Some(&20));
+        match counts.entry("saw") {
+            Entry::Occupied(entry) => assert_eq!(entry.remove(), 1),
+            Entry::Vacant(_) => unreachable!(),
+        }
+        match counts.entry("dog") {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.key(), &"dog");
+                *entry.insert(3) += 1;
+            }
+            Entry::Occupied(_) => unreachable!(),
+        }
+        assert_eq!(counts.get("dog"), Some(&4));
+        assert_eq!(counts.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut set: AvlTreeSet<i32> = [5, 1, 3].into_iter().collect();
+        assert!(set.insert(4));
+        assert!(!set.insert(4));
+        assert!(set.remove(&1));
+        assert!(!set.contains(&1));
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!((set.first(), set.last()), (Some(&3), Some(&5)));
+        assert_eq!((set.floor(&2), set.ceiling(&2)), (None, Some(&3)));
+        assert_eq!(set.range(4..).count(), 2);
+        assert_eq!(format!("{:?}", set), "{3, 4, 5}");
+        assert_eq!(set.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_matches_btree_map_under_random_operations() {
+        let mut rng = XorShift64::new(0xA71);
+        let mut map = AvlTreeMap::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..4000 {
+            let key = rng.below(300);
+            match rng.below(3) {
+                0 | 1 => assert_eq!(map.insert(key, step), expected.insert(key, step)),
+                _ => assert_eq!(map.remove(&key), expected.remove(&key)),
+            }
+            if step % 100 == 0 {
+                assert_eq!(map.validate(), Ok(()));
+            }
+            let probe = rng.below(320);
+            assert_eq!(map.get(&probe), expected.get(&probe));
+            assert_eq!(map.floor(&probe), expected.range(..=probe).next_back());
+            assert_eq!(map.ceiling(&probe), expected.range(probe..).next());
+        }
+        assert_eq!(map.validate(), Ok(()));
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+        assert_eq!(map.first(), expected.first_key_value());
+        assert_eq!(map.last(), expected.last_key_value());
+        while let Some((&key, _)) = expected.first_key_value() {
+            assert_eq!(map.remove(&key), expected.remove(&key));
+        }
+        assert!(map.is_empty());
+    }
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // Number of nodes on the longest path down to a leaf, counting this one
+    height: u32,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> BinaryNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+impl<K, V> BinaryNodeMut for Node<K, V> {
+    fn left_mut(&mut self) -> &mut Link<K, V> {
+        &mut self.left
+    }
+
+    fn right_mut(&mut self) -> &mut Link<K, V> {
+        &mut self.right
+    }
+}
+
+impl<K, V> AvlNode for Node<K, V> {
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn update(&mut self) {
+        self.height = 1 + node::height(self.left()).max(node::height(self.right()));
+    }
+}
+
+impl<K, V> Node<K, V> {
+    fn leaf(key: K, value: V) -> Box<Self> {
+        Box::new(Node { key, value, height: 1, left: None, right: None })
+    }
+}
+
+/// Inserts into the subtree at `link`. When `path` is given it ends up
+/// leading from the returned root to the inserted node.
+fn insert<K: Ord, V>(
+    link: Link<K, V>,
+    key: K,
+    value: V,
+    replaced: &mut Option<V>,
+    mut path: Option<&mut Path>,
+) -> Box<Node<K, V>> {
+    let Some(mut node) = link else {
+        return Node::leaf(key, value);
+    };
+    let side = match key.cmp(&node.key) {
+        Ordering::Less => {
+            node.left = Some(insert(node.left.take(), key, value, replaced, path.as_deref_mut()));
+            Side::Left
+        }
+        Ordering::Greater => {
+            node.right = Some(insert(node.right.take(), key, value, replaced, path.as_deref_mut()));
+            Side::Right
+        }
+        Ordering::Equal => {
+            *replaced = Some(mem::replace(&mut node.value, value));
+            return node;
+        }
+    };
+    match path {
+        Some(path) => {
+            path.push(side);
+            path.track(node, node::rebalance)
+        }
+        None => node::rebalance(node),
+    }
+}
+
+fn remove<K, V, Q>(link: &mut Link<K, V>, key: &Q) -> Option<(K, V)>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let node = link.as_mut()?;
+    let removed = match key.cmp(node.key.borrow()) {
+        Ordering::Less => remove(&mut node.left, key),
+        Ordering::Greater => remove(&mut node.right, key),
+        Ordering::Equal => {
+            let mut node = link.take().expect("checked above");
+            *link = node::splice_out(&mut *node);
+            return Some((node.key, node.value));
+        }
+    };
+    if removed.is_some() {
+        *link = link.take().map(node::rebalance);
+    }
+    removed
+}
+
+/// An ordered map backed by an AVL tree: the heights of every node's two
+/// subtrees differ by at most one, so the tree height stays below
+/// 1.44·log2(n + 2) and lookups, inserts and removals are O(log n).
+pub struct AvlTreeMap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+impl<K, V> AvlTreeMap<K, V> {
+    pub fn new() -> Self {
+        AvlTreeMap { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of nodes on the longest root-to-leaf path; 0 when empty.
+    pub fn height(&self) -> u32 {
+        node::height(self.root.as_deref())
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::first(root).entry())
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::last(root).entry())
+    }
+
+    /// Iterates the entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        InOrder::new(self.root.as_deref()).map(Node::entry)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: Ord, V> AvlTreeMap<K, V> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut replaced = None;
+        self.root = Some(insert(self.root.take(), key, value, &mut replaced, None));
+        if replaced.is_none() {
+            self.len += 1;
+        }
+        replaced
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (_, value) = remove(&mut self.root, key)?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = self.root.as_deref_mut();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// The entry with the greatest key less than or equal to `key`.
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::floor(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// The entry with the least key greater than or equal to `key`.
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::ceiling(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// Iterates the entries whose keys fall in `range`, in key order.
+    pub fn range<'a, Q, R>(&'a self, range: R) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized + 'a,
+        R: RangeBounds<Q> + 'a,
+    {
+        InOrder::starting_at(self.root.as_deref(), range.start_bound())
+            .take_while(move |node| node::below_end(&node.key, range.end_bound()))
+            .map(Node::entry)
+    }
+
+    /// Checks the BST ordering, every cached height, the AVL balance and the
+    /// stored length.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        fn check<K, V>(link: &Link<K, V>) -> Result<u32, InvariantError> {
+            let Some(node) = link else {
+                return Ok(0);
+            };
+            let (left, right) = (check(&node.left)?, check(&node.right)?);
+            if node.height != 1 + left.max(right) {
+                return Err(InvariantError::WrongHeight);
+            }
+            if left.abs_diff(right) > 1 {
+                return Err(InvariantError::Unbalanced);
+            }
+            Ok(node.height)
+        }
+        check(&self.root)?;
+        let keys: Vec<&K> = self.keys().collect();
+        if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(InvariantError::Unordered);
+        }
+        if keys.len() != self.len {
+            return Err(InvariantError::WrongLength { expected: self.len, found: keys.len() });
+        }
+        Ok(())
+    }
+}
+
+impl<K: Ord, V> AvlTreeMap<K, V> {
+    /// Looks up `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry::new(self, key)
+    }
+}
+
+impl<K: Ord, V> EntryMap<K, V> for AvlTreeMap<K, V> {
+    fn find_path(&self, key: &K) -> Option<Path> {
+        node::find_path(self.root.as_deref(), key)
+    }
+
+    fn value_at(&self, path: &Path) -> &V {
+        &node::follow(self.root.as_deref().expect("entry is occupied"), path).value
+    }
+
+    fn value_at_mut(&mut self, path: &Path) -> &mut V {
+        &mut node::follow_mut(self.root.as_deref_mut().expect("entry is occupied"), path).value
+    }
+
+    fn insert_absent(&mut self, key: K, value: V) -> Path {
+        let mut path = Path::default();
+        self.root = Some(insert(self.root.take(), key, value, &mut None, Some(&mut path)));
+        self.len += 1;
+        path
+    }
+
+    fn remove_present(&mut self, key: &K) -> V {
+        self.remove(key).expect("entry is occupied")
+    }
+}
+
+impl<K, V> Default for AvlTreeMap<K, V> {
+    fn default() -> Self {
+        AvlTreeMap::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for AvlTreeMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for AvlTreeMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for AvlTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AvlTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/// An ordered set backed by an [`AvlTreeMap`] with unit values.
+pub struct AvlTreeSet<T> {
+    map: AvlTreeMap<T, ()>,
+}
+
+impl<T: Ord> AvlTreeSet<T> {
+    pub fn new() -> Self {
+        AvlTreeSet { map: AvlTreeMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Adds `value`, returning whether it was new.
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(value, _)| value)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(value, _)| value)
+    }
+
+    pub fn floor<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.floor(value).map(|(value, _)| value)
+    }
+
+    pub fn ceiling<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.ceiling(value).map(|(value, _)| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.map.keys()
+    }
+
+    pub fn range<'a, Q, R>(&'a self, range: R) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized + 'a,
+        R: RangeBounds<Q> + 'a,
+    {
+        self.map.range(range).map(|(value, _)| value)
+    }
+
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        self.map.validate()
+    }
+}
+
+impl<T: Ord> Default for AvlTreeSet<T> {
+    fn default() -> Self {
+        AvlTreeSet::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AvlTreeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.map.keys()).finish()
+    }
+}
+
+impl<T: Ord> Extend<T> for AvlTreeSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for AvlTreeSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = AvlTreeSet::new();
+        set.extend(iter);
+        set
+    }
+}
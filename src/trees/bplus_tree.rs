@@ -0,0 +1,740 @@
+use super::InvariantError;
+use std::borrow::Borrow;
+use std::fmt;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge_sort::merge_sort;
+    use crate::rng::XorShift64;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut tree: BPlusTree<u32, char, 2> = BPlusTree::new();
+        for (i, c) in "hello world".chars().enumerate() {
+            tree.insert(i as u32, c);
+        }
+        assert_eq!(tree.len(), 11);
+        assert_eq!(tree.insert(4, 'O'), Some('o'));
+        assert_eq!(tree.get(&4), Some(&'O'));
+        *tree.get_mut(&0).unwrap() = 'H';
+        assert_eq!(tree.remove(&5), Some(' '));
+        assert_eq!(tree.remove(&5), None);
+        assert_eq!(tree.values().collect::<String>(), "HellOworld");
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_range_scans_follow_leaf_links() {
+        let tree: BPlusTree<i32, i32, 2> = (0..50).map(|k| (k * 10, k)).collect();
+        let keys = |range: Vec<(&i32, &i32)>| range.into_iter().map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(keys(tree.range(95..=130).collect()), vec![100, 110, 120, 130]);
+        assert_eq!(keys(tree.range((Bound::Excluded(470), Bound::Unbounded)).collect()), vec![480, 490]);
+        assert_eq!(tree.range(..).count(), 50);
+        assert_eq!(tree.range(1000..).count(), 0);
+        assert_eq!((tree.first(), tree.last()), (Some((&0, &0)), Some((&490, &49))));
+    }
+
+    #[test]
+    fn test_bulk_load_from_sorted_input() {
+        let mut rng = XorShift64::new(3);
+        let mut keys: Vec<u64> = (0..500).map(|_| rng.below(1_000_000)).collect();
+        keys = merge_sort(keys);
+        keys.dedup();
+        let tree: BPlusTree<u64, usize, 4> =
+            BPlusTree::bulk_load(keys.iter().enumerate().map(|(i, &k)| (k, i))).unwrap();
+        assert_eq!(tree.validate(), Ok(()));
+        assert_eq!(tree.len(), keys.len());
+        assert!(tree.keys().eq(keys.iter()));
+        // Every leaf but possibly the root is at least half full
+        let mut tree = tree;
+        tree.insert(0, 0);
+        tree.remove(&keys[10]);
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_bulk_load_edge_cases() {
+        let empty: BPlusTree<u8, (), 2> = BPlusTree::bulk_load(std::iter::empty()).unwrap();
+        assert!(empty.is_empty() && empty.validate().is_ok());
+        for n in 1..40 {
+            let tree: BPlusTree<u32, (), 2> = BPlusTree::bulk_load((0..n).map(|k| (k, ()))).unwrap();
+            assert_eq!(tree.validate(), Ok(()), "n = {}", n);
+            assert_eq!(tree.len(), n as usize);
+        }
+        let unsorted = BPlusTree::<u8, (), 2>::bulk_load([(1, ()), (3, ()), (2, ())]);
+        assert_eq!(unsorted.unwrap_err(), BulkLoadError::Unsorted { index: 2 });
+        let duplicate = BPlusTree::<u8, (), 2>::bulk_load([(1, ()), (1, ())]).unwrap_err();
+        assert_eq!(duplicate.to_string(), "key at index 1 is not greater than the one before it");
+    }
+
+    #[test]
+    fn test_matches_btree_map_under_random_operations() {
+        let mut rng = XorShift64::new(0xB9);
+        let mut tree: BPlusTree<u64, usize, 3> = BPlusTree::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..5000 {
+            let key = rng.below(400);
+            match rng.below(5) {
+                0..=2 => assert_eq!(tree.insert(key, step), expected.insert(key, step)),
+                _ => assert_eq!(tree.remove(&key), expected.remove(&key)),
+            }
+            if step % 200 == 0 {
+                assert_eq!(tree.validate(), Ok(()));
+            }
+            let probe = rng.below(420);
+            assert_eq!(tree.get(&probe), expected.get(&probe));
+        }
+        assert_eq!(tree.validate(), Ok(()));
+        assert!(tree.iter().eq(expected.iter()));
+        assert!(tree.range(100..300).eq(expected.range(100..300)));
+        let keys: Vec<u64> = expected.keys().copied().collect();
+        for key in keys {
+            assert_eq!(tree.remove(&key), expected.remove(&key));
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.validate(), Ok(()));
+    }
+}
+
+/// Returned by [`BPlusTree::bulk_load`] when the input is not sorted by key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkLoadError {
+    /// The key at `index` is less than or equal to the key before it.
+    Unsorted { index: usize },
+}
+
+impl fmt::Display for BulkLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BulkLoadError::Unsorted { index } => {
+                write!(f, "key at index {} is not greater than the one before it", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BulkLoadError {}
+
+struct Leaf<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    next: Option<usize>,
+}
+
+struct Internal<K> {
+    // keys[i] is a lower bound of every key under children[i + 1] and an
+    // exclusive upper bound of every key under children[i]
+    keys: Vec<K>,
+    children: Vec<usize>,
+}
+
+enum Node<K, V> {
+    Leaf(Leaf<K, V>),
+    Internal(Internal<K>),
+}
+
+/// A B+tree map: entries live only in the leaves, which are linked in key
+/// order, and internal nodes hold copies of keys to route searches. Every
+/// node except the root holds between `B - 1` and `2B - 1` keys.
+///
+/// Nodes are stored in an arena and refer to each other by index, so the
+/// leaf chain needs no shared ownership. Range scans find their first leaf
+/// in O(log n) and then walk the chain without revisiting internal nodes.
+pub struct BPlusTree<K, V, const B: usize> {
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    root: usize,
+    len: usize,
+}
+
+impl<K, V, const B: usize> BPlusTree<K, V, B> {
+    const MAX_KEYS: usize = 2 * B - 1;
+
+    pub fn new() -> Self {
+        const { assert!(B >= 2, "a B+tree needs a minimum degree of at least 2") };
+        let root = Node::Leaf(Leaf { keys: Vec::new(), values: Vec::new(), next: None });
+        BPlusTree { nodes: vec![Some(root)], free: Vec::new(), root: 0, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of levels, counting the leaves; 0 when empty.
+    pub fn height(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let mut height = 1;
+        let mut node = self.root;
+        while let Node::Internal(internal) = self.node(node) {
+            height += 1;
+            node = internal.children[0];
+        }
+        height
+    }
+
+    pub fn clear(&mut self) {
+        *self = BPlusTree::new();
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut node = self.root;
+        loop {
+            match self.node(node) {
+                Node::Internal(internal) => node = *internal.children.last().expect("internal nodes have children"),
+                Node::Leaf(leaf) => return Some((leaf.keys.last()?, leaf.values.last()?)),
+            }
+        }
+    }
+
+    /// Iterates the entries in key order along the leaf chain.
+    pub fn iter(&self) -> Iter<'_, K, V, B> {
+        let mut node = self.root;
+        while let Node::Internal(internal) = self.node(node) {
+            node = internal.children[0];
+        }
+        Iter { tree: self, leaf: Some(node), index: 0 }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    fn node(&self, index: usize) -> &Node<K, V> {
+        self.nodes[index].as_ref().expect("node is live")
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut Node<K, V> {
+        self.nodes[index].as_mut().expect("node is live")
+    }
+
+    fn leaf_mut(&mut self, index: usize) -> &mut Leaf<K, V> {
+        match self.node_mut(index) {
+            Node::Leaf(leaf) => leaf,
+            Node::Internal(_) => panic!("expected a leaf"),
+        }
+    }
+
+    fn internal_mut(&mut self, index: usize) -> &mut Internal<K> {
+        match self.node_mut(index) {
+            Node::Internal(internal) => internal,
+            Node::Leaf(_) => panic!("expected an internal node"),
+        }
+    }
+
+    fn allocate(&mut self, node: Node<K, V>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn release(&mut self, index: usize) -> Node<K, V> {
+        self.free.push(index);
+        self.nodes[index].take().expect("node is live")
+    }
+
+    fn key_count(&self, index: usize) -> usize {
+        match self.node(index) {
+            Node::Leaf(leaf) => leaf.keys.len(),
+            Node::Internal(internal) => internal.keys.len(),
+        }
+    }
+}
+
+impl<K: Ord + Clone, V, const B: usize> BPlusTree<K, V, B> {
+    /// Builds a tree from entries in strictly increasing key order in O(n),
+    /// packing each level evenly so every node is at least half full.
+    pub fn bulk_load<I: IntoIterator<Item = (K, V)>>(entries: I) -> Result<Self, BulkLoadError> {
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        for (index, (key, value)) in entries.into_iter().enumerate() {
+            if keys.last().is_some_and(|last| *last >= key) {
+                return Err(BulkLoadError::Unsorted { index });
+            }
+            keys.push(key);
+            values.push(value);
+        }
+        let mut tree = BPlusTree::new();
+        if keys.is_empty() {
+            return Ok(tree);
+        }
+        tree.len = keys.len();
+        tree.nodes.clear();
+
+        // Each level is a list of (node index, smallest key beneath it)
+        let mut level = Vec::new();
+        let mut keys = keys.into_iter();
+        let mut values = values.into_iter();
+        for size in even_chunks(tree.len, Self::MAX_KEYS) {
+            let leaf = Leaf {
+                keys: keys.by_ref().take(size).collect::<Vec<_>>(),
+                values: values.by_ref().take(size).collect(),
+                next: None,
+            };
+            let min = leaf.keys[0].clone();
+            level.push((tree.allocate(Node::Leaf(leaf)), min));
+        }
+        for pair in level.windows(2) {
+            tree.leaf_mut(pair[0].0).next = Some(pair[1].0);
+        }
+        while level.len() > 1 {
+            let mut parents = Vec::new();
+            let mut children = level.into_iter();
+            for size in even_chunks(children.len(), Self::MAX_KEYS + 1) {
+                let group: Vec<(usize, K)> = children.by_ref().take(size).collect();
+                let mut group = group.into_iter();
+                let (first, min) = group.next().expect("groups are not empty");
+                let mut internal = Internal { keys: Vec::new(), children: vec![first] };
+                for (child, key) in group {
+                    internal.keys.push(key);
+                    internal.children.push(child);
+                }
+                parents.push((tree.allocate(Node::Internal(internal)), min));
+            }
+            level = parents;
+        }
+        tree.root = level[0].0;
+        Ok(tree)
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut replaced = None;
+        if let Some((separator, right)) = self.insert_into(self.root, key, value, &mut replaced) {
+            let internal = Internal { keys: vec![separator], children: vec![self.root, right] };
+            self.root = self.allocate(Node::Internal(internal));
+        }
+        if replaced.is_none() {
+            self.len += 1;
+        }
+        replaced
+    }
+
+    /// Inserts below `index`, returning the separator and new right sibling
+    /// if the node had to split.
+    fn insert_into(&mut self, index: usize, key: K, value: V, replaced: &mut Option<V>) -> Option<(K, usize)> {
+        match self.node_mut(index) {
+            Node::Leaf(leaf) => {
+                match leaf.keys.binary_search(&key) {
+                    Ok(i) => {
+                        *replaced = Some(mem::replace(&mut leaf.values[i], value));
+                        return None;
+                    }
+                    Err(i) => {
+                        leaf.keys.insert(i, key);
+                        leaf.values.insert(i, value);
+                    }
+                }
+                if leaf.keys.len() <= Self::MAX_KEYS {
+                    return None;
+                }
+                let at = leaf.keys.len() / 2;
+                let right = Leaf { keys: leaf.keys.split_off(at), values: leaf.values.split_off(at), next: leaf.next };
+                let separator = right.keys[0].clone();
+                let right = self.allocate(Node::Leaf(right));
+                self.leaf_mut(index).next = Some(right);
+                Some((separator, right))
+            }
+            Node::Internal(internal) => {
+                let i = internal.keys.partition_point(|k| *k <= key);
+                let child = internal.children[i];
+                let (separator, right) = self.insert_into(child, key, value, replaced)?;
+                let internal = self.internal_mut(index);
+                internal.keys.insert(i, separator);
+                internal.children.insert(i + 1, right);
+                if internal.keys.len() <= Self::MAX_KEYS {
+                    return None;
+                }
+                // The middle key moves up; it is not kept in either half
+                let at = internal.keys.len() / 2;
+                let keys = internal.keys.split_off(at + 1);
+                let separator = internal.keys.pop().expect("node is overfull");
+                let children = internal.children.split_off(at + 1);
+                let right = self.allocate(Node::Internal(Internal { keys, children }));
+                Some((separator, right))
+            }
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let removed = self.remove_from(self.root, key)?;
+        self.len -= 1;
+        if let Node::Internal(root) = self.node(self.root) {
+            if root.keys.is_empty() {
+                let child = root.children[0];
+                self.release(self.root);
+                self.root = child;
+            }
+        }
+        Some(removed)
+    }
+
+    fn remove_from<Q>(&mut self, index: usize, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.node_mut(index) {
+            Node::Leaf(leaf) => {
+                let i = leaf.keys.binary_search_by(|probe| probe.borrow().cmp(key)).ok()?;
+                leaf.keys.remove(i);
+                Some(leaf.values.remove(i))
+            }
+            Node::Internal(internal) => {
+                let i = internal.keys.partition_point(|k| k.borrow() <= key);
+                let child = internal.children[i];
+                let removed = self.remove_from(child, key)?;
+                if self.key_count(child) < B - 1 {
+                    self.rebalance_child(index, i);
+                }
+                Some(removed)
+            }
+        }
+    }
+
+    /// Refills the underfull child `i` of `parent` by borrowing from a
+    /// sibling that can spare a key, or merges it with one.
+    fn rebalance_child(&mut self, parent: usize, i: usize) {
+        let children = &self.internal_mut(parent).children;
+        let left = i.checked_sub(1).map(|l| children[l]);
+        let right = children.get(i + 1).copied();
+        if left.is_some_and(|left| self.key_count(left) >= B) {
+            self.borrow_from_left(parent, i);
+        } else if right.is_some_and(|right| self.key_count(right) >= B) {
+            self.borrow_from_right(parent, i);
+        } else if left.is_some() {
+            self.merge_children(parent, i - 1);
+        } else {
+            self.merge_children(parent, i);
+        }
+    }
+
+    /// Moves the last key of child `i - 1` to child `i`.
+    fn borrow_from_left(&mut self, parent: usize, i: usize) {
+        let children = &self.internal_mut(parent).children;
+        let (left, child) = (children[i - 1], children[i]);
+        let mut left_node = self.take_node(left);
+        let mut child_node = self.take_node(child);
+        let separator = &mut self.internal_mut(parent).keys[i - 1];
+        match (&mut left_node, &mut child_node) {
+            (Node::Leaf(left), Node::Leaf(child)) => {
+                child.keys.insert(0, left.keys.pop().expect("left can spare a key"));
+                child.values.insert(0, left.values.pop().expect("left can spare a key"));
+                *separator = child.keys[0].clone();
+            }
+            (Node::Internal(left), Node::Internal(child)) => {
+                // Rotate right through the separator
+                child.children.insert(0, left.children.pop().expect("left can spare a child"));
+                let up = left.keys.pop().expect("left can spare a key");
+                child.keys.insert(0, mem::replace(separator, up));
+            }
+            _ => unreachable!("siblings are at the same depth"),
+        }
+        self.nodes[left] = Some(left_node);
+        self.nodes[child] = Some(child_node);
+    }
+
+    /// Moves the first key of child `i + 1` to child `i`.
+    fn borrow_from_right(&mut self, parent: usize, i: usize) {
+        let children = &self.internal_mut(parent).children;
+        let (child, right) = (children[i], children[i + 1]);
+        let mut child_node = self.take_node(child);
+        let mut right_node = self.take_node(right);
+        let separator = &mut self.internal_mut(parent).keys[i];
+        match (&mut child_node, &mut right_node) {
+            (Node::Leaf(child), Node::Leaf(right)) => {
+                child.keys.push(right.keys.remove(0));
+                child.values.push(right.values.remove(0));
+                *separator = right.keys[0].clone();
+            }
+            (Node::Internal(child), Node::Internal(right)) => {
+                // Rotate left through the separator
+                child.children.push(right.children.remove(0));
+                let up = right.keys.remove(0);
+                child.keys.push(mem::replace(separator, up));
+            }
+            _ => unreachable!("siblings are at the same depth"),
+        }
+        self.nodes[child] = Some(child_node);
+        self.nodes[right] = Some(right_node);
+    }
+
+    /// Merges child `i + 1` of `parent` into child `i`.
+    fn merge_children(&mut self, parent: usize, i: usize) {
+        let internal = self.internal_mut(parent);
+        let separator = internal.keys.remove(i);
+        let right = internal.children.remove(i + 1);
+        let left = internal.children[i];
+        let right_node = self.release(right);
+        match (self.node_mut(left), right_node) {
+            (Node::Leaf(left), Node::Leaf(right)) => {
+                // Leaves need no separator; the right leaf's link is inherited
+                left.keys.extend(right.keys);
+                left.values.extend(right.values);
+                left.next = right.next;
+            }
+            (Node::Internal(left), Node::Internal(right)) => {
+                left.keys.push(separator);
+                left.keys.extend(right.keys);
+                left.children.extend(right.children);
+            }
+            _ => unreachable!("siblings are at the same depth"),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let leaf = self.find_leaf(key);
+        let i = leaf.keys.binary_search_by(|probe| probe.borrow().cmp(key)).ok()?;
+        Some(&leaf.values[i])
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = self.root;
+        while let Node::Internal(internal) = self.node(node) {
+            node = internal.children[internal.keys.partition_point(|k| k.borrow() <= key)];
+        }
+        let leaf = self.leaf_mut(node);
+        let i = leaf.keys.binary_search_by(|probe| probe.borrow().cmp(key)).ok()?;
+        Some(&mut leaf.values[i])
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Iterates the entries whose keys fall in `range`, in key order.
+    pub fn range<'a, Q, R>(&'a self, range: R) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized + 'a,
+        R: RangeBounds<Q> + 'a,
+    {
+        let mut node = self.root;
+        let (leaf, index) = loop {
+            match self.node(node) {
+                Node::Internal(internal) => {
+                    let i = match range.start_bound() {
+                        Bound::Included(start) | Bound::Excluded(start) => {
+                            internal.keys.partition_point(|k| k.borrow() <= start)
+                        }
+                        Bound::Unbounded => 0,
+                    };
+                    node = internal.children[i];
+                }
+                Node::Leaf(leaf) => {
+                    let i = match range.start_bound() {
+                        Bound::Included(start) => leaf.keys.partition_point(|k| k.borrow() < start),
+                        Bound::Excluded(start) => leaf.keys.partition_point(|k| k.borrow() <= start),
+                        Bound::Unbounded => 0,
+                    };
+                    break (node, i);
+                }
+            }
+        };
+        Iter { tree: self, leaf: Some(leaf), index }.take_while(move |(key, _)| match range.end_bound() {
+            Bound::Included(end) => (*key).borrow() <= end,
+            Bound::Excluded(end) => (*key).borrow() < end,
+            Bound::Unbounded => true,
+        })
+    }
+
+    /// Checks node fill factors, that every key lies between the separators
+    /// above it, that all leaves are at the same depth, and that the leaf
+    /// chain visits every leaf in order with strictly increasing keys.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut leaves = Vec::new();
+        self.check(self.root, true, None, None, &mut leaves)?;
+        let mut chain = Vec::new();
+        let mut leaf = leaves.first().copied();
+        while let Some(index) = leaf {
+            if chain.len() > leaves.len() {
+                break;
+            }
+            chain.push(index);
+            leaf = match self.node(index) {
+                Node::Leaf(leaf) => leaf.next,
+                Node::Internal(_) => return Err(InvariantError::BrokenLeafChain),
+            };
+        }
+        if chain != leaves {
+            return Err(InvariantError::BrokenLeafChain);
+        }
+        let keys: Vec<&K> = self.keys().collect();
+        if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(InvariantError::Unordered);
+        }
+        if keys.len() != self.len {
+            return Err(InvariantError::WrongLength { expected: self.len, found: keys.len() });
+        }
+        Ok(())
+    }
+
+    /// Validates the subtree at `index`, whose keys must lie in
+    /// `[lower, upper)`, collecting its leaves in order and returning its depth.
+    fn check(
+        &self,
+        index: usize,
+        is_root: bool,
+        lower: Option<&K>,
+        upper: Option<&K>,
+        leaves: &mut Vec<usize>,
+    ) -> Result<usize, InvariantError> {
+        let count = self.key_count(index);
+        if count > Self::MAX_KEYS {
+            return Err(InvariantError::Overfull);
+        }
+        if !is_root && count < B - 1 {
+            return Err(InvariantError::Underfull);
+        }
+        let keys = match self.node(index) {
+            Node::Leaf(leaf) => &leaf.keys,
+            Node::Internal(internal) => &internal.keys,
+        };
+        let in_bounds = |key: &K| lower.is_none_or(|lower| key >= lower) && upper.is_none_or(|upper| key < upper);
+        if !keys.iter().all(in_bounds) || keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(InvariantError::Unordered);
+        }
+        match self.node(index) {
+            Node::Leaf(leaf) => {
+                if leaf.values.len() != leaf.keys.len() {
+                    return Err(InvariantError::WrongChildCount);
+                }
+                leaves.push(index);
+                Ok(1)
+            }
+            Node::Internal(internal) => {
+                if internal.children.len() != internal.keys.len() + 1 || (is_root && internal.keys.is_empty()) {
+                    return Err(InvariantError::WrongChildCount);
+                }
+                let mut depth = None;
+                for (i, &child) in internal.children.iter().enumerate() {
+                    let lower = if i == 0 { lower } else { Some(&internal.keys[i - 1]) };
+                    let upper = internal.keys.get(i).or(upper);
+                    let child_depth = self.check(child, false, lower, upper, leaves)?;
+                    if depth.is_some_and(|depth| depth != child_depth) {
+                        return Err(InvariantError::UnevenDepth);
+                    }
+                    depth = Some(child_depth);
+                }
+                Ok(depth.expect("internal nodes have children") + 1)
+            }
+        }
+    }
+
+    fn find_leaf<Q>(&self, key: &Q) -> &Leaf<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = self.root;
+        loop {
+            match self.node(node) {
+                Node::Internal(internal) => node = internal.children[internal.keys.partition_point(|k| k.borrow() <= key)],
+                Node::Leaf(leaf) => return leaf,
+            }
+        }
+    }
+
+    fn take_node(&mut self, index: usize) -> Node<K, V> {
+        self.nodes[index].take().expect("node is live")
+    }
+}
+
+/// Splits `n` items into the fewest chunks of at most `max` items, with
+/// sizes differing by at most one.
+fn even_chunks(n: usize, max: usize) -> impl Iterator<Item = usize> {
+    let chunks = n.div_ceil(max);
+    (0..chunks).map(move |i| n / chunks + usize::from(i < n % chunks))
+}
+
+/// Iterator over a [`BPlusTree`] that walks the linked leaves.
+pub struct Iter<'a, K, V, const B: usize> {
+    tree: &'a BPlusTree<K, V, B>,
+    leaf: Option<usize>,
+    index: usize,
+}
+
+impl<'a, K, V, const B: usize> Iterator for Iter<'a, K, V, B> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        loop {
+            let Node::Leaf(leaf) = self.tree.node(self.leaf?) else {
+                unreachable!("the chain only links leaves");
+            };
+            if self.index < leaf.keys.len() {
+                self.index += 1;
+                return Some((&leaf.keys[self.index - 1], &leaf.values[self.index - 1]));
+            }
+            self.leaf = leaf.next;
+            self.index = 0;
+        }
+    }
+}
+
+impl<K, V, const B: usize> Default for BPlusTree<K, V, B> {
+    fn default() -> Self {
+        BPlusTree::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, const B: usize> fmt::Debug for BPlusTree<K, V, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord + Clone, V, const B: usize> Extend<(K, V)> for BPlusTree<K, V, B> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord + Clone, V, const B: usize> FromIterator<(K, V)> for BPlusTree<K, V, B> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BPlusTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
@@ -0,0 +1,509 @@
+use super::InvariantError;
+use std::borrow::Borrow;
+use std::fmt;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut tree: BTree<u32, &str, 2> = BTree::new();
+        for (key, value) in [(5, "e"), (1, "a"), (3, "c"), (4, "d"), (2, "b")] {
+            assert_eq!(tree.insert(key, value), None);
+        }
+        assert_eq!(tree.insert(3, "C"), Some("c"));
+        assert_eq!(tree.get(&3), Some(&"C"));
+        *tree.get_mut(&4).unwrap() = "D";
+        assert_eq!(tree.remove(&4), Some("D"));
+        assert_eq!(tree.remove(&4), None);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 2, 3, 5]);
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_height_shrinks_with_branching_factor() {
+        let narrow: BTree<u32, (), 2> = (0..1000).map(|k| (k, ())).collect();
+        let wide: BTree<u32, (), 16> = (0..1000).map(|k| (k, ())).collect();
+        assert_eq!((narrow.validate(), wide.validate()), (Ok(()), Ok(())));
+        assert!(wide.height() < narrow.height());
+        assert!(wide.height() <= 3);
+    }
+
+    #[test]
+    fn test_range_scans() {
+        let tree: BTree<i32, i32, 3> = (0..100).map(|k| (k * 2, k)).collect();
+        let keys = |range: Vec<(&i32, &i32)>| range.into_iter().map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(keys(tree.range(10..=16).collect()), vec![10, 12, 14, 16]);
+        assert_eq!(keys(tree.range(11..17).collect()), vec![12, 14, 16]);
+        assert_eq!(keys(tree.range((Bound::Excluded(194), Bound::Unbounded)).collect()), vec![196, 198]);
+        assert_eq!(tree.range(..0).count(), 0);
+        assert_eq!(tree.range(..).count(), 100);
+        assert_eq!((tree.first(), tree.last()), (Some((&0, &0)), Some((&198, &99))));
+    }
+
+    #[test]
+    fn test_remove_everything_in_both_orders() {
+        let mut tree: BTree<u32, u32, 2> = (0..200).map(|k| (k, k)).collect();
+        for key in (0..200).step_by(2) {
+            assert_eq!(tree.remove(&key), Some(key));
+        }
+        assert_eq!(tree.validate(), Ok(()));
+        for key in (1..200).step_by(2).rev() {
+            assert_eq!(tree.remove(&key), Some(key));
+            assert_eq!(tree.validate(), Ok(()));
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.height(), 0);
+    }
+
+    #[test]
+    fn test_matches_btree_map_under_random_operations() {
+        let mut rng = XorShift64::new(0xB7);
+        let mut tree: BTree<u64, usize, 3> = BTree::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..5000 {
+            let key = rng.below(400);
+            match rng.below(5) {
+                0..=2 => assert_eq!(tree.insert(key, step), expected.insert(key, step)),
+                _ => assert_eq!(tree.remove(&key), expected.remove(&key)),
+            }
+            if step % 200 == 0 {
+                assert_eq!(tree.validate(), Ok(()));
+            }
+            let probe = rng.below(420);
+            assert_eq!(tree.get(&probe), expected.get(&probe));
+        }
+        assert_eq!(tree.validate(), Ok(()));
+        assert!(tree.iter().eq(expected.iter()));
+        assert!(tree.range(100..300).eq(expected.range(100..300)));
+    }
+}
+
+struct Node<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    // Empty for leaves, otherwise one more than `keys`
+    children: Vec<Node<K, V>>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(capacity: usize) -> Self {
+        Node { keys: Vec::with_capacity(capacity), values: Vec::with_capacity(capacity), children: Vec::new() }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Index of `key` in this node, or of the child that would contain it.
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.binary_search_by(|probe| probe.borrow().cmp(key))
+    }
+}
+
+/// A B-tree map in which every node except the root holds between `B - 1`
+/// and `2B - 1` keys (`B` is the minimum degree), so a tree of `n` keys is
+/// at most log_B((n + 1) / 2) levels deep.
+///
+/// Keys and values live in per-node `Vec`s, so a lookup touches a few
+/// contiguous arrays rather than one allocation per key. Inserts split full
+/// nodes and removals borrow from or merge with siblings on the way down,
+/// so each operation makes a single pass from the root.
+pub struct BTree<K, V, const B: usize> {
+    root: Node<K, V>,
+    len: usize,
+}
+
+impl<K, V, const B: usize> BTree<K, V, B> {
+    const MAX_KEYS: usize = 2 * B - 1;
+
+    pub fn new() -> Self {
+        const { assert!(B >= 2, "a B-tree needs a minimum degree of at least 2") };
+        BTree { root: Node::new(Self::MAX_KEYS), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of levels; 0 when empty.
+    pub fn height(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let mut height = 1;
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            height += 1;
+            node = child;
+        }
+        height
+    }
+
+    pub fn clear(&mut self) {
+        *self = BTree::new();
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            node = child;
+        }
+        Some((node.keys.first()?, node.values.first()?))
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(child) = node.children.last() {
+            node = child;
+        }
+        Some((node.keys.last()?, node.values.last()?))
+    }
+
+    /// Iterates the entries in key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_leftmost(&self.root);
+        iter
+    }
+
+    /// Splits the full child `i` of `node` around its median key.
+    fn split_child(node: &mut Node<K, V>, i: usize) {
+        let child = &mut node.children[i];
+        let mut right = Node::new(Self::MAX_KEYS);
+        right.keys = child.keys.split_off(B);
+        right.values = child.values.split_off(B);
+        if !child.is_leaf() {
+            right.children = child.children.split_off(B);
+        }
+        let (key, value) = (child.keys.pop().expect("child is full"), child.values.pop().expect("child is full"));
+        node.keys.insert(i, key);
+        node.values.insert(i, value);
+        node.children.insert(i + 1, right);
+    }
+
+    /// Makes sure child `i` of `node` has at least `B` keys before a removal
+    /// descends into it, returning the child's possibly shifted index.
+    fn fill_child(node: &mut Node<K, V>, i: usize) -> usize {
+        if node.children[i].keys.len() >= B {
+            return i;
+        }
+        if i > 0 && node.children[i - 1].keys.len() >= B {
+            // Rotate right through the separator
+            let (left, right) = node.children.split_at_mut(i);
+            let (left, child) = (&mut left[i - 1], &mut right[0]);
+            let key = mem::replace(&mut node.keys[i - 1], left.keys.pop().expect("left has keys"));
+            let value = mem::replace(&mut node.values[i - 1], left.values.pop().expect("left has keys"));
+            child.keys.insert(0, key);
+            child.values.insert(0, value);
+            if let Some(grandchild) = left.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+            i
+        } else if i + 1 < node.children.len() && node.children[i + 1].keys.len() >= B {
+            // Rotate left through the separator
+            let (left, right) = node.children.split_at_mut(i + 1);
+            let (child, right) = (&mut left[i], &mut right[0]);
+            let key = mem::replace(&mut node.keys[i], right.keys.remove(0));
+            let value = mem::replace(&mut node.values[i], right.values.remove(0));
+            child.keys.push(key);
+            child.values.push(value);
+            if !right.is_leaf() {
+                child.children.push(right.children.remove(0));
+            }
+            i
+        } else if i + 1 < node.children.len() {
+            Self::merge_children(node, i);
+            i
+        } else {
+            Self::merge_children(node, i - 1);
+            i - 1
+        }
+    }
+
+    /// Merges child `i + 1` and the separator between them into child `i`.
+    fn merge_children(node: &mut Node<K, V>, i: usize) {
+        let right = node.children.remove(i + 1);
+        let left = &mut node.children[i];
+        left.keys.push(node.keys.remove(i));
+        left.values.push(node.values.remove(i));
+        left.keys.extend(right.keys);
+        left.values.extend(right.values);
+        left.children.extend(right.children);
+    }
+
+    fn remove_last(node: &mut Node<K, V>) -> (K, V) {
+        if node.is_leaf() {
+            return (node.keys.pop().expect("node has keys"), node.values.pop().expect("node has keys"));
+        }
+        let i = Self::fill_child(node, node.children.len() - 1);
+        Self::remove_last(&mut node.children[i])
+    }
+
+    fn remove_first(node: &mut Node<K, V>) -> (K, V) {
+        if node.is_leaf() {
+            return (node.keys.remove(0), node.values.remove(0));
+        }
+        let i = Self::fill_child(node, 0);
+        Self::remove_first(&mut node.children[i])
+    }
+}
+
+impl<K: Ord, V, const B: usize> BTree<K, V, B> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.root.keys.len() == Self::MAX_KEYS {
+            let old_root = mem::replace(&mut self.root, Node::new(Self::MAX_KEYS));
+            self.root.children.push(old_root);
+            Self::split_child(&mut self.root, 0);
+        }
+        let mut node = &mut self.root;
+        loop {
+            let i = match node.search(&key) {
+                Ok(i) => return Some(mem::replace(&mut node.values[i], value)),
+                Err(i) => i,
+            };
+            if node.is_leaf() {
+                node.keys.insert(i, key);
+                node.values.insert(i, value);
+                self.len += 1;
+                return None;
+            }
+            let mut i = i;
+            if node.children[i].keys.len() == Self::MAX_KEYS {
+                Self::split_child(node, i);
+                match key.cmp(&node.keys[i]) {
+                    std::cmp::Ordering::Equal => return Some(mem::replace(&mut node.values[i], value)),
+                    std::cmp::Ordering::Greater => i += 1,
+                    std::cmp::Ordering::Less => {}
+                }
+            }
+            node = &mut node.children[i];
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let removed = Self::remove_from(&mut self.root, key);
+        if self.root.keys.is_empty() && !self.root.is_leaf() {
+            self.root = self.root.children.pop().expect("root has a child");
+        }
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    fn remove_from<Q>(node: &mut Node<K, V>, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match node.search(key) {
+            Ok(i) if node.is_leaf() => {
+                node.keys.remove(i);
+                Some(node.values.remove(i))
+            }
+            Ok(i) => {
+                // Swap in the predecessor or successor from a child that can spare a key
+                if node.children[i].keys.len() >= B {
+                    let (key, value) = Self::remove_last(&mut node.children[i]);
+                    node.keys[i] = key;
+                    Some(mem::replace(&mut node.values[i], value))
+                } else if node.children[i + 1].keys.len() >= B {
+                    let (key, value) = Self::remove_first(&mut node.children[i + 1]);
+                    node.keys[i] = key;
+                    Some(mem::replace(&mut node.values[i], value))
+                } else {
+                    Self::merge_children(node, i);
+                    Self::remove_from(&mut node.children[i], key)
+                }
+            }
+            Err(_) if node.is_leaf() => None,
+            Err(i) => {
+                let i = Self::fill_child(node, i);
+                Self::remove_from(&mut node.children[i], key)
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = &self.root;
+        loop {
+            match node.search(key) {
+                Ok(i) => return Some(&node.values[i]),
+                Err(i) => node = node.children.get(i)?,
+            }
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = &mut self.root;
+        loop {
+            match node.search(key) {
+                Ok(i) => return Some(&mut node.values[i]),
+                Err(i) => node = node.children.get_mut(i)?,
+            }
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Iterates the entries whose keys fall in `range`, in key order.
+    pub fn range<'a, Q, R>(&'a self, range: R) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized + 'a,
+        R: RangeBounds<Q> + 'a,
+    {
+        // Seed the stack with the path to the first key at or after the start
+        let mut iter = Iter { stack: Vec::new() };
+        let mut node = Some(&self.root);
+        while let Some(n) = node {
+            let skip = match range.start_bound() {
+                Bound::Included(start) => n.keys.partition_point(|k| k.borrow() < start),
+                Bound::Excluded(start) => n.keys.partition_point(|k| k.borrow() <= start),
+                Bound::Unbounded => 0,
+            };
+            iter.stack.push((n, skip));
+            node = n.children.get(skip);
+        }
+        iter.take_while(move |(key, _)| match range.end_bound() {
+            Bound::Included(end) => (*key).borrow() <= end,
+            Bound::Excluded(end) => (*key).borrow() < end,
+            Bound::Unbounded => true,
+        })
+    }
+
+    /// Checks that keys are strictly increasing, that every non-root node
+    /// holds between `B - 1` and `2B - 1` keys, that internal nodes have one
+    /// more child than keys, and that all leaves are at the same depth.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        fn check<K, V, const B: usize>(node: &Node<K, V>, is_root: bool) -> Result<usize, InvariantError> {
+            if node.keys.len() > 2 * B - 1 {
+                return Err(InvariantError::Overfull);
+            }
+            if !is_root && node.keys.len() < B - 1 {
+                return Err(InvariantError::Underfull);
+            }
+            if node.keys.len() != node.values.len()
+                || (!node.is_leaf() && node.children.len() != node.keys.len() + 1)
+            {
+                return Err(InvariantError::WrongChildCount);
+            }
+            let mut depths = node.children.iter().map(|child| check::<K, V, B>(child, false));
+            let depth = depths.next().transpose()?.unwrap_or(0);
+            for other in depths {
+                if other? != depth {
+                    return Err(InvariantError::UnevenDepth);
+                }
+            }
+            Ok(depth + 1)
+        }
+        check::<K, V, B>(&self.root, true)?;
+        let keys: Vec<&K> = self.iter().map(|(key, _)| key).collect();
+        if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(InvariantError::Unordered);
+        }
+        if keys.len() != self.len {
+            return Err(InvariantError::WrongLength { expected: self.len, found: keys.len() });
+        }
+        Ok(())
+    }
+}
+
+/// In-order iterator over a [`BTree`]: a stack of nodes with the index of
+/// the next key to yield from each.
+pub struct Iter<'a, K, V> {
+    stack: Vec<(&'a Node<K, V>, usize)>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_leftmost(&mut self, mut node: &'a Node<K, V>) {
+        loop {
+            self.stack.push((node, 0));
+            match node.children.first() {
+                Some(child) => node = child,
+                None => return,
+            }
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        loop {
+            let (node, next) = self.stack.last_mut()?;
+            let node: &'a Node<K, V> = node;
+            if *next < node.keys.len() {
+                let i = *next;
+                *next += 1;
+                if let Some(child) = node.children.get(i + 1) {
+                    self.push_leftmost(child);
+                }
+                return Some((&node.keys[i], &node.values[i]));
+            }
+            self.stack.pop();
+        }
+    }
+}
+
+impl<K, V, const B: usize> Default for BTree<K, V, B> {
+    fn default() -> Self {
+        BTree::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, const B: usize> fmt::Debug for BTree<K, V, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V, const B: usize> Extend<(K, V)> for BTree<K, V, B> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V, const B: usize> FromIterator<(K, V)> for BTree<K, V, B> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
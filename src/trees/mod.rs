@@ -0,0 +1,80 @@
+//! Balanced search trees keeping keys in order.
+//!
+//! The binary trees share their read-only walks (lookup, `first`/`last`,
+//! `floor`/`ceiling` and in-order iteration) through the `BinaryNode` trait
+//! in `node`; each tree only implements its own rebalancing. `btree` and
+//! `bplus_tree` hold many keys per node and take the branching factor as a
+//! const generic. `treap`, `splay` and `skip_list` add randomized and
+//! self-adjusting alternatives, and every map implements the shared
+//! `OrderedMap` trait from `ordered_map`.
+
+use std::fmt;
+
+pub mod avl;
+pub mod bplus_tree;
+pub mod btree;
+mod node;
+pub mod order_statistic;
+pub mod ordered_map;
+pub mod red_black;
+pub mod skip_list;
+pub mod splay;
+pub mod treap;
+
+/// A broken structural invariant reported by a tree's `validate` method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantError {
+    /// An in-order walk does not visit the keys in strictly increasing order.
+    Unordered,
+    /// The number of reachable entries differs from the stored length.
+    WrongLength { expected: usize, found: usize },
+    /// A node's cached height differs from its subtree's real height.
+    WrongHeight,
+    /// A node's cached subtree size differs from its subtree's real size.
+    WrongSize,
+    /// The heights of a node's subtrees differ by more than one.
+    Unbalanced,
+    /// A non-root node holds fewer keys than the minimum fill.
+    Underfull,
+    /// A node holds more keys than fit.
+    Overfull,
+    /// An internal node's child count does not match its key count.
+    WrongChildCount,
+    /// Leaves sit at different depths.
+    UnevenDepth,
+    /// The linked list of B+tree leaves skips, repeats or reorders leaves.
+    BrokenLeafChain,
+    /// The root of a red-black tree is red.
+    RedRoot,
+    /// A left-leaning red-black tree has a red right link.
+    RedRightLink,
+    /// A red node has a red child.
+    DoubleRed,
+    /// Two root-to-leaf paths pass through different numbers of black nodes.
+    BlackHeight,
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvariantError::Unordered => write!(f, "keys are out of order"),
+            InvariantError::WrongLength { expected, found } => {
+                write!(f, "expected {} entries but found {}", expected, found)
+            }
+            InvariantError::WrongHeight => write!(f, "a cached subtree height is wrong"),
+            InvariantError::WrongSize => write!(f, "a cached subtree size is wrong"),
+            InvariantError::Unbalanced => write!(f, "subtree heights differ by more than one"),
+            InvariantError::Underfull => write!(f, "a node is below the minimum fill"),
+            InvariantError::Overfull => write!(f, "a node is over capacity"),
+            InvariantError::WrongChildCount => write!(f, "a node's child count does not match its keys"),
+            InvariantError::UnevenDepth => write!(f, "leaves are at different depths"),
+            InvariantError::BrokenLeafChain => write!(f, "the leaf chain does not match the tree"),
+            InvariantError::RedRoot => write!(f, "the root is red"),
+            InvariantError::RedRightLink => write!(f, "a right link is red"),
+            InvariantError::DoubleRed => write!(f, "a red node has a red child"),
+            InvariantError::BlackHeight => write!(f, "paths have different black heights"),
+        }
+    }
+}
+
+impl std::error::Error for InvariantError {}
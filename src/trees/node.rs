@@ -0,0 +1,490 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::mem;
+use std::ops::Bound;
+use std::ptr;
+
+/// Read-only access to a binary search tree node, shared by the trees'
+/// lookups and iterators.
+pub(super) trait BinaryNode {
+    type Key;
+    type Value;
+
+    fn key(&self) -> &Self::Key;
+    fn value(&self) -> &Self::Value;
+    fn left(&self) -> Option<&Self>;
+    fn right(&self) -> Option<&Self>;
+
+    fn entry(&self) -> (&Self::Key, &Self::Value) {
+        (self.key(), self.value())
+    }
+}
+
+/// Mutable access to a node that owns its children.
+pub(super) trait BinaryNodeMut: BinaryNode + Sized {
+    fn left_mut(&mut self) -> &mut Option<Box<Self>>;
+    fn right_mut(&mut self) -> &mut Option<Box<Self>>;
+}
+
+/// A node of an AVL tree. It caches its height, plus any other summary of
+/// its subtree that `update` recomputes. The rotation and rebalancing below
+/// keep those caches up to date.
+pub(super) trait AvlNode: BinaryNodeMut {
+    fn height(&self) -> u32;
+    /// Recomputes the cached height and summaries from the children.
+    fn update(&mut self);
+}
+
+pub(super) fn height<N: AvlNode>(node: Option<&N>) -> u32 {
+    node.map_or(0, N::height)
+}
+
+/// Left height minus right height.
+fn balance_factor<N: AvlNode>(node: &N) -> i64 {
+    height(node.left()) as i64 - height(node.right()) as i64
+}
+
+fn rotate_right<N: AvlNode>(mut node: Box<N>) -> Box<N> {
+    let mut left = node.left_mut().take().expect("rotating right needs a left child");
+    *node.left_mut() = left.right_mut().take();
+    node.update();
+    *left.right_mut() = Some(node);
+    left.update();
+    left
+}
+
+fn rotate_left<N: AvlNode>(mut node: Box<N>) -> Box<N> {
+    let mut right = node.right_mut().take().expect("rotating left needs a right child");
+    *node.right_mut() = right.left_mut().take();
+    node.update();
+    *right.left_mut() = Some(node);
+    right.update();
+    right
+}
+
+/// Refreshes `node`'s caches and restores the AVL property at it, given
+/// that its subtrees are balanced and differ in height by at most two.
+pub(super) fn rebalance<N: AvlNode>(mut node: Box<N>) -> Box<N> {
+    node.update();
+    let balance = balance_factor(&*node);
+    if balance > 1 {
+        if node.left().is_some_and(|left| balance_factor(left) < 0) {
+            *node.left_mut() = node.left_mut().take().map(rotate_left);
+        }
+        rotate_right(node)
+    } else if balance < -1 {
+        if node.right().is_some_and(|right| balance_factor(right) > 0) {
+            *node.right_mut() = node.right_mut().take().map(rotate_right);
+        }
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+/// Takes `node`'s children and joins them into the balanced subtree that
+/// replaces it, promoting the in-order successor when there are two.
+pub(super) fn splice_out<N: AvlNode>(node: &mut N) -> Option<Box<N>> {
+    match (node.left_mut().take(), node.right_mut().take()) {
+        (None, child) | (child, None) => child,
+        (left, mut right) => {
+            let mut successor = remove_first(&mut right);
+            *successor.left_mut() = left;
+            *successor.right_mut() = right;
+            Some(rebalance(successor))
+        }
+    }
+}
+
+/// Detaches the leftmost node of a non-empty subtree, rebalancing the path
+/// above it.
+fn remove_first<N: AvlNode>(link: &mut Option<Box<N>>) -> Box<N> {
+    let node = link.as_mut().expect("subtree is not empty");
+    if node.left().is_some() {
+        let first = remove_first(node.left_mut());
+        *link = link.take().map(rebalance);
+        first
+    } else {
+        let mut node = link.take().expect("checked above");
+        *link = node.right_mut().take();
+        node
+    }
+}
+
+pub(super) fn find<'a, N, Q>(mut node: Option<&'a N>, key: &Q) -> Option<&'a N>
+where
+    N: BinaryNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    while let Some(n) = node {
+        node = match key.cmp(n.key().borrow()) {
+            Ordering::Less => n.left(),
+            Ordering::Greater => n.right(),
+            Ordering::Equal => return Some(n),
+        };
+    }
+    None
+}
+
+pub(super) fn first<N: BinaryNode>(mut node: &N) -> &N {
+    while let Some(left) = node.left() {
+        node = left;
+    }
+    node
+}
+
+pub(super) fn last<N: BinaryNode>(mut node: &N) -> &N {
+    while let Some(right) = node.right() {
+        node = right;
+    }
+    node
+}
+
+/// The node with the greatest key `<= key`.
+pub(super) fn floor<'a, N, Q>(mut node: Option<&'a N>, key: &Q) -> Option<&'a N>
+where
+    N: BinaryNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut best = None;
+    while let Some(n) = node {
+        match key.cmp(n.key().borrow()) {
+            Ordering::Less => node = n.left(),
+            Ordering::Greater => {
+                best = Some(n);
+                node = n.right();
+            }
+            Ordering::Equal => return Some(n),
+        }
+    }
+    best
+}
+
+/// The node with the least key `>= key`.
+pub(super) fn ceiling<'a, N, Q>(mut node: Option<&'a N>, key: &Q) -> Option<&'a N>
+where
+    N: BinaryNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut best = None;
+    while let Some(n) = node {
+        match key.cmp(n.key().borrow()) {
+            Ordering::Less => {
+                best = Some(n);
+                node = n.left();
+            }
+            Ordering::Greater => node = n.right(),
+            Ordering::Equal => return Some(n),
+        }
+    }
+    best
+}
+
+/// Which child a step down the tree takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Side {
+    Left,
+    Right,
+}
+
+fn child<N: BinaryNode>(node: &N, side: Side) -> Option<&N> {
+    match side {
+        Side::Left => node.left(),
+        Side::Right => node.right(),
+    }
+}
+
+fn child_mut<N: BinaryNodeMut>(node: &mut N, side: Side) -> Option<&mut N> {
+    match side {
+        Side::Left => node.left_mut().as_deref_mut(),
+        Side::Right => node.right_mut().as_deref_mut(),
+    }
+}
+
+// Insertion fix-ups relink at most the top three nodes of a subtree (an AVL
+// double rotation) and leave everything from the third level down intact,
+// moving those subtrees at most one level deeper
+const UNTOUCHED_DEPTH: usize = 3;
+
+/// The steps from a subtree's root down to one of its nodes, stored deepest
+/// first so that each ancestor adds its own step with `push` on the way back
+/// up a recursive insert.
+#[derive(Debug, Default)]
+pub(super) struct Path {
+    steps: Vec<Side>,
+}
+
+impl Path {
+    /// Extends the path up by one level, to start at the parent of its root.
+    pub(super) fn push(&mut self, side: Side) {
+        self.steps.push(side);
+    }
+
+    /// Runs `fix_up` on the path's root and rewrites the path to lead to the
+    /// same node in the restructured subtree.
+    ///
+    /// The node `UNTOUCHED_DEPTH` steps down the old path (or the target
+    /// itself, if it is shallower) roots a subtree the fix-up leaves alone,
+    /// so it is found again by address near the new root and the rest of the
+    /// path still applies below it.
+    pub(super) fn track<N: BinaryNode>(&mut self, node: Box<N>, fix_up: impl FnOnce(Box<N>) -> Box<N>) -> Box<N> {
+        let old_root: *const N = &*node;
+        let kept = self.steps.len().min(UNTOUCHED_DEPTH);
+        let mut anchor = &*node;
+        for &side in self.steps.iter().rev().take(kept) {
+            anchor = child(anchor, side).expect("path leads to a node");
+        }
+        let anchor: *const N = anchor;
+        let node = fix_up(node);
+        if !ptr::eq(old_root, &*node) {
+            let mut prefix = Vec::new();
+            assert!(find_by_address(&*node, anchor, UNTOUCHED_DEPTH + 1, &mut prefix), "fix-up moved a subtree too far");
+            self.steps.truncate(self.steps.len() - kept);
+            self.steps.extend(prefix.into_iter().rev());
+        }
+        node
+    }
+}
+
+/// Searches the top `depth` levels below `node` for the node at `target`,
+/// leaving the steps to it in `path`, root first.
+fn find_by_address<N: BinaryNode>(node: &N, target: *const N, depth: usize, path: &mut Vec<Side>) -> bool {
+    if ptr::eq(node, target) {
+        return true;
+    }
+    if depth == 0 {
+        return false;
+    }
+    for side in [Side::Left, Side::Right] {
+        if let Some(child) = child(node, side) {
+            path.push(side);
+            if find_by_address(child, target, depth - 1, path) {
+                return true;
+            }
+            path.pop();
+        }
+    }
+    false
+}
+
+/// The path from `node` down to the node holding `key`.
+pub(super) fn find_path<N, Q>(mut node: Option<&N>, key: &Q) -> Option<Path>
+where
+    N: BinaryNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut steps = Vec::new();
+    while let Some(n) = node {
+        let side = match key.cmp(n.key().borrow()) {
+            Ordering::Less => Side::Left,
+            Ordering::Greater => Side::Right,
+            Ordering::Equal => {
+                steps.reverse();
+                return Some(Path { steps });
+            }
+        };
+        steps.push(side);
+        node = child(n, side);
+    }
+    None
+}
+
+pub(super) fn follow<'a, N: BinaryNode>(mut node: &'a N, path: &Path) -> &'a N {
+    for &side in path.steps.iter().rev() {
+        node = child(node, side).expect("path leads to a node");
+    }
+    node
+}
+
+pub(super) fn follow_mut<'a, N: BinaryNodeMut>(mut node: &'a mut N, path: &Path) -> &'a mut N {
+    for &side in path.steps.iter().rev() {
+        node = child_mut(node, side).expect("path leads to a node");
+    }
+    node
+}
+
+/// The map operations behind [`Entry`].
+pub(super) trait EntryMap<K, V> {
+    /// The path from the root to the node holding `key`, if there is one.
+    fn find_path(&self, key: &K) -> Option<Path>;
+    fn value_at(&self, path: &Path) -> &V;
+    fn value_at_mut(&mut self, path: &Path) -> &mut V;
+    /// Inserts a key that is not in the map and returns the path to its node.
+    fn insert_absent(&mut self, key: K, value: V) -> Path;
+    /// Removes a key that is in the map.
+    fn remove_present(&mut self, key: &K) -> V;
+}
+
+/// A view into a single entry of an [`AvlTreeMap`](super::avl::AvlTreeMap)
+/// or a [`RedBlackTreeMap`](super::red_black::RedBlackTreeMap), which may be
+/// vacant or occupied.
+///
+/// Looking up the entry records the path to its node, so reading or
+/// replacing an occupied value follows that path without comparing keys.
+/// Inserting into a vacant entry tracks the new node through the rotations
+/// that rebalance the tree. Removing an occupied entry searches again, since
+/// removal restructures the tree on the way down.
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+    map: &'a mut dyn EntryMap<K, V>,
+    key: K,
+    path: Path,
+}
+
+pub struct VacantEntry<'a, K, V> {
+    map: &'a mut dyn EntryMap<K, V>,
+    key: K,
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub(super) fn new(map: &'a mut dyn EntryMap<K, V>, key: K) -> Self {
+        match map.find_path(&key) {
+            Some(path) => Entry::Occupied(OccupiedEntry { map, key, path }),
+            None => Entry::Vacant(VacantEntry { map, key }),
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with(self, default: impl FnOnce() -> V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn and_modify(mut self, f: impl FnOnce(&mut V)) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V: Default> Entry<'a, K, V> {
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get(&self) -> &V {
+        self.map.value_at(&self.path)
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.map.value_at_mut(&self.path)
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        self.map.value_at_mut(&self.path)
+    }
+
+    /// Replaces the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> V {
+        self.map.remove_present(&self.key)
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        let path = self.map.insert_absent(self.key, value);
+        self.map.value_at_mut(&path)
+    }
+}
+
+/// In-order traversal with an explicit stack of the left spine still to
+/// visit, so it uses O(height) memory.
+pub(super) struct InOrder<'a, N> {
+    stack: Vec<&'a N>,
+}
+
+impl<'a, N: BinaryNode> InOrder<'a, N> {
+    pub(super) fn new(root: Option<&'a N>) -> Self {
+        let mut iter = InOrder { stack: Vec::new() };
+        iter.push_left_spine(root);
+        iter
+    }
+
+    /// Starts at the first node whose key satisfies the lower bound `start`.
+    pub(super) fn starting_at<Q>(mut node: Option<&'a N>, start: Bound<&Q>) -> Self
+    where
+        N::Key: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut stack = Vec::new();
+        while let Some(n) = node {
+            let above = match start {
+                Bound::Included(start) => n.key().borrow() >= start,
+                Bound::Excluded(start) => n.key().borrow() > start,
+                Bound::Unbounded => true,
+            };
+            if above {
+                stack.push(n);
+                node = n.left();
+            } else {
+                node = n.right();
+            }
+        }
+        InOrder { stack }
+    }
+
+    fn push_left_spine(&mut self, mut node: Option<&'a N>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = n.left();
+        }
+    }
+}
+
+impl<'a, N: BinaryNode> Iterator for InOrder<'a, N> {
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<&'a N> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right());
+        Some(node)
+    }
+}
+
+/// Whether `key` is within the upper bound `end`.
+pub(super) fn below_end<K, Q>(key: &K, end: Bound<&Q>) -> bool
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    match end {
+        Bound::Included(end) => key.borrow() <= end,
+        Bound::Excluded(end) => key.borrow() < end,
+        Bound::Unbounded => true,
+    }
+}
@@ -0,0 +1,435 @@
+use super::node::{self, AvlNode, BinaryNode, BinaryNodeMut, InOrder};
+use super::InvariantError;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter;
+use std::ops::{Bound, RangeBounds};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    #[test]
+    fn test_rank_and_select_with_duplicates() {
+        let tree: OrderStatisticTree<u32> = [50, 20, 80, 20, 60, 20].into_iter().collect();
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.count(&20), 3);
+        assert_eq!(tree.rank(&20), 0);
+        assert_eq!(tree.rank(&50), 3);
+        assert_eq!(tree.rank(&55), 4);
+        assert_eq!(tree.rank(&100), 6);
+        assert_eq!((0..6).map(|k| *tree.select(k).unwrap()).collect::<Vec<_>>(), vec![20, 20, 20, 50, 60, 80]);
+        assert_eq!(tree.select(6), None);
+    }
+
+    #[test]
+    fn test_count_range() {
+        let tree: OrderStatisticTree<i32> = [1, 3, 3, 5, 7, 9].into_iter().collect();
+        assert_eq!(tree.count_range(3..7), 3);
+        assert_eq!(tree.count_range(3..=7), 4);
+        assert_eq!(tree.count_range((Bound::Excluded(3), Bound::Unbounded)), 3);
+        assert_eq!(tree.count_range(..), 6);
+        assert_eq!(tree.count_range((Bound::Included(8), Bound::Excluded(4))), 0);
+    }
+
+    #[test]
+    fn test_binary_search_matches_sorted_slice() {
+        let values = [2, 4, 4, 4, 8, 16];
+        let tree: OrderStatisticTree<i32> = values.into_iter().collect();
+        for probe in 0..20 {
+            let expected = values.binary_search(&probe).map(|_| values.partition_point(|&v| v < probe));
+            assert_eq!(tree.binary_search(&probe), expected);
+        }
+    }
+
+    #[test]
+    fn test_remove_one_and_all() {
+        let mut tree: OrderStatisticTree<char> = "mississippi".chars().collect();
+        assert!(tree.remove(&'s'));
+        assert_eq!(tree.count(&'s'), 3);
+        assert_eq!(tree.remove_all(&'i'), 4);
+        assert!(!tree.remove(&'i'));
+        assert_eq!(tree.iter().collect::<String>(), "mppsss");
+        assert_eq!(tree.iter_counts().collect::<Vec<_>>(), vec![(&'m', 1), (&'p', 2), (&'s', 3)]);
+        assert_eq!((tree.first(), tree.last()), (Some(&'m'), Some(&'s')));
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        // Scores sorted descending by storing them reversed
+        let mut board = OrderStatisticTree::new();
+        for score in [120, 300, 250, 300, 90] {
+            board.insert(std::cmp::Reverse(score));
+        }
+        assert_eq!(board.rank(&std::cmp::Reverse(250)) + 1, 3);
+        assert_eq!(board.select(0), Some(&std::cmp::Reverse(300)));
+        assert_eq!(board.select(4), Some(&std::cmp::Reverse(90)));
+    }
+
+    #[test]
+    fn test_matches_sorted_vec_under_random_operations() {
+        let mut rng = XorShift64::new(0x057);
+        let mut tree = OrderStatisticTree::new();
+        let mut sorted: Vec<u64> = Vec::new();
+        for step in 0..3000 {
+            let value = rng.below(100);
+            if rng.below(3) < 2 {
+                tree.insert(value);
+                let at = sorted.partition_point(|&v| v <= value);
+                sorted.insert(at, value);
+            } else {
+                let present = sorted.binary_search(&value).is_ok();
+                assert_eq!(tree.remove(&value), present);
+                if present {
+                    let at = sorted.partition_point(|&v| v < value);
+                    sorted.remove(at);
+                }
+            }
+            if step % 100 == 0 {
+                assert_eq!(tree.validate(), Ok(()));
+            }
+            let probe = rng.below(110);
+            assert_eq!(tree.rank(&probe), sorted.partition_point(|&v| v < probe));
+            let k = rng.below(sorted.len() as u64 + 1) as usize;
+            assert_eq!(tree.select(k), sorted.get(k));
+            let (lo, hi) = (rng.below(110), rng.below(110));
+            assert_eq!(tree.count_range(lo..hi), sorted.iter().filter(|&&v| lo <= v && v < hi).count());
+        }
+        assert_eq!(tree.len(), sorted.len());
+        assert!(tree.iter().eq(sorted.iter()));
+        assert_eq!(tree.validate(), Ok(()));
+    }
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    // Occurrences of `value`
+    count: usize,
+    // Occurrences of every value in this subtree
+    size: usize,
+    height: u32,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> BinaryNode for Node<T> {
+    type Key = T;
+    type Value = usize;
+
+    fn key(&self) -> &T {
+        &self.value
+    }
+
+    fn value(&self) -> &usize {
+        &self.count
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+impl<T> BinaryNodeMut for Node<T> {
+    fn left_mut(&mut self) -> &mut Link<T> {
+        &mut self.left
+    }
+
+    fn right_mut(&mut self) -> &mut Link<T> {
+        &mut self.right
+    }
+}
+
+impl<T> AvlNode for Node<T> {
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn update(&mut self) {
+        self.height = 1 + node::height(self.left()).max(node::height(self.right()));
+        self.size = size(&self.left) + self.count + size(&self.right);
+    }
+}
+
+fn insert<T: Ord>(link: Link<T>, value: T) -> Box<Node<T>> {
+    let Some(mut node) = link else {
+        return Box::new(Node { value, count: 1, size: 1, height: 1, left: None, right: None });
+    };
+    match value.cmp(&node.value) {
+        Ordering::Less => node.left = Some(insert(node.left.take(), value)),
+        Ordering::Greater => node.right = Some(insert(node.right.take(), value)),
+        Ordering::Equal => node.count += 1,
+    }
+    node::rebalance(node)
+}
+
+/// Removes one occurrence of `value`, or all of them if `all` is set, and
+/// returns how many were removed.
+fn remove<T, Q>(link: &mut Link<T>, value: &Q, all: bool) -> usize
+where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let Some(node) = link.as_mut() else {
+        return 0;
+    };
+    let removed = match value.cmp(node.value.borrow()) {
+        Ordering::Less => remove(&mut node.left, value, all),
+        Ordering::Greater => remove(&mut node.right, value, all),
+        Ordering::Equal if node.count > 1 && !all => {
+            node.count -= 1;
+            1
+        }
+        Ordering::Equal => {
+            let mut node = link.take().expect("checked above");
+            *link = node::splice_out(&mut *node);
+            return node.count;
+        }
+    };
+    if removed > 0 {
+        *link = link.take().map(node::rebalance);
+    }
+    removed
+}
+
+/// A sorted multiset answering rank and selection queries in O(log n).
+///
+/// An AVL tree in which each node stores one distinct value with its
+/// multiplicity and the total multiplicity of its subtree. Positions count
+/// duplicates, so the tree behaves like a sorted `Vec` kept up to date with
+/// `binary_search` + `insert`, but with O(log n) updates.
+pub struct OrderStatisticTree<T> {
+    root: Link<T>,
+}
+
+impl<T> OrderStatisticTree<T> {
+    pub fn new() -> Self {
+        OrderStatisticTree { root: None }
+    }
+
+    /// Number of elements, counting duplicates.
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.root.as_deref().map(|root| &node::first(root).value)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.root.as_deref().map(|root| &node::last(root).value)
+    }
+
+    /// The element at position `k` in sorted order, counting duplicates.
+    pub fn select(&self, mut k: usize) -> Option<&T> {
+        let mut link = &self.root;
+        while let Some(node) = link {
+            let left = size(&node.left);
+            if k < left {
+                link = &node.left;
+            } else if k < left + node.count {
+                return Some(&node.value);
+            } else {
+                k -= left + node.count;
+                link = &node.right;
+            }
+        }
+        None
+    }
+
+    /// Iterates the elements in sorted order, repeating duplicates.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter_counts().flat_map(|(value, count)| iter::repeat_n(value, count))
+    }
+
+    /// Iterates the distinct elements in sorted order with their counts.
+    pub fn iter_counts(&self) -> impl Iterator<Item = (&T, usize)> + '_ {
+        InOrder::new(self.root.as_deref()).map(|node| (&node.value, node.count))
+    }
+}
+
+impl<T: Ord> OrderStatisticTree<T> {
+    /// Adds one occurrence of `value`.
+    pub fn insert(&mut self, value: T) {
+        self.root = Some(insert(self.root.take(), value));
+    }
+
+    /// Removes one occurrence of `value`, returning whether there was one.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        remove(&mut self.root, value, false) > 0
+    }
+
+    /// Removes every occurrence of `value`, returning how many there were.
+    pub fn remove_all<Q>(&mut self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        remove(&mut self.root, value, true)
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count(value) > 0
+    }
+
+    /// Number of occurrences of `value`.
+    pub fn count<Q>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::find(self.root.as_deref(), value).map_or(0, |node| node.count)
+    }
+
+    /// Number of elements strictly less than `value`: the position of its
+    /// first occurrence, or where it would be inserted.
+    pub fn rank<Q>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_below(value, false)
+    }
+
+    /// Like `slice::binary_search` on the sorted elements, except that a
+    /// found value always reports its first occurrence.
+    pub fn binary_search<Q>(&self, value: &Q) -> Result<usize, usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let rank = self.rank(value);
+        if self.contains(value) {
+            Ok(rank)
+        } else {
+            Err(rank)
+        }
+    }
+
+    /// Number of elements, counting duplicates, that fall in `range`.
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let below_start = match range.start_bound() {
+            Bound::Included(start) => self.count_below(start, false),
+            Bound::Excluded(start) => self.count_below(start, true),
+            Bound::Unbounded => 0,
+        };
+        let below_end = match range.end_bound() {
+            Bound::Included(end) => self.count_below(end, true),
+            Bound::Excluded(end) => self.count_below(end, false),
+            Bound::Unbounded => self.len(),
+        };
+        below_end.saturating_sub(below_start)
+    }
+
+    /// Number of elements less than `value`, or less than or equal to it
+    /// when `inclusive` is set.
+    fn count_below<Q>(&self, value: &Q, inclusive: bool) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut below = 0;
+        let mut link = &self.root;
+        while let Some(node) = link {
+            match value.cmp(node.value.borrow()) {
+                Ordering::Less => link = &node.left,
+                Ordering::Greater => {
+                    below += size(&node.left) + node.count;
+                    link = &node.right;
+                }
+                Ordering::Equal => {
+                    return below + size(&node.left) + if inclusive { node.count } else { 0 };
+                }
+            }
+        }
+        below
+    }
+
+    /// Checks the BST ordering, the AVL balance and every cached height and
+    /// subtree size.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        fn check<T>(link: &Link<T>) -> Result<(u32, usize), InvariantError> {
+            let Some(node) = link else {
+                return Ok((0, 0));
+            };
+            let (left_height, left_size) = check(&node.left)?;
+            let (right_height, right_size) = check(&node.right)?;
+            if node.height != 1 + left_height.max(right_height) {
+                return Err(InvariantError::WrongHeight);
+            }
+            if left_height.abs_diff(right_height) > 1 {
+                return Err(InvariantError::Unbalanced);
+            }
+            if node.count == 0 || node.size != left_size + node.count + right_size {
+                return Err(InvariantError::WrongSize);
+            }
+            Ok((node.height, node.size))
+        }
+        check(&self.root)?;
+        let values: Vec<&T> = self.iter_counts().map(|(value, _)| value).collect();
+        if values.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(InvariantError::Unordered);
+        }
+        Ok(())
+    }
+}
+
+impl<T> Default for OrderStatisticTree<T> {
+    fn default() -> Self {
+        OrderStatisticTree::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OrderStatisticTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> Extend<T> for OrderStatisticTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for OrderStatisticTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = OrderStatisticTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
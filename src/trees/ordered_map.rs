@@ -0,0 +1,291 @@
+use super::avl::AvlTreeMap;
+use super::bplus_tree::BPlusTree;
+use super::btree::BTree;
+use super::red_black::RedBlackTreeMap;
+use super::skip_list::SkipList;
+use super::splay::SplayTree;
+use super::treap::Treap;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+    use std::collections::BTreeMap;
+    use std::time::Instant;
+
+    /// Drives `map` through a random workload and checks every answer
+    /// against `BTreeMap`.
+    fn check_against_btree_map<M: OrderedMap<u64, u64>>(mut map: M) {
+        let mut rng = XorShift64::new(0x0DE4);
+        let mut expected = BTreeMap::new();
+        for step in 0..2000 {
+            let key = rng.below(300);
+            match rng.below(4) {
+                0 => assert_eq!(map.remove(&key), expected.remove(&key)),
+                1 => assert_eq!(map.get(&key), expected.get(&key)),
+                _ => assert_eq!(map.insert(key, step), expected.insert(key, step)),
+            }
+            assert_eq!(map.len(), expected.len());
+        }
+        assert!(map.iter().eq(expected.iter()));
+        assert_eq!(map.is_empty(), expected.is_empty());
+    }
+
+    #[test]
+    fn test_every_map_matches_btree_map() {
+        check_against_btree_map(Treap::new());
+        check_against_btree_map(SplayTree::new());
+        check_against_btree_map(SkipList::new());
+        check_against_btree_map(AvlTreeMap::new());
+        check_against_btree_map(RedBlackTreeMap::new());
+        check_against_btree_map(BTree::<_, _, 3>::new());
+        check_against_btree_map(BPlusTree::<_, _, 3>::new());
+    }
+
+    fn bench_map<M: OrderedMap<u64, u64>>(name: &str, mut map: M, keys: &[u64], probes: &[u64]) {
+        let start = Instant::now();
+        for &key in keys {
+            map.insert(key, key);
+        }
+        let build = start.elapsed();
+        let start = Instant::now();
+        let hits = probes.iter().filter(|&&key| map.get(&key).is_some()).count();
+        println!("{name:<16} build {build:>12?}  lookup {:>12?}  ({hits} hits)", start.elapsed());
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_ordered_maps_vs_sorted_vec() {
+        const KEYS: usize = 200_000;
+        let mut rng = XorShift64::new(0xBE4C);
+        let keys: Vec<u64> = (0..KEYS).map(|_| rng.next_u64() % (4 * KEYS as u64)).collect();
+        let probes: Vec<u64> = (0..KEYS).map(|_| rng.next_u64() % (4 * KEYS as u64)).collect();
+
+        // A sorted vector is built once and then only searched
+        let start = Instant::now();
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        let build = start.elapsed();
+        let start = Instant::now();
+        let hits = probes.iter().filter(|key| sorted.binary_search(key).is_ok()).count();
+        println!("{:<16} build {build:>12?}  lookup {:>12?}  ({hits} hits)", "sorted vec", start.elapsed());
+
+        bench_map("treap", Treap::new(), &keys, &probes);
+        bench_map("splay tree", SplayTree::new(), &keys, &probes);
+        bench_map("skip list", SkipList::new(), &keys, &probes);
+        bench_map("skip list p=1/4", SkipList::with_params(0.25, 16, 1), &keys, &probes);
+        bench_map("avl tree", AvlTreeMap::new(), &keys, &probes);
+        bench_map("red-black tree", RedBlackTreeMap::new(), &keys, &probes);
+        bench_map("b-tree B=16", BTree::<_, _, 16>::new(), &keys, &probes);
+        bench_map("b+tree B=16", BPlusTree::<_, _, 16>::new(), &keys, &probes);
+    }
+}
+
+/// The operations shared by the ordered maps in this module, so callers and
+/// benchmarks can be written once over any of them.
+///
+/// `get` takes `&mut self` because self-adjusting maps such as
+/// [`SplayTree`] restructure on every lookup; the other maps simply forward
+/// to their `&self` method.
+pub trait OrderedMap<K, V> {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    fn get(&mut self, key: &K) -> Option<&V>;
+
+    /// Iterates the entries in increasing key order.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a;
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for Treap<K, V> {
+    fn len(&self) -> usize {
+        Treap::len(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        Treap::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        Treap::remove(self, key)
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        Treap::get(self, key)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        Treap::iter(self)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for SplayTree<K, V> {
+    fn len(&self) -> usize {
+        SplayTree::len(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        SplayTree::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        SplayTree::remove(self, key)
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        SplayTree::get(self, key)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        SplayTree::iter(self)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for SkipList<K, V> {
+    fn len(&self) -> usize {
+        SkipList::len(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        SkipList::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        SkipList::remove(self, key)
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        SkipList::get(self, key)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        SkipList::iter(self)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for AvlTreeMap<K, V> {
+    fn len(&self) -> usize {
+        AvlTreeMap::len(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        AvlTreeMap::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        AvlTreeMap::remove(self, key)
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        AvlTreeMap::get(self, key)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        AvlTreeMap::iter(self)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> for RedBlackTreeMap<K, V> {
+    fn len(&self) -> usize {
+        RedBlackTreeMap::len(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        RedBlackTreeMap::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        RedBlackTreeMap::remove(self, key)
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        RedBlackTreeMap::get(self, key)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        RedBlackTreeMap::iter(self)
+    }
+}
+
+impl<K: Ord, V, const B: usize> OrderedMap<K, V> for BTree<K, V, B> {
+    fn len(&self) -> usize {
+        BTree::len(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTree::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTree::remove(self, key)
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        BTree::get(self, key)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        BTree::iter(self)
+    }
+}
+
+impl<K: Ord + Clone, V, const B: usize> OrderedMap<K, V> for BPlusTree<K, V, B> {
+    fn len(&self) -> usize {
+        BPlusTree::len(self)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BPlusTree::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BPlusTree::remove(self, key)
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        BPlusTree::get(self, key)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: 'a,
+        V: 'a,
+    {
+        BPlusTree::iter(self)
+    }
+}
@@ -0,0 +1,699 @@
+use super::node::{self, BinaryNode, BinaryNodeMut, EntryMap, InOrder, Path, Side};
+pub use super::node::Entry;
+use super::InvariantError;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+use std::ops::RangeBounds;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = RedBlackTreeMap::new();
+        assert_eq!(map.insert("b", 2), None);
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("b", 20), Some(2));
+        *map.get_mut("a").unwrap() += 10;
+        assert_eq!(map.get("a"), Some(&11));
+        assert_eq!(map.remove("a"), Some(11));
+        assert_eq!(map.remove("a"), None);
+        assert_eq!(map.remove("zzz"), None);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_sequential_inserts_stay_balanced() {
+        let map: RedBlackTreeMap<u32, ()> = (0..1000).map(|k| (k, ())).collect();
+        assert_eq!(map.validate(), Ok(()));
+        // A red-black tree's height is at most 2·log2(n + 1)
+        assert!(map.height() <= 20);
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_ordered_queries_with_borrowed_keys() {
+        let map: RedBlackTreeMap<String, usize> =
+            ["apple", "banana", "cherry", "date"].iter().map(|s| (s.to_string(), s.len())).collect();
+        assert_eq!(map.first().map(|(k, _)| k.as_str()), Some("apple"));
+        assert_eq!(map.last().map(|(k, _)| k.as_str()), Some("date"));
+        assert_eq!(map.floor("c").map(|(k, _)| k.as_str()), Some("banana"));
+        assert_eq!(map.ceiling("c").map(|(k, _)| k.as_str()), Some("cherry"));
+        let middle: Vec<&str> =
+            map.range::<str, _>((Bound::Excluded("apple"), Bound::Included("cherry"))).map(|(k, _)| k.as_str()).collect();
+        assert_eq!(middle, vec!["banana", "cherry"]);
+    }
+
+    #[test]
+    fn test_entry_inserts_survive_rotations() {
+        // Entries need neither `Clone` keys nor a second search to hand back
+        // the inserted value, even when the insert rotates the tree
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Key(u64);
+        let mut map = RedBlackTreeMap::new();
+        let mut rng = XorShift64::new(46);
+        let keys: Vec<u64> = (0..300).chain((300..600).rev()).chain((0..600).map(|_| rng.below(900))).collect();
+        for &k in &keys {
+            map.entry(Key(k)).or_insert_with(Vec::new).push(k);
+        }
+        assert_eq!(map.validate(), Ok(()));
+        assert_eq!(map.len(), keys.iter().collect::<std::collections::BTreeSet<_>>().len());
+        assert!(map.iter().all(|(key, seen)| seen.iter().all(|&k| k == key.0)));
+        assert_eq!(map.iter().map(|(_, seen)| seen.len()).sum::<usize>(), keys.len());
+    }
+
+    #[test]
+    fn test_entry_api() {
+        let mut map = RedBlackTreeMap::new();
+        map.entry(1).or_insert_with(Vec::new).push('a');
+        map.entry(1).or_default().push('b');
+        map.entry(2).and_modify(|v| v.push('x')).or_default().push('c');
+        assert_eq!(map.get(&1), Some(&vec!['a', 'b']));
+        assert_eq!(map.get(&2), Some(&vec!['c']));
+        if let Entry::Occupied(mut entry) = map.entry(2) {
+            assert_eq!(entry.insert(vec![]), vec!['c']);
+            assert_eq!(entry.get(), &Vec::<char>::new());
+        }
+        assert_eq!(map.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut set: RedBlackTreeSet<char> = "hello world".chars().collect();
+        assert_eq!(set.len(), 8);
+        assert!(set.remove(&' '));
+        assert!(!set.insert('h'));
+        assert_eq!(set.iter().collect::<String>(), "dehlorw");
+        assert_eq!(set.range('e'..'o').collect::<String>(), "ehl");
+        assert_eq!((set.floor(&'n'), set.ceiling(&'n')), (Some(&'l'), Some(&'o')));
+        assert_eq!(set.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_matches_btree_map_under_random_operations() {
+        let mut rng = XorShift64::new(0x4B);
+        let mut map = RedBlackTreeMap::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..4000 {
+            let key = rng.below(300);
+            match rng.below(3) {
+                0 | 1 => assert_eq!(map.insert(key, step), expected.insert(key, step)),
+                _ => assert_eq!(map.remove(&key), expected.remove(&key)),
+            }
+            if step % 100 == 0 {
+                assert_eq!(map.validate(), Ok(()));
+            }
+            let probe = rng.below(320);
+            assert_eq!(map.get(&probe), expected.get(&probe));
+            assert_eq!(map.floor(&probe), expected.range(..=probe).next_back());
+            assert_eq!(map.ceiling(&probe), expected.range(probe..).next());
+        }
+        assert_eq!(map.validate(), Ok(()));
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(50..150).eq(expected.range(50..150)));
+        while let Some((&key, _)) = expected.last_key_value() {
+            assert_eq!(map.remove(&key), expected.remove(&key));
+            assert_eq!(map.validate(), Ok(()));
+        }
+        assert!(map.is_empty());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+impl Color {
+    fn flip(self) -> Self {
+        match self {
+            Color::Red => Color::Black,
+            Color::Black => Color::Red,
+        }
+    }
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // Colour of the link from the parent to this node
+    color: Color,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> BinaryNodeMut for Node<K, V> {
+    fn left_mut(&mut self) -> &mut Link<K, V> {
+        &mut self.left
+    }
+
+    fn right_mut(&mut self) -> &mut Link<K, V> {
+        &mut self.right
+    }
+}
+
+impl<K, V> BinaryNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+fn is_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| node.color == Color::Red)
+}
+
+fn is_left_left_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| is_red(&node.left))
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut right = node.right.take().expect("rotating left needs a right child");
+    node.right = right.left.take();
+    right.color = node.color;
+    node.color = Color::Red;
+    right.left = Some(node);
+    right
+}
+
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut left = node.left.take().expect("rotating right needs a left child");
+    node.left = left.right.take();
+    left.color = node.color;
+    node.color = Color::Red;
+    left.right = Some(node);
+    left
+}
+
+/// Flips the colours of a node and both its children, splitting or merging
+/// a temporary 4-node.
+fn flip_colors<K, V>(node: &mut Node<K, V>) {
+    node.color = node.color.flip();
+    for child in [&mut node.left, &mut node.right].into_iter().flatten() {
+        child.color = child.color.flip();
+    }
+}
+
+/// Restores the left-leaning invariants on the way back up.
+fn fix_up<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    if is_red(&node.right) && !is_red(&node.left) {
+        node = rotate_left(node);
+    }
+    if is_red(&node.left) && is_left_left_red(&node.left) {
+        node = rotate_right(node);
+    }
+    if is_red(&node.left) && is_red(&node.right) {
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Makes `node.left` or one of its children red before descending left.
+fn move_red_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    flip_colors(&mut node);
+    if node.right.as_ref().is_some_and(|right| is_red(&right.left)) {
+        node.right = node.right.take().map(rotate_right);
+        node = rotate_left(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Makes `node.right` or one of its children red before descending right.
+fn move_red_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    flip_colors(&mut node);
+    if is_left_left_red(&node.left) {
+        node = rotate_right(node);
+        flip_colors(&mut node);
+    }
+    node
+}
+
+/// Inserts into the subtree at `link`. When `path` is given it ends up
+/// leading from the returned root to the inserted node.
+fn insert<K: Ord, V>(
+    link: Link<K, V>,
+    key: K,
+    value: V,
+    replaced: &mut Option<V>,
+    mut path: Option<&mut Path>,
+) -> Box<Node<K, V>> {
+    let Some(mut node) = link else {
+        return Box::new(Node { key, value, color: Color::Red, left: None, right: None });
+    };
+    let side = match key.cmp(&node.key) {
+        Ordering::Less => {
+            node.left = Some(insert(node.left.take(), key, value, replaced, path.as_deref_mut()));
+            Side::Left
+        }
+        Ordering::Greater => {
+            node.right = Some(insert(node.right.take(), key, value, replaced, path.as_deref_mut()));
+            Side::Right
+        }
+        Ordering::Equal => {
+            *replaced = Some(mem::replace(&mut node.value, value));
+            return fix_up(node);
+        }
+    };
+    match path {
+        Some(path) => {
+            path.push(side);
+            path.track(node, fix_up)
+        }
+        None => fix_up(node),
+    }
+}
+
+/// Detaches the leftmost node, returning the rebuilt subtree and that node.
+fn remove_first<K, V>(mut node: Box<Node<K, V>>) -> (Link<K, V>, Box<Node<K, V>>) {
+    if node.left.is_none() {
+        // A left-leaning node without a left child has no right child either
+        return (None, node);
+    }
+    if !is_red(&node.left) && !is_left_left_red(&node.left) {
+        node = move_red_left(node);
+    }
+    let (left, first) = remove_first(node.left.take().expect("checked above"));
+    node.left = left;
+    (Some(fix_up(node)), first)
+}
+
+/// Removes `key`, which must be present in the subtree rooted at `node`.
+fn remove<K, V, Q>(mut node: Box<Node<K, V>>, key: &Q, removed: &mut Option<(K, V)>) -> Link<K, V>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    if key < node.key.borrow() {
+        if !is_red(&node.left) && !is_left_left_red(&node.left) {
+            node = move_red_left(node);
+        }
+        node.left = remove(node.left.take().expect("key is present"), key, removed);
+    } else {
+        if is_red(&node.left) {
+            node = rotate_right(node);
+        }
+        if key == node.key.borrow() && node.right.is_none() {
+            *removed = Some((node.key, node.value));
+            return None;
+        }
+        if !is_red(&node.right) && !is_left_left_red(&node.right) {
+            node = move_red_right(node);
+        }
+        let right = node.right.take().expect("key is present");
+        if key == node.key.borrow() {
+            // Replace the node's entry with its in-order successor's
+            let (right, successor) = remove_first(right);
+            node.right = right;
+            let key = mem::replace(&mut node.key, successor.key);
+            let value = mem::replace(&mut node.value, successor.value);
+            *removed = Some((key, value));
+        } else {
+            node.right = remove(right, key, removed);
+        }
+    }
+    Some(fix_up(node))
+}
+
+/// An ordered map backed by a left-leaning red-black tree (Sedgewick's
+/// 2-3 tree encoding): red links lean left, no node has two red links and
+/// every root-to-leaf path crosses the same number of black links, so the
+/// height stays below 2·log2(n + 1).
+pub struct RedBlackTreeMap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+impl<K, V> RedBlackTreeMap<K, V> {
+    pub fn new() -> Self {
+        RedBlackTreeMap { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of nodes on the longest root-to-leaf path; 0 when empty.
+    pub fn height(&self) -> u32 {
+        fn height<K, V>(link: &Link<K, V>) -> u32 {
+            link.as_ref().map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+        }
+        height(&self.root)
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::first(root).entry())
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::last(root).entry())
+    }
+
+    /// Iterates the entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        InOrder::new(self.root.as_deref()).map(Node::entry)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: Ord, V> RedBlackTreeMap<K, V> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut replaced = None;
+        let mut root = insert(self.root.take(), key, value, &mut replaced, None);
+        root.color = Color::Black;
+        self.root = Some(root);
+        if replaced.is_none() {
+            self.len += 1;
+        }
+        replaced
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // The top-down deletion assumes the key is present
+        if !self.contains_key(key) {
+            return None;
+        }
+        let mut root = self.root.take().expect("key is present");
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.color = Color::Red;
+        }
+        let mut removed = None;
+        self.root = remove(root, key, &mut removed);
+        if let Some(root) = &mut self.root {
+            root.color = Color::Black;
+        }
+        self.len -= 1;
+        removed.map(|(_, value)| value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = self.root.as_deref_mut();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// The entry with the greatest key less than or equal to `key`.
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::floor(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// The entry with the least key greater than or equal to `key`.
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::ceiling(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// Iterates the entries whose keys fall in `range`, in key order.
+    pub fn range<'a, Q, R>(&'a self, range: R) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized + 'a,
+        R: RangeBounds<Q> + 'a,
+    {
+        InOrder::starting_at(self.root.as_deref(), range.start_bound())
+            .take_while(move |node| node::below_end(&node.key, range.end_bound()))
+            .map(Node::entry)
+    }
+
+    /// Checks the BST ordering, the left-leaning red-black colouring, equal
+    /// black heights and the stored length.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        fn black_height<K, V>(link: &Link<K, V>) -> Result<u32, InvariantError> {
+            let Some(node) = link else {
+                return Ok(0);
+            };
+            if is_red(&node.right) {
+                return Err(InvariantError::RedRightLink);
+            }
+            if node.color == Color::Red && is_red(&node.left) {
+                return Err(InvariantError::DoubleRed);
+            }
+            let left = black_height(&node.left)?;
+            if left != black_height(&node.right)? {
+                return Err(InvariantError::BlackHeight);
+            }
+            Ok(left + u32::from(node.color == Color::Black))
+        }
+        if is_red(&self.root) {
+            return Err(InvariantError::RedRoot);
+        }
+        black_height(&self.root)?;
+        let keys: Vec<&K> = self.keys().collect();
+        if keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(InvariantError::Unordered);
+        }
+        if keys.len() != self.len {
+            return Err(InvariantError::WrongLength { expected: self.len, found: keys.len() });
+        }
+        Ok(())
+    }
+}
+
+impl<K: Ord, V> RedBlackTreeMap<K, V> {
+    /// Looks up `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry::new(self, key)
+    }
+}
+
+impl<K: Ord, V> EntryMap<K, V> for RedBlackTreeMap<K, V> {
+    fn find_path(&self, key: &K) -> Option<Path> {
+        node::find_path(self.root.as_deref(), key)
+    }
+
+    fn value_at(&self, path: &Path) -> &V {
+        &node::follow(self.root.as_deref().expect("entry is occupied"), path).value
+    }
+
+    fn value_at_mut(&mut self, path: &Path) -> &mut V {
+        &mut node::follow_mut(self.root.as_deref_mut().expect("entry is occupied"), path).value
+    }
+
+    fn insert_absent(&mut self, key: K, value: V) -> Path {
+        let mut path = Path::default();
+        let mut root = insert(self.root.take(), key, value, &mut None, Some(&mut path));
+        root.color = Color::Black;
+        self.root = Some(root);
+        self.len += 1;
+        path
+    }
+
+    fn remove_present(&mut self, key: &K) -> V {
+        self.remove(key).expect("entry is occupied")
+    }
+}
+
+impl<K, V> Default for RedBlackTreeMap<K, V> {
+    fn default() -> Self {
+        RedBlackTreeMap::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for RedBlackTreeMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for RedBlackTreeMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for RedBlackTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = RedBlackTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/// An ordered set backed by a [`RedBlackTreeMap`] with unit values.
+pub struct RedBlackTreeSet<T> {
+    map: RedBlackTreeMap<T, ()>,
+}
+
+impl<T: Ord> RedBlackTreeSet<T> {
+    pub fn new() -> Self {
+        RedBlackTreeSet { map: RedBlackTreeMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Adds `value`, returning whether it was new.
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(value, _)| value)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(value, _)| value)
+    }
+
+    pub fn floor<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.floor(value).map(|(value, _)| value)
+    }
+
+    pub fn ceiling<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.ceiling(value).map(|(value, _)| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.map.keys()
+    }
+
+    pub fn range<'a, Q, R>(&'a self, range: R) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized + 'a,
+        R: RangeBounds<Q> + 'a,
+    {
+        self.map.range(range).map(|(value, _)| value)
+    }
+
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        self.map.validate()
+    }
+}
+
+impl<T: Ord> Default for RedBlackTreeSet<T> {
+    fn default() -> Self {
+        RedBlackTreeSet::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RedBlackTreeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.map.keys()).finish()
+    }
+}
+
+impl<T: Ord> Extend<T> for RedBlackTreeSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for RedBlackTreeSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = RedBlackTreeSet::new();
+        set.extend(iter);
+        set
+    }
+}
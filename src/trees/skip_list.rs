@@ -0,0 +1,372 @@
+use crate::rng::XorShift64;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_operations() {
+        let mut list = SkipList::new();
+        assert_eq!(list.insert("m", 1), None);
+        assert_eq!(list.insert("c", 2), None);
+        assert_eq!(list.insert("x", 3), None);
+        assert_eq!(list.insert("c", 20), Some(2));
+        assert_eq!(list.get("c"), Some(&20));
+        *list.get_mut("x").unwrap() += 1;
+        assert_eq!(list.remove("m"), Some(1));
+        assert_eq!(list.remove("m"), None);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![(&"c", &20), (&"x", &4)]);
+        assert_eq!((list.first(), list.len()), (Some((&"c", &20)), 2));
+    }
+
+    #[test]
+    fn test_range() {
+        let list: SkipList<u32, u32> = (0..100).map(|k| (k * 3, k)).collect();
+        let keys: Vec<u32> = list.range(10..=21).map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![12, 15, 18, 21]);
+        assert_eq!(list.range((Bound::Excluded(294), Bound::Unbounded)).count(), 1);
+        assert_eq!(list.range(..).count(), 100);
+    }
+
+    #[test]
+    fn test_seed_makes_levels_reproducible() {
+        let build = |seed| {
+            let mut list = SkipList::with_params(0.25, 12, seed);
+            list.extend((0..500).map(|k| (k, ())));
+            list.level_counts()
+        };
+        assert_eq!(build(7), build(7));
+        let counts = build(7);
+        assert_eq!(counts[0], 500);
+        // With p = 1/4 roughly a quarter of the nodes reach each next level
+        assert!((80..170).contains(&counts[1]));
+        assert!(counts.len() <= 12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rejects_invalid_probability() {
+        SkipList::<u8, u8>::with_params(1.0, 8, 0);
+    }
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // next[l] is the following node on level `l`; its length is the node's level
+    next: Vec<Option<usize>>,
+}
+
+/// An ordered map backed by a skip list: a sorted linked list in which each
+/// node is also linked on each higher level with probability `p`, giving
+/// express lanes that make search, insert and remove expected
+/// O(log_{1/p} n).
+///
+/// Nodes live in an arena and link by index. Levels come from a seeded
+/// generator, so a given seed and operation sequence always builds the
+/// same list.
+pub struct SkipList<K, V> {
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    // The head's links on every level in use
+    head: Vec<Option<usize>>,
+    len: usize,
+    probability: f64,
+    max_level: usize,
+    rng: XorShift64,
+}
+
+impl<K, V> SkipList<K, V> {
+    /// A skip list with `p = 1/2`, at most 32 levels and a fixed seed.
+    pub fn new() -> Self {
+        SkipList::with_params(0.5, 32, 0x5EED_5C1B)
+    }
+
+    /// A skip list promoting nodes to the next level with probability
+    /// `probability`, using at most `max_level` levels, with levels drawn
+    /// from a generator seeded with `seed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `0 < probability < 1` and `max_level >= 1`.
+    pub fn with_params(probability: f64, max_level: usize, seed: u64) -> Self {
+        assert!(probability > 0.0 && probability < 1.0, "probability must be in (0, 1)");
+        assert!(max_level >= 1, "a skip list needs at least one level");
+        SkipList {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            head: Vec::new(),
+            len: 0,
+            probability,
+            max_level,
+            rng: XorShift64::new(seed),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.head.clear();
+        self.len = 0;
+    }
+
+    /// Number of nodes linked on each level, from the bottom up.
+    pub fn level_counts(&self) -> Vec<usize> {
+        (0..self.head.len())
+            .map(|level| {
+                let mut count = 0;
+                let mut link = self.head[level];
+                while let Some(index) = link {
+                    count += 1;
+                    link = self.node(index).next[level];
+                }
+                count
+            })
+            .collect()
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// Iterates the entries in key order along the bottom level.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { list: self, link: self.head.first().copied().flatten() }
+    }
+
+    fn node(&self, index: usize) -> &Node<K, V> {
+        self.nodes[index].as_ref().expect("node is live")
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut Node<K, V> {
+        self.nodes[index].as_mut().expect("node is live")
+    }
+
+    /// The link following `from` (the head for `None`) on `level`.
+    fn next(&self, from: Option<usize>, level: usize) -> Option<usize> {
+        match from {
+            Some(index) => self.node(index).next[level],
+            None => self.head[level],
+        }
+    }
+
+    fn set_next(&mut self, from: Option<usize>, level: usize, to: Option<usize>) {
+        match from {
+            Some(index) => self.node_mut(index).next[level] = to,
+            None => self.head[level] = to,
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < self.max_level && self.rng.next_f64() < self.probability {
+            level += 1;
+        }
+        level
+    }
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    /// For every level, the last node (or the head, `None`) whose key is
+    /// below `key`: the nodes whose links an insert or remove must update.
+    fn predecessors<Q>(&self, key: &Q) -> Vec<Option<usize>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut path = vec![None; self.head.len()];
+        let mut current = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(next) = self.next(current, level) {
+                if self.node(next).key.borrow() >= key {
+                    break;
+                }
+                current = Some(next);
+            }
+            path[level] = current;
+        }
+        path
+    }
+
+    /// The first node with a key at or after `key` (after it if `exclusive`).
+    fn seek<Q>(&self, key: &Q, exclusive: bool) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(next) = self.next(current, level) {
+                let ordering = self.node(next).key.borrow().cmp(key);
+                if ordering == Ordering::Greater || (ordering == Ordering::Equal && !exclusive) {
+                    break;
+                }
+                current = Some(next);
+            }
+        }
+        self.head.first().and_then(|_| self.next(current, 0))
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(key, false).filter(|&index| self.node(index).key.borrow() == key)
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let path = self.predecessors(&key);
+        if let Some(index) = self.head.first().and_then(|_| self.next(path[0], 0)) {
+            if self.node(index).key == key {
+                return Some(mem::replace(&mut self.node_mut(index).value, value));
+            }
+        }
+        let level = self.random_level();
+        let node = Node { key, value, next: vec![None; level] };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        for l in 0..level {
+            // Levels above the current top start from the head
+            let from = path.get(l).copied().flatten();
+            if l >= self.head.len() {
+                self.head.push(None);
+            }
+            let next = self.next(from, l);
+            self.node_mut(index).next[l] = next;
+            self.set_next(from, l, Some(index));
+        }
+        self.len += 1;
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let path = self.predecessors(key);
+        let index = self.head.first().and_then(|_| self.next(path[0], 0))?;
+        if self.node(index).key.borrow() != key {
+            return None;
+        }
+        let node = self.nodes[index].take().expect("node is live");
+        for (level, &next) in node.next.iter().enumerate() {
+            self.set_next(path[level], level, next);
+        }
+        while self.head.last() == Some(&None) {
+            self.head.pop();
+        }
+        self.free.push(index);
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|index| &self.node(index).value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.find(key)?;
+        Some(&mut self.node_mut(index).value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Iterates the entries whose keys fall in `range`, in key order.
+    pub fn range<'a, Q, R>(&'a self, range: R) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized + 'a,
+        R: RangeBounds<Q> + 'a,
+    {
+        let link = match range.start_bound() {
+            Bound::Included(start) => self.seek(start, false),
+            Bound::Excluded(start) => self.seek(start, true),
+            Bound::Unbounded => self.head.first().copied().flatten(),
+        };
+        Iter { list: self, link }.take_while(move |(key, _)| match range.end_bound() {
+            Bound::Included(end) => (*key).borrow() <= end,
+            Bound::Excluded(end) => (*key).borrow() < end,
+            Bound::Unbounded => true,
+        })
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    list: &'a SkipList<K, V>,
+    link: Option<usize>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let node = self.list.node(self.link?);
+        self.link = node.next[0];
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        SkipList::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SkipList<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SkipList<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipList<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut list = SkipList::new();
+        list.extend(iter);
+        list
+    }
+}
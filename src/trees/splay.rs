@@ -0,0 +1,340 @@
+use super::node::{self, BinaryNode, InOrder};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_operations() {
+        let mut tree = SplayTree::new();
+        assert_eq!(tree.insert(5, 'e'), None);
+        assert_eq!(tree.insert(2, 'b'), None);
+        assert_eq!(tree.insert(8, 'h'), None);
+        assert_eq!(tree.insert(2, 'B'), Some('b'));
+        assert_eq!(tree.get(&2), Some(&'B'));
+        assert_eq!(tree.get(&3), None);
+        *tree.get_mut(&8).unwrap() = 'H';
+        assert_eq!(tree.remove(&5), Some('e'));
+        assert_eq!(tree.remove(&5), None);
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![(&2, &'B'), (&8, &'H')]);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn test_access_moves_key_to_root() {
+        let mut tree: SplayTree<u32, ()> = (0..100).map(|k| (k, ())).collect();
+        tree.get(&37);
+        assert_eq!(tree.root_key(), Some(&37));
+        // A miss splays the last node on the search path
+        tree.get(&1000);
+        assert_eq!(tree.root_key(), Some(&99));
+        assert!(tree.contains_key(&0));
+        assert_eq!(tree.root_key(), Some(&0));
+    }
+
+    #[test]
+    fn test_ordered_queries_do_not_restructure() {
+        let tree: SplayTree<i32, i32> = [(10, 1), (20, 2), (30, 3)].into_iter().collect();
+        let root = tree.root_key().copied();
+        assert_eq!(tree.floor(&25), Some((&20, &2)));
+        assert_eq!(tree.ceiling(&25), Some((&30, &3)));
+        assert_eq!((tree.first(), tree.last()), (Some((&10, &1)), Some((&30, &3))));
+        assert_eq!(tree.root_key().copied(), root);
+    }
+
+    #[test]
+    fn test_sequential_access_is_cheap() {
+        // Inserting sorted keys builds a path, but a full in-order access
+        // sequence still costs O(n) amortised in total
+        let mut tree: SplayTree<u32, u32> = (0..10_000).map(|k| (k, k)).collect();
+        assert!((0..10_000).all(|k| tree.get(&k) == Some(&k)));
+    }
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> BinaryNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+/// Top-down splay (Sleator and Tarjan): walks from the root towards `key`,
+/// rotating on zig-zig steps and peeling the nodes passed on the left and
+/// right into two side trees, then reassembles them under the last node
+/// reached. That node, `key` itself if present, becomes the root.
+fn splay<K, V, Q>(mut root: Box<Node<K, V>>, key: &Q) -> Box<Node<K, V>>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    // Nodes whose keys are below `key`, in the order they were passed;
+    // each becomes the right child of the one before it
+    let mut smaller: Vec<Box<Node<K, V>>> = Vec::new();
+    // Likewise for keys above `key`, chained through left children
+    let mut larger: Vec<Box<Node<K, V>>> = Vec::new();
+    loop {
+        match key.cmp(root.key.borrow()) {
+            Ordering::Less => {
+                let Some(mut left) = root.left.take() else { break };
+                if key < left.key.borrow() {
+                    // Zig-zig: rotate right before linking
+                    root.left = left.right.take();
+                    left.right = Some(root);
+                    root = left;
+                    match root.left.take() {
+                        Some(next) => left = next,
+                        None => break,
+                    }
+                }
+                larger.push(mem::replace(&mut root, left));
+            }
+            Ordering::Greater => {
+                let Some(mut right) = root.right.take() else { break };
+                if key > right.key.borrow() {
+                    // Zag-zag: rotate left before linking
+                    root.right = right.left.take();
+                    right.left = Some(root);
+                    root = right;
+                    match root.right.take() {
+                        Some(next) => right = next,
+                        None => break,
+                    }
+                }
+                smaller.push(mem::replace(&mut root, right));
+            }
+            Ordering::Equal => break,
+        }
+    }
+    let mut left = root.left.take();
+    for mut node in smaller.into_iter().rev() {
+        node.right = left;
+        left = Some(node);
+    }
+    let mut right = root.right.take();
+    for mut node in larger.into_iter().rev() {
+        node.left = right;
+        right = Some(node);
+    }
+    root.left = left;
+    root.right = right;
+    root
+}
+
+/// An ordered map backed by a splay tree: every access rotates the touched
+/// node to the root. There is no balance information, and single operations
+/// can take O(n), but any sequence of m operations costs O(m log n), and
+/// recently or frequently used keys stay near the root.
+///
+/// Lookups restructure the tree, so `get` takes `&mut self`; `floor`,
+/// `ceiling`, `first`, `last` and iteration only read it.
+pub struct SplayTree<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+impl<K, V> SplayTree<K, V> {
+    pub fn new() -> Self {
+        SplayTree { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// The key at the root: the most recently accessed one.
+    pub fn root_key(&self) -> Option<&K> {
+        self.root.as_ref().map(|root| &root.key)
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::first(root).entry())
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::last(root).entry())
+    }
+
+    /// Iterates the entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        InOrder::new(self.root.as_deref()).map(Node::entry)
+    }
+}
+
+impl<K: Ord, V> SplayTree<K, V> {
+    /// Splays `key` to the root, returning whether it is present.
+    fn access<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root = self.root.take().map(|root| splay(root, key));
+        self.root.as_ref().is_some_and(|root| root.key.borrow() == key)
+    }
+
+    /// Inserts `value` under `key` at the root, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.access(&key) {
+            let root = self.root.as_mut().expect("key is at the root");
+            return Some(mem::replace(&mut root.value, value));
+        }
+        let mut node = Box::new(Node { key, value, left: None, right: None });
+        if let Some(mut root) = self.root.take() {
+            // The old root is the new key's neighbour, so it splits cleanly
+            if node.key < root.key {
+                node.left = root.left.take();
+                node.right = Some(root);
+            } else {
+                node.right = root.right.take();
+                node.left = Some(root);
+            }
+        }
+        self.root = Some(node);
+        self.len += 1;
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if !self.access(key) {
+            return None;
+        }
+        let mut root = self.root.take().expect("key is at the root");
+        self.root = match root.left.take() {
+            None => root.right.take(),
+            Some(left) => {
+                // Splaying the left subtree for `key` brings its maximum up,
+                // which has no right child to clash with
+                let mut left = splay(left, key);
+                left.right = root.right.take();
+                Some(left)
+            }
+        };
+        self.len -= 1;
+        Some(root.value)
+    }
+
+    /// Returns the value for `key`, splaying it (or the last node on its
+    /// search path) to the root.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.access(key) {
+            self.root.as_mut().map(|root| &mut root.value)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains_key<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.access(key)
+    }
+
+    /// The entry with the greatest key less than or equal to `key`.
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::floor(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// The entry with the least key greater than or equal to `key`.
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::ceiling(self.root.as_deref(), key).map(Node::entry)
+    }
+}
+
+impl<K, V> Drop for SplayTree<K, V> {
+    fn drop(&mut self) {
+        // Splay trees can degenerate into long paths; free them iteratively
+        let mut stack: Vec<Box<Node<K, V>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+impl<K, V> Default for SplayTree<K, V> {
+    fn default() -> Self {
+        SplayTree::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SplayTree<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SplayTree<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SplayTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = SplayTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
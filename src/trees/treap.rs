@@ -0,0 +1,665 @@
+use super::node::{self, BinaryNode, InOrder};
+use crate::rng::XorShift64;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_operations() {
+        let mut treap = Treap::with_seed(1);
+        assert_eq!(treap.insert(3, "c"), None);
+        assert_eq!(treap.insert(1, "a"), None);
+        assert_eq!(treap.insert(2, "b"), None);
+        assert_eq!(treap.insert(2, "B"), Some("b"));
+        assert_eq!(treap.get(&2), Some(&"B"));
+        assert_eq!(treap.remove(&1), Some("a"));
+        assert_eq!(treap.remove(&1), None);
+        assert_eq!(treap.len(), 2);
+        assert_eq!((treap.first(), treap.last()), (Some((&2, &"B")), Some((&3, &"c"))));
+        assert_eq!(treap.floor(&4), Some((&3, &"c")));
+    }
+
+    #[test]
+    fn test_split_off_and_append() {
+        let mut low: Treap<u32, u32> = (0..100).map(|k| (k, k * k)).collect();
+        let mut high = low.split_off(&60);
+        assert_eq!((low.len(), high.len()), (60, 40));
+        assert_eq!(low.last(), Some((&59, &3481)));
+        assert_eq!(high.first(), Some((&60, &3600)));
+        low.append(&mut high);
+        assert!(high.is_empty());
+        assert_eq!(low.len(), 100);
+        assert!(low.iter().map(|(k, _)| *k).eq(0..100));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_append_rejects_overlapping_keys() {
+        let mut a: Treap<u8, ()> = [(1, ()), (5, ())].into_iter().collect();
+        let mut b: Treap<u8, ()> = [(3, ())].into_iter().collect();
+        a.append(&mut b);
+    }
+
+    #[test]
+    fn test_same_seed_same_shape() {
+        let a: Treap<u32, ()> = (0..64).map(|k| (k, ())).collect();
+        let b: Treap<u32, ()> = (0..64).map(|k| (k, ())).collect();
+        assert_eq!(a.height(), b.height());
+        // Expected height is O(log n); sorted input would degrade a plain BST to 64
+        assert!(a.height() < 20);
+    }
+
+    #[test]
+    fn test_implicit_sequence_operations() {
+        let mut seq: ImplicitTreap<char> = "hello".chars().collect();
+        seq.push_back('!');
+        seq.insert(0, '>');
+        assert_eq!(seq.iter().collect::<String>(), ">hello!");
+        assert_eq!(seq.remove(6), '!');
+        assert_eq!(seq.get(1), Some(&'h'));
+        assert_eq!(seq.get(9), None);
+        *seq.get_mut(1).unwrap() = 'H';
+        let mut tail = seq.split_off(3);
+        assert_eq!((seq.iter().collect::<String>(), tail.iter().collect::<String>()), (">He".into(), "llo".into()));
+        tail.append(&mut seq);
+        assert_eq!(tail.iter().collect::<String>(), "llo>He");
+        assert_eq!(tail.len(), 6);
+    }
+
+    #[test]
+    fn test_implicit_reverse() {
+        let mut seq: ImplicitTreap<u32> = (0..10).collect();
+        seq.reverse(2..7);
+        assert_eq!(seq.iter().copied().collect::<Vec<_>>(), vec![0, 1, 6, 5, 4, 3, 2, 7, 8, 9]);
+        seq.reverse(0..10);
+        assert_eq!(seq.get(0), Some(&9));
+        assert_eq!(seq.get(3), Some(&2));
+        seq.reverse(4..4);
+        *seq.get_mut(9).unwrap() = 100;
+        seq.insert(5, 42);
+        assert_eq!(seq.iter().copied().collect::<Vec<_>>(), vec![9, 8, 7, 2, 3, 42, 4, 5, 6, 1, 100]);
+    }
+
+    #[test]
+    fn test_implicit_matches_vec() {
+        let mut rng = XorShift64::new(99);
+        let mut seq = ImplicitTreap::with_seed(5);
+        let mut expected = Vec::new();
+        for step in 0..2000u64 {
+            let len = expected.len() as u64;
+            match rng.below(4) {
+                0 | 1 => {
+                    let at = rng.below(len + 1) as usize;
+                    seq.insert(at, step);
+                    expected.insert(at, step);
+                }
+                2 if len > 0 => {
+                    let at = rng.below(len) as usize;
+                    assert_eq!(seq.remove(at), expected.remove(at));
+                }
+                _ => {
+                    let (a, b) = (rng.below(len + 1) as usize, rng.below(len + 1) as usize);
+                    let range = a.min(b)..a.max(b);
+                    seq.reverse(range.clone());
+                    expected[range].reverse();
+                }
+            }
+        }
+        assert_eq!(seq.len(), expected.len());
+        assert!(seq.iter().eq(expected.iter()));
+        assert!((0..expected.len()).all(|i| seq.get(i) == expected.get(i)));
+    }
+}
+
+/// Seed used by `new`, so unseeded treaps are reproducible too.
+const DEFAULT_SEED: u64 = 0x5EED_7EA9;
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // Max-heap ordered: a parent's priority is at least its children's
+    priority: u64,
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> BinaryNode for Node<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+impl<K, V> Node<K, V> {
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+/// Splits a subtree into the keys below `key` (up to and including it if
+/// `inclusive`) and the rest.
+fn split<K, V, Q>(link: Link<K, V>, key: &Q, inclusive: bool) -> (Link<K, V>, Link<K, V>)
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    let goes_left = match node.key.borrow().cmp(key) {
+        Ordering::Less => true,
+        Ordering::Equal => inclusive,
+        Ordering::Greater => false,
+    };
+    if goes_left {
+        let (left, right) = split(node.right.take(), key, inclusive);
+        node.right = left;
+        node.update();
+        (Some(node), right)
+    } else {
+        let (left, right) = split(node.left.take(), key, inclusive);
+        node.left = right;
+        node.update();
+        (left, Some(node))
+    }
+}
+
+/// Joins two subtrees where every key of `left` is below every key of `right`.
+fn merge<K, V>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    match (left, right) {
+        (None, tree) | (tree, None) => tree,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                left.update();
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                right.update();
+                Some(right)
+            }
+        }
+    }
+}
+
+/// An ordered map backed by a treap: a BST on keys that is also a heap on
+/// random priorities, which makes its shape that of a BST built from a
+/// random insertion order. Expected depth is O(log n) whatever the input.
+///
+/// Every update is a `split` followed by `merge`s, and both are exposed as
+/// `split_off` and `append` for O(log n) bulk moves.
+pub struct Treap<K, V> {
+    root: Link<K, V>,
+    rng: XorShift64,
+}
+
+impl<K, V> Treap<K, V> {
+    pub fn new() -> Self {
+        Treap::with_seed(DEFAULT_SEED)
+    }
+
+    /// Creates a treap drawing priorities from a generator seeded with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Treap { root: None, rng: XorShift64::new(seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of nodes on the longest root-to-leaf path; 0 when empty.
+    pub fn height(&self) -> usize {
+        fn height<K, V>(link: &Link<K, V>) -> usize {
+            link.as_ref().map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+        }
+        height(&self.root)
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::first(root).entry())
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.root.as_deref().map(|root| node::last(root).entry())
+    }
+
+    /// Iterates the entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        InOrder::new(self.root.as_deref()).map(Node::entry)
+    }
+}
+
+impl<K: Ord, V> Treap<K, V> {
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(mem::replace(existing, value));
+        }
+        let priority = self.rng.next_u64();
+        let node = Box::new(Node { key, value, priority, size: 1, left: None, right: None });
+        let (left, right) = split(self.root.take(), &node.key, false);
+        self.root = merge(merge(left, Some(node)), right);
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (left, rest) = split(self.root.take(), key, false);
+        let (found, right) = split(rest, key, true);
+        self.root = merge(left, right);
+        found.map(|node| node.value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = self.root.as_deref_mut();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// The entry with the greatest key less than or equal to `key`.
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::floor(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// The entry with the least key greater than or equal to `key`.
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        node::ceiling(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// Moves the entries with keys at or above `key` into a new treap in
+    /// expected O(log n).
+    pub fn split_off<Q>(&mut self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (left, right) = split(self.root.take(), key, false);
+        self.root = left;
+        Treap { root: right, rng: XorShift64::new(self.rng.next_u64()) }
+    }
+
+    /// Moves every entry of `other` into `self` in expected O(log n).
+    ///
+    /// # Panics
+    ///
+    /// Panics unless every key of `other` is greater than every key of `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        if let (Some((last, _)), Some((first, _))) = (self.last(), other.first()) {
+            assert!(last < first, "appended keys must follow the existing ones");
+        }
+        self.root = merge(self.root.take(), other.root.take());
+    }
+}
+
+impl<K, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Treap::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Treap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for Treap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for Treap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut treap = Treap::new();
+        treap.extend(iter);
+        treap
+    }
+}
+
+type SeqLink<T> = Option<Box<SeqNode<T>>>;
+
+struct SeqNode<T> {
+    value: T,
+    priority: u64,
+    size: usize,
+    // Pending reversal of this subtree, not yet applied to the children
+    reversed: bool,
+    left: SeqLink<T>,
+    right: SeqLink<T>,
+}
+
+fn seq_size<T>(link: &SeqLink<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+impl<T> SeqNode<T> {
+    fn update(&mut self) {
+        self.size = 1 + seq_size(&self.left) + seq_size(&self.right);
+    }
+
+    /// Applies a pending reversal by swapping the children and handing the
+    /// flag down to them.
+    fn push_down(&mut self) {
+        if mem::take(&mut self.reversed) {
+            mem::swap(&mut self.left, &mut self.right);
+            for child in [&mut self.left, &mut self.right].into_iter().flatten() {
+                child.reversed ^= true;
+            }
+        }
+    }
+
+    /// The children in sequence order given the reversals above this node.
+    fn children(&self, flipped: bool) -> (&SeqLink<T>, &SeqLink<T>) {
+        if flipped {
+            (&self.right, &self.left)
+        } else {
+            (&self.left, &self.right)
+        }
+    }
+}
+
+/// Splits off the first `at` elements of a subtree.
+fn split_at<T>(link: SeqLink<T>, at: usize) -> (SeqLink<T>, SeqLink<T>) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    node.push_down();
+    let left_size = seq_size(&node.left);
+    if at <= left_size {
+        let (left, right) = split_at(node.left.take(), at);
+        node.left = right;
+        node.update();
+        (left, Some(node))
+    } else {
+        let (left, right) = split_at(node.right.take(), at - left_size - 1);
+        node.right = left;
+        node.update();
+        (Some(node), right)
+    }
+}
+
+fn concat<T>(left: SeqLink<T>, right: SeqLink<T>) -> SeqLink<T> {
+    match (left, right) {
+        (None, tree) | (tree, None) => tree,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.push_down();
+                left.right = concat(left.right.take(), Some(right));
+                left.update();
+                Some(left)
+            } else {
+                right.push_down();
+                right.left = concat(Some(left), right.left.take());
+                right.update();
+                Some(right)
+            }
+        }
+    }
+}
+
+/// A sequence backed by an implicit treap: nodes are ordered by position
+/// rather than by key, each storing its subtree size, so inserting,
+/// removing, splitting and concatenating at any index are all expected
+/// O(log n). Reversing a range is O(log n) too, by tagging a subtree and
+/// applying the swap lazily.
+pub struct ImplicitTreap<T> {
+    root: SeqLink<T>,
+    rng: XorShift64,
+}
+
+impl<T> ImplicitTreap<T> {
+    pub fn new() -> Self {
+        ImplicitTreap::with_seed(DEFAULT_SEED)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        ImplicitTreap { root: None, rng: XorShift64::new(seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        seq_size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let node = self.node(value);
+        self.root = concat(self.root.take(), node);
+    }
+
+    /// Inserts `value` so that it ends up at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "insertion index {} out of bounds for length {}", index, self.len());
+        let (left, right) = split_at(self.root.take(), index);
+        let node = self.node(value);
+        self.root = concat(concat(left, node), right);
+    }
+
+    /// Removes and returns the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len(), "removal index {} out of bounds for length {}", index, self.len());
+        let (left, rest) = split_at(self.root.take(), index);
+        let (found, right) = split_at(rest, 1);
+        self.root = concat(left, right);
+        found.expect("index is in bounds").value
+    }
+
+    pub fn get(&self, mut index: usize) -> Option<&T> {
+        let mut link = &self.root;
+        let mut flipped = false;
+        while let Some(node) = link {
+            flipped ^= node.reversed;
+            let (left, right) = node.children(flipped);
+            let left_size = seq_size(left);
+            match index.cmp(&left_size) {
+                Ordering::Less => link = left,
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    link = right;
+                }
+            }
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, mut index: usize) -> Option<&mut T> {
+        let mut link = self.root.as_deref_mut();
+        while let Some(node) = link {
+            node.push_down();
+            let left_size = seq_size(&node.left);
+            link = match index.cmp(&left_size) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    node.right.as_deref_mut()
+                }
+            };
+        }
+        None
+    }
+
+    /// Moves the elements from `at` onwards into a new sequence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "split index {} out of bounds for length {}", at, self.len());
+        let (left, right) = split_at(self.root.take(), at);
+        self.root = left;
+        ImplicitTreap { root: right, rng: XorShift64::new(self.rng.next_u64()) }
+    }
+
+    /// Moves every element of `other` to the end of `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        self.root = concat(self.root.take(), other.root.take());
+    }
+
+    /// Reverses the elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or extends past `len`.
+    pub fn reverse(&mut self, range: Range<usize>) {
+        assert!(range.start <= range.end && range.end <= self.len(), "range {:?} out of bounds", range);
+        let (left, rest) = split_at(self.root.take(), range.start);
+        let (mut middle, right) = split_at(rest, range.end - range.start);
+        if let Some(node) = &mut middle {
+            node.reversed ^= true;
+        }
+        self.root = concat(concat(left, middle), right);
+    }
+
+    /// Iterates the elements in sequence order.
+    pub fn iter(&self) -> SeqIter<'_, T> {
+        let mut iter = SeqIter { stack: Vec::new() };
+        iter.push_left_spine(&self.root, false);
+        iter
+    }
+
+    fn node(&mut self, value: T) -> SeqLink<T> {
+        let priority = self.rng.next_u64();
+        Some(Box::new(SeqNode { value, priority, size: 1, reversed: false, left: None, right: None }))
+    }
+}
+
+/// In-order iterator over an [`ImplicitTreap`] that honours pending
+/// reversals without applying them.
+pub struct SeqIter<'a, T> {
+    // Each node with whether its subtree is flipped, its own flag included
+    stack: Vec<(&'a SeqNode<T>, bool)>,
+}
+
+impl<'a, T> SeqIter<'a, T> {
+    fn push_left_spine(&mut self, mut link: &'a SeqLink<T>, mut flipped: bool) {
+        while let Some(node) = link {
+            flipped ^= node.reversed;
+            self.stack.push((node, flipped));
+            link = node.children(flipped).0;
+        }
+    }
+}
+
+impl<'a, T> Iterator for SeqIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (node, flipped) = self.stack.pop()?;
+        self.push_left_spine(node.children(flipped).1, flipped);
+        Some(&node.value)
+    }
+}
+
+impl<T> Default for ImplicitTreap<T> {
+    fn default() -> Self {
+        ImplicitTreap::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ImplicitTreap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Extend<T> for ImplicitTreap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ImplicitTreap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut treap = ImplicitTreap::new();
+        treap.extend(iter);
+        treap
+    }
+}
@@ -0,0 +1,134 @@
+//! Maps keyed by byte strings that answer prefix queries.
+//!
+//! All three take keys as anything `AsRef<[u8]>` and share the same
+//! methods: exact lookup, `starts_with` iteration in lexicographic order,
+//! `longest_prefix` matching and a `memory_usage` report. `trie` spends one
+//! node per key byte, `radix_tree` collapses single-child chains into
+//! labelled edges, and `ternary_search_tree` trades child arrays for a
+//! binary search over the bytes at each depth.
+
+use std::fmt;
+
+pub mod radix_tree;
+pub mod ternary_search_tree;
+pub mod trie;
+
+#[cfg(test)]
+mod tests {
+    use super::radix_tree::RadixTree;
+    use super::ternary_search_tree::TernarySearchTree;
+    use super::trie::Trie;
+    use super::*;
+    use crate::rng::XorShift64;
+    use std::collections::BTreeMap;
+    use std::time::Instant;
+
+    fn random_word(rng: &mut XorShift64) -> Vec<u8> {
+        // A small alphabet and short words give plenty of shared prefixes
+        (0..rng.below(7)).map(|_| b'a' + rng.below(4) as u8).collect()
+    }
+
+    #[test]
+    fn test_tries_match_btree_map() {
+        let mut rng = XorShift64::new(0x7E1E);
+        let mut trie = Trie::new();
+        let mut radix = RadixTree::new();
+        let mut ternary = TernarySearchTree::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..3000 {
+            let key = random_word(&mut rng);
+            if rng.below(3) < 2 {
+                let old = expected.insert(key.clone(), step);
+                assert_eq!(trie.insert(&key, step), old);
+                assert_eq!(radix.insert(&key, step), old);
+                assert_eq!(ternary.insert(&key, step), old);
+            } else {
+                let old = expected.remove(&key);
+                assert_eq!(trie.remove(&key), old);
+                assert_eq!(radix.remove(&key), old);
+                assert_eq!(ternary.remove(&key), old);
+            }
+
+            let probe = random_word(&mut rng);
+            let exact = expected.get(&probe);
+            assert_eq!((trie.get(&probe), radix.get(&probe), ternary.get(&probe)), (exact, exact, exact));
+            let longest = (0..=probe.len()).rev().find_map(|n| expected.get(&probe[..n]).map(|value| (n, value)));
+            assert_eq!(trie.longest_prefix(&probe), longest);
+            assert_eq!(radix.longest_prefix(&probe), longest);
+            assert_eq!(ternary.longest_prefix(&probe), longest);
+            let prefixed: Vec<(Vec<u8>, &usize)> = expected
+                .iter()
+                .filter(|(key, _)| key.starts_with(&probe))
+                .map(|(key, value)| (key.clone(), value))
+                .collect();
+            assert_eq!(trie.starts_with(&probe).collect::<Vec<_>>(), prefixed);
+            assert_eq!(radix.starts_with(&probe).collect::<Vec<_>>(), prefixed);
+            assert_eq!(ternary.starts_with(&probe).collect::<Vec<_>>(), prefixed);
+        }
+        assert_eq!((trie.len(), radix.len(), ternary.len()), (expected.len(), expected.len(), expected.len()));
+    }
+
+    #[test]
+    fn test_memory_usage_reflects_structure() {
+        let words = ["compute", "computer", "computation", "commute", "compile", "complete", "zebra"];
+        let trie: Trie<()> = words.iter().map(|word| (word, ())).collect();
+        let radix: RadixTree<()> = words.iter().map(|word| (word, ())).collect();
+        let ternary: TernarySearchTree<()> = words.iter().map(|word| (word, ())).collect();
+        let (trie, radix, ternary) = (trie.memory_usage(), radix.memory_usage(), ternary.memory_usage());
+        // One node per distinct non-empty prefix, plus the root
+        assert_eq!(trie.nodes, 30);
+        assert!(radix.nodes < 2 * words.len());
+        // The same nodes, but without a root
+        assert_eq!(ternary.nodes, trie.nodes - 1);
+        assert!(radix.bytes < trie.bytes);
+        assert_eq!(MemoryUsage { nodes: 3, bytes: 96 }.to_string(), "3 nodes, 96 bytes");
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_prefix_trees() {
+        const WORDS: usize = 100_000;
+        let mut rng = XorShift64::new(0xB0B);
+        // Word lengths 4..16 over 26 letters: long sparse tails below shared stems
+        let words: Vec<Vec<u8>> =
+            (0..WORDS).map(|_| (0..4 + rng.below(12)).map(|_| b'a' + rng.below(26) as u8).collect()).collect();
+
+        let start = Instant::now();
+        let trie: Trie<usize> = words.iter().enumerate().map(|(i, word)| (word, i)).collect();
+        let build = start.elapsed();
+        let start = Instant::now();
+        let hits = words.iter().filter(|word| trie.get(word).is_some()).count();
+        let lookup = start.elapsed();
+        println!("trie      build {build:>12?}  lookup {lookup:>12?}  {} ({hits} hits)", trie.memory_usage());
+
+        let start = Instant::now();
+        let radix: RadixTree<usize> = words.iter().enumerate().map(|(i, word)| (word, i)).collect();
+        let build = start.elapsed();
+        let start = Instant::now();
+        let hits = words.iter().filter(|word| radix.get(word).is_some()).count();
+        let lookup = start.elapsed();
+        println!("radix     build {build:>12?}  lookup {lookup:>12?}  {} ({hits} hits)", radix.memory_usage());
+
+        let start = Instant::now();
+        let ternary: TernarySearchTree<usize> = words.iter().enumerate().map(|(i, word)| (word, i)).collect();
+        let build = start.elapsed();
+        let start = Instant::now();
+        let hits = words.iter().filter(|word| ternary.get(word).is_some()).count();
+        let lookup = start.elapsed();
+        println!("ternary   build {build:>12?}  lookup {lookup:>12?}  {} ({hits} hits)", ternary.memory_usage());
+    }
+}
+
+/// How much memory a prefix tree occupies: its node count and the bytes
+/// taken by the structure itself, excluding whatever the values own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub nodes: usize,
+    pub bytes: usize,
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} nodes, {} bytes", self.nodes, self.bytes)
+    }
+}
@@ -0,0 +1,381 @@
+use super::MemoryUsage;
+use std::fmt;
+use std::mem;
+use std::slice;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_splits_edges() {
+        let mut tree = RadixTree::new();
+        tree.insert("romane", 1);
+        assert_eq!(tree.memory_usage().nodes, 2);
+        tree.insert("romanus", 2);
+        // "roman" is split off into its own node with "e" and "us" below it
+        assert_eq!(tree.memory_usage().nodes, 4);
+        tree.insert("roman", 3);
+        assert_eq!(tree.memory_usage().nodes, 4);
+        tree.insert("rom", 4);
+        assert_eq!(tree.memory_usage().nodes, 5);
+        assert_eq!(tree.insert("romane", 10), Some(1));
+        assert_eq!(
+            tree.iter().collect::<Vec<_>>(),
+            vec![(b"rom".to_vec(), &4), (b"roman".to_vec(), &3), (b"romane".to_vec(), &10), (b"romanus".to_vec(), &2)]
+        );
+        assert_eq!(tree.get("roma"), None);
+        assert_eq!(tree.get("romanes"), None);
+        assert_eq!(tree.get("roman"), Some(&3));
+    }
+
+    #[test]
+    fn test_remove_merges_edges() {
+        let mut tree: RadixTree<u8> = [("test", 1), ("team", 2), ("toast", 3)].into_iter().collect();
+        assert_eq!(tree.memory_usage().nodes, 6);
+        assert_eq!(tree.remove("te"), None);
+        assert_eq!(tree.remove("team"), Some(2));
+        // "te" + "st" merge back into a single "est" edge below "t"
+        assert_eq!(tree.memory_usage().nodes, 4);
+        assert_eq!(tree.remove("toast"), Some(3));
+        assert_eq!(tree.memory_usage().nodes, 2);
+        *tree.get_mut("test").unwrap() += 1;
+        assert_eq!(tree.remove("test"), Some(2));
+        assert_eq!((tree.len(), tree.memory_usage().nodes), (0, 1));
+    }
+
+    #[test]
+    fn test_starts_with_inside_an_edge() {
+        let words = ["interval", "internal", "internet", "into", "in"];
+        let tree: RadixTree<()> = words.into_iter().map(|k| (k, ())).collect();
+        let keys: Vec<Vec<u8>> = tree.starts_with("inter").map(|(key, _)| key).collect();
+        assert_eq!(keys, vec![b"internal".to_vec(), b"internet".to_vec(), b"interval".to_vec()]);
+        // "interv" ends part way along the "val" edge
+        assert_eq!(tree.starts_with("interv").count(), 1);
+        assert_eq!(tree.starts_with("intx").count(), 0);
+        assert_eq!(tree.starts_with("").count(), 5);
+    }
+
+    #[test]
+    fn test_deeply_nested_keys_without_recursion() {
+        // Every prefix of one long key is a key, so each gets a node one
+        // level below the last; inserting longest first splits the top edge
+        let long = vec![b'n'; 10_000];
+        let mut tree = RadixTree::new();
+        for len in (1..=long.len()).rev() {
+            tree.insert(&long[..len], len);
+        }
+        assert_eq!(tree.memory_usage().nodes, long.len() + 1);
+        assert_eq!(tree.remove(&long[..1]), Some(1));
+        assert_eq!(tree.remove(&long), Some(long.len()));
+        assert_eq!(tree.get(&long[..2]), Some(&2));
+    }
+
+    #[test]
+    fn test_longest_prefix_match() {
+        let mut tree = RadixTree::new();
+        tree.insert("10.0", "a");
+        tree.insert("10.0.0", "b");
+        assert_eq!(tree.longest_prefix("10.0.0.1"), Some((6, &"b")));
+        assert_eq!(tree.longest_prefix("10.0.1.1"), Some((4, &"a")));
+        assert_eq!(tree.longest_prefix("10."), None);
+    }
+}
+
+struct Node<V> {
+    // The edge label leading into this node; empty only for the root
+    label: Vec<u8>,
+    value: Option<V>,
+    // Sorted by the first byte of their labels, which are distinct
+    children: Vec<Node<V>>,
+}
+
+impl<V> Node<V> {
+    fn new(label: Vec<u8>, value: Option<V>) -> Self {
+        Node { label, value, children: Vec::new() }
+    }
+
+    fn child_index(&self, byte: u8) -> Result<usize, usize> {
+        self.children.binary_search_by_key(&byte, |child| child.label[0])
+    }
+
+    fn child(&self, byte: u8) -> Option<&Node<V>> {
+        self.child_index(byte).ok().map(|index| &self.children[index])
+    }
+
+    /// Folds the only child of a node without a value into it.
+    fn merge_only_child(&mut self) {
+        if self.value.is_none() && self.children.len() == 1 {
+            let mut child = self.children.pop().expect("one child");
+            self.label.extend_from_slice(&child.label);
+            self.value = child.value.take();
+            self.children = mem::take(&mut child.children);
+        }
+    }
+}
+
+impl<V> Drop for Node<V> {
+    fn drop(&mut self) {
+        // Detach descendants onto a stack; dropping them recursively could
+        // overflow the stack when many keys extend one another
+        let mut stack = mem::take(&mut self.children);
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+/// A compressed trie (Patricia trie) mapping byte strings to values.
+///
+/// Chains of nodes with a single child and no value are collapsed into one
+/// edge labelled with the whole byte run, so the tree has at most two nodes
+/// per key. Inserting a key that diverges part way along an edge splits the
+/// edge; removing a key merges a node left with one child back into it.
+pub struct RadixTree<V> {
+    root: Node<V>,
+    len: usize,
+}
+
+impl<V> RadixTree<V> {
+    pub fn new() -> Self {
+        RadixTree { root: Node::new(Vec::new(), None), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::new(Vec::new(), None);
+        self.len = 0;
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key: K, value: V) -> Option<V> {
+        let mut key = key.as_ref();
+        let mut node = &mut self.root;
+        while let Some(&first) = key.first() {
+            let index = match node.child_index(first) {
+                Ok(index) => index,
+                Err(index) => {
+                    node.children.insert(index, Node::new(key.to_vec(), Some(value)));
+                    self.len += 1;
+                    return None;
+                }
+            };
+            let child = &mut node.children[index];
+            let common = common_prefix(&child.label, key);
+            if common < child.label.len() {
+                // Split the edge: the shared part becomes a new node above the child
+                let suffix = child.label.split_off(common);
+                let mut lower = mem::replace(child, Node::new(suffix, None));
+                mem::swap(&mut lower.label, &mut child.label);
+                child.children.push(lower);
+            }
+            key = &key[common..];
+            node = child;
+        }
+        let old = node.value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&V> {
+        let mut key = key.as_ref();
+        let mut node = &self.root;
+        while let Some(&first) = key.first() {
+            node = node.child(first)?;
+            key = key.strip_prefix(node.label.as_slice())?;
+        }
+        node.value.as_ref()
+    }
+
+    pub fn get_mut<K: AsRef<[u8]>>(&mut self, key: K) -> Option<&mut V> {
+        let mut key = key.as_ref();
+        let mut node = &mut self.root;
+        while let Some(&first) = key.first() {
+            let index = node.child_index(first).ok()?;
+            node = &mut node.children[index];
+            key = key.strip_prefix(node.label.as_slice())?;
+        }
+        node.value.as_mut()
+    }
+
+    pub fn contains_key<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key`, dropping a node left without value or children and
+    /// merging one left with a single child into that child.
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Option<V> {
+        let mut key = key.as_ref();
+        let mut path = Vec::new();
+        let mut node = &self.root;
+        while let Some(&first) = key.first() {
+            let index = node.child_index(first).ok()?;
+            node = &node.children[index];
+            key = key.strip_prefix(node.label.as_slice())?;
+            path.push(index);
+        }
+        node.value.as_ref()?;
+        self.len -= 1;
+        let Some((&index, above)) = path.split_last() else {
+            return self.root.value.take();
+        };
+
+        // Only the node losing its value and its parent can be left bare;
+        // every node further up keeps its value or both its children
+        let mut parent = &mut self.root;
+        for &index in above {
+            parent = &mut parent.children[index];
+        }
+        let target = &mut parent.children[index];
+        let removed = target.value.take();
+        if target.children.is_empty() {
+            parent.children.remove(index);
+            if !above.is_empty() {
+                parent.merge_only_child();
+            }
+        } else {
+            target.merge_only_child();
+        }
+        removed
+    }
+
+    /// The longest stored key that is a prefix of `key`, as its length and
+    /// value.
+    pub fn longest_prefix<K: AsRef<[u8]>>(&self, key: K) -> Option<(usize, &V)> {
+        let key = key.as_ref();
+        let mut node = &self.root;
+        let mut depth = 0;
+        let mut best = node.value.as_ref().map(|value| (0, value));
+        while let Some(child) = key.get(depth).and_then(|&byte| node.child(byte)) {
+            if !key[depth..].starts_with(&child.label) {
+                break;
+            }
+            node = child;
+            depth += child.label.len();
+            if let Some(value) = &node.value {
+                best = Some((depth, value));
+            }
+        }
+        best
+    }
+
+    /// Iterates the entries whose keys start with `prefix`, in
+    /// lexicographic order. The prefix may end part way along an edge.
+    pub fn starts_with<K: AsRef<[u8]>>(&self, prefix: K) -> Iter<'_, V> {
+        let mut prefix = prefix.as_ref();
+        let mut key = Vec::new();
+        let mut node = &self.root;
+        while let Some(&first) = prefix.first() {
+            let Some(child) = node.child(first) else {
+                return Iter { key: Vec::new(), pending: None, stack: Vec::new() };
+            };
+            let common = common_prefix(&child.label, prefix);
+            if common < prefix.len() && common < child.label.len() {
+                return Iter { key: Vec::new(), pending: None, stack: Vec::new() };
+            }
+            key.extend_from_slice(&child.label);
+            prefix = &prefix[common..];
+            node = child;
+        }
+        Iter::new(key, node)
+    }
+
+    /// Iterates every entry in lexicographic key order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter::new(Vec::new(), &self.root)
+    }
+
+    /// The number of nodes and the bytes they and their edge labels occupy,
+    /// not counting heap memory owned by the values themselves.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        // The root is inline and every other node lives in its parent's vector
+        let mut usage = MemoryUsage { nodes: 0, bytes: mem::size_of::<Self>() };
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            usage.nodes += 1;
+            usage.bytes += node.label.capacity() + node.children.capacity() * mem::size_of::<Node<V>>();
+            stack.extend(&node.children);
+        }
+        usage
+    }
+}
+
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Entries of a [`RadixTree`] in lexicographic order, as owned keys.
+pub struct Iter<'a, V> {
+    key: Vec<u8>,
+    pending: Option<&'a V>,
+    // Children still to visit, with the key length at their parent
+    stack: Vec<(slice::Iter<'a, Node<V>>, usize)>,
+}
+
+impl<'a, V> Iter<'a, V> {
+    fn new(key: Vec<u8>, node: &'a Node<V>) -> Self {
+        let depth = key.len();
+        Iter { key, pending: node.value.as_ref(), stack: vec![(node.children.iter(), depth)] }
+    }
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<(Vec<u8>, &'a V)> {
+        if let Some(value) = self.pending.take() {
+            return Some((self.key.clone(), value));
+        }
+        loop {
+            let (children, depth) = self.stack.last_mut()?;
+            let depth = *depth;
+            let Some(child) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+            self.key.truncate(depth);
+            self.key.extend_from_slice(&child.label);
+            self.stack.push((child.children.iter(), self.key.len()));
+            if let Some(value) = &child.value {
+                return Some((self.key.clone(), value));
+            }
+        }
+    }
+}
+
+impl<V> Default for RadixTree<V> {
+    fn default() -> Self {
+        RadixTree::new()
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for RadixTree<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self.iter().map(|(key, value)| (String::from_utf8_lossy(&key).into_owned(), value));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<K: AsRef<[u8]>, V> Extend<(K, V)> for RadixTree<V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: AsRef<[u8]>, V> FromIterator<(K, V)> for RadixTree<V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = RadixTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
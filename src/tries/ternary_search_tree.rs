@@ -0,0 +1,434 @@
+use super::MemoryUsage;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut tree = TernarySearchTree::new();
+        for (i, word) in ["cat", "cap", "car", "cute", "at", ""].iter().enumerate() {
+            assert_eq!(tree.insert(word, i), None);
+        }
+        assert_eq!(tree.insert("car", 20), Some(2));
+        assert_eq!(tree.get("car"), Some(&20));
+        assert_eq!(tree.get("ca"), None);
+        assert_eq!(tree.get(""), Some(&5));
+        *tree.get_mut("cute").unwrap() *= 10;
+        assert_eq!(tree.remove("cute"), Some(30));
+        assert_eq!(tree.remove("cute"), None);
+        assert_eq!(tree.remove(""), Some(5));
+        assert_eq!(tree.len(), 4);
+        assert!(tree.contains_key("cap") && !tree.contains_key("cu"));
+    }
+
+    #[test]
+    fn test_remove_unlinks_nodes_with_two_siblings() {
+        let mut tree = TernarySearchTree::new();
+        // "m" splits between "d" and "t", so removing it must relink both
+        for word in ["m", "d", "t", "b", "f", "x"] {
+            tree.insert(word, ());
+        }
+        assert_eq!(tree.remove("m"), Some(()));
+        assert_eq!(tree.memory_usage().nodes, 5);
+        let keys: Vec<Vec<u8>> = tree.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, [b"b", b"d", b"f", b"t", b"x"].map(|k| k.to_vec()));
+        for word in ["b", "d", "f", "t", "x"] {
+            tree.remove(word);
+        }
+        assert_eq!(tree.memory_usage().nodes, 0);
+    }
+
+    #[test]
+    fn test_long_key_drops_without_recursion() {
+        let long = vec![b'y'; 200_000];
+        let mut tree = TernarySearchTree::new();
+        tree.insert(&long, 1);
+        tree.insert(&long[..1000], 2);
+        tree.insert("x", 0);
+        assert_eq!(tree.remove(&long), Some(1));
+        assert_eq!(tree.memory_usage().nodes, 1001);
+        assert_eq!(tree.remove(&long[..1000]), Some(2));
+        assert_eq!(tree.memory_usage().nodes, 1);
+        tree.insert(&long, 3);
+        assert_eq!(tree.get(&long), Some(&3));
+    }
+
+    #[test]
+    fn test_starts_with_is_lexicographic() {
+        let tree: TernarySearchTree<()> =
+            ["shell", "she", "sells", "sea", "shore", "by", "the"].into_iter().map(|k| (k, ())).collect();
+        let keys: Vec<Vec<u8>> = tree.starts_with("sh").map(|(key, _)| key).collect();
+        assert_eq!(keys, vec![b"she".to_vec(), b"shell".to_vec(), b"shore".to_vec()]);
+        let all: Vec<Vec<u8>> = tree.iter().map(|(key, _)| key).collect();
+        let mut sorted = all.clone();
+        sorted.sort();
+        assert_eq!(all, sorted);
+        assert_eq!(tree.starts_with("q").count(), 0);
+    }
+
+    #[test]
+    fn test_longest_prefix_match() {
+        let tree: TernarySearchTree<u8> = [("a", 1), ("abc", 3), ("abcde", 5)].into_iter().collect();
+        assert_eq!(tree.longest_prefix("abcd"), Some((3, &3)));
+        assert_eq!(tree.longest_prefix("abcdef"), Some((5, &5)));
+        assert_eq!(tree.longest_prefix("b"), None);
+    }
+}
+
+struct Node<V> {
+    byte: u8,
+    value: Option<V>,
+    // Keys whose byte at this depth is below, equal to and above `byte`
+    lo: Option<Box<Node<V>>>,
+    eq: Option<Box<Node<V>>>,
+    hi: Option<Box<Node<V>>>,
+}
+
+impl<V> Drop for Node<V> {
+    fn drop(&mut self) {
+        // Detach descendants onto a stack; dropping them recursively could
+        // overflow the stack for long keys
+        let mut stack = Vec::new();
+        stack.extend([self.lo.take(), self.eq.take(), self.hi.take()].into_iter().flatten());
+        while let Some(mut node) = stack.pop() {
+            stack.extend([node.lo.take(), node.eq.take(), node.hi.take()].into_iter().flatten());
+        }
+    }
+}
+
+/// A ternary search tree mapping byte strings to values.
+///
+/// Each node holds one byte and three links: `lo` and `hi` form a binary
+/// search tree over the bytes at one depth, while `eq` moves on to the next
+/// byte. Nodes carry no per-alphabet arrays, so the tree stays small for
+/// sparse key sets at the cost of a few extra comparisons per byte. The
+/// empty key is stored beside the tree.
+pub struct TernarySearchTree<V> {
+    root: Option<Box<Node<V>>>,
+    empty: Option<V>,
+    len: usize,
+}
+
+impl<V> TernarySearchTree<V> {
+    pub fn new() -> Self {
+        TernarySearchTree { root: None, empty: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.empty = None;
+        self.len = 0;
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key: K, value: V) -> Option<V> {
+        let key = key.as_ref();
+        if key.is_empty() {
+            let old = self.empty.replace(value);
+            self.len += usize::from(old.is_none());
+            return old;
+        }
+        let mut slot = &mut self.root;
+        let mut depth = 0;
+        loop {
+            let byte = key[depth];
+            let node =
+                slot.get_or_insert_with(|| Box::new(Node { byte, value: None, lo: None, eq: None, hi: None }));
+            slot = match byte.cmp(&node.byte) {
+                Ordering::Less => &mut node.lo,
+                Ordering::Greater => &mut node.hi,
+                Ordering::Equal if depth + 1 == key.len() => {
+                    let old = node.value.replace(value);
+                    self.len += usize::from(old.is_none());
+                    return old;
+                }
+                Ordering::Equal => {
+                    depth += 1;
+                    &mut node.eq
+                }
+            };
+        }
+    }
+
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&V> {
+        let key = key.as_ref();
+        if key.is_empty() {
+            return self.empty.as_ref();
+        }
+        self.find(key)?.value.as_ref()
+    }
+
+    pub fn get_mut<K: AsRef<[u8]>>(&mut self, key: K) -> Option<&mut V> {
+        let key = key.as_ref();
+        if key.is_empty() {
+            return self.empty.as_mut();
+        }
+        let mut node = self.root.as_deref_mut()?;
+        let mut depth = 0;
+        loop {
+            node = match key[depth].cmp(&node.byte) {
+                Ordering::Less => node.lo.as_deref_mut()?,
+                Ordering::Greater => node.hi.as_deref_mut()?,
+                Ordering::Equal if depth + 1 == key.len() => return node.value.as_mut(),
+                Ordering::Equal => {
+                    depth += 1;
+                    node.eq.as_deref_mut()?
+                }
+            };
+        }
+    }
+
+    pub fn contains_key<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key`, unlinking every node left without a value or `eq`
+    /// subtree on the way back up.
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Option<V> {
+        let key = key.as_ref();
+        if key.is_empty() {
+            let removed = self.empty.take()?;
+            self.len -= 1;
+            return Some(removed);
+        }
+
+        // Walk down once, noting each move and what the node it leaves holds
+        let mut path = Vec::new();
+        let mut node = self.root.as_deref()?;
+        let mut depth = 0;
+        loop {
+            let step = key[depth].cmp(&node.byte);
+            let has_siblings = node.lo.is_some() || node.hi.is_some();
+            path.push(Step { step, has_value: node.value.is_some(), has_siblings });
+            node = match step {
+                Ordering::Less => node.lo.as_deref()?,
+                Ordering::Greater => node.hi.as_deref()?,
+                Ordering::Equal if depth + 1 == key.len() => break,
+                Ordering::Equal => {
+                    depth += 1;
+                    node.eq.as_deref()?
+                }
+            };
+        }
+        node.value.as_ref()?;
+        self.len -= 1;
+
+        // Without an `eq` subtree the target goes, and so does each parent
+        // whose only remaining purpose was to lead to it through `eq`
+        let mut cut = path.len() - 1;
+        let prune = node.eq.is_none();
+        while prune && cut > 0 {
+            let parent = &path[cut - 1];
+            if parent.step != Ordering::Equal || parent.has_value || path[cut].has_siblings {
+                break;
+            }
+            cut -= 1;
+        }
+        let mut slot = &mut self.root;
+        for step in &path[..cut] {
+            let node = slot.as_mut().expect("path was just walked");
+            slot = match step.step {
+                Ordering::Less => &mut node.lo,
+                Ordering::Greater => &mut node.hi,
+                Ordering::Equal => &mut node.eq,
+            };
+        }
+        if !prune {
+            return slot.as_mut().expect("path was just walked").value.take();
+        }
+        let mut branch = slot.take().expect("path was just walked");
+        *slot = unlink(&mut branch);
+        // The branch is now an `eq` chain ending at the target
+        let mut last = &mut branch;
+        while let Some(next) = last.eq.as_mut() {
+            last = next;
+        }
+        last.value.take()
+    }
+
+    /// The longest stored key that is a prefix of `key`, as its length and
+    /// value.
+    pub fn longest_prefix<K: AsRef<[u8]>>(&self, key: K) -> Option<(usize, &V)> {
+        let key = key.as_ref();
+        let mut best = self.empty.as_ref().map(|value| (0, value));
+        let mut link = self.root.as_deref();
+        let mut depth = 0;
+        while let (Some(node), Some(&byte)) = (link, key.get(depth)) {
+            link = match byte.cmp(&node.byte) {
+                Ordering::Less => node.lo.as_deref(),
+                Ordering::Greater => node.hi.as_deref(),
+                Ordering::Equal => {
+                    depth += 1;
+                    if let Some(value) = &node.value {
+                        best = Some((depth, value));
+                    }
+                    node.eq.as_deref()
+                }
+            };
+        }
+        best
+    }
+
+    /// Iterates the entries whose keys start with `prefix`, in
+    /// lexicographic order.
+    pub fn starts_with<K: AsRef<[u8]>>(&self, prefix: K) -> Iter<'_, V> {
+        let prefix = prefix.as_ref();
+        if prefix.is_empty() {
+            return self.iter();
+        }
+        match self.find(prefix) {
+            Some(node) => Iter {
+                key: prefix.to_vec(),
+                pending: node.value.as_ref(),
+                stack: node.eq.as_deref().map(|eq| Frame::Visit(eq, prefix.len())).into_iter().collect(),
+            },
+            None => Iter { key: Vec::new(), pending: None, stack: Vec::new() },
+        }
+    }
+
+    /// Iterates every entry in lexicographic key order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            key: Vec::new(),
+            pending: self.empty.as_ref(),
+            stack: self.root.as_deref().map(|root| Frame::Visit(root, 0)).into_iter().collect(),
+        }
+    }
+
+    /// The number of nodes and the bytes they occupy, not counting heap
+    /// memory owned by the values themselves.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage { nodes: 0, bytes: mem::size_of::<Self>() };
+        let mut stack: Vec<&Node<V>> = self.root.as_deref().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            usage.nodes += 1;
+            usage.bytes += mem::size_of::<Node<V>>();
+            stack.extend([&node.lo, &node.eq, &node.hi].into_iter().filter_map(|link| link.as_deref()));
+        }
+        usage
+    }
+
+    /// The node holding the last byte of the non-empty `key`.
+    fn find(&self, key: &[u8]) -> Option<&Node<V>> {
+        let mut node = self.root.as_deref()?;
+        let mut depth = 0;
+        loop {
+            node = match key[depth].cmp(&node.byte) {
+                Ordering::Less => node.lo.as_deref()?,
+                Ordering::Greater => node.hi.as_deref()?,
+                Ordering::Equal if depth + 1 == key.len() => return Some(node),
+                Ordering::Equal => {
+                    depth += 1;
+                    node.eq.as_deref()?
+                }
+            };
+        }
+    }
+}
+
+/// Detaches `node`'s `lo` and `hi` subtrees and joins them into the one
+/// subtree that takes its place.
+fn unlink<V>(node: &mut Node<V>) -> Option<Box<Node<V>>> {
+    match (node.lo.take(), node.hi.take()) {
+        (None, sibling) | (sibling, None) => sibling,
+        (Some(lo), Some(mut hi)) => {
+            // Every byte in `lo` is below every byte in `hi`, so `lo`
+            // hangs off the leftmost node of `hi`
+            let mut leftmost = &mut hi.lo;
+            while let Some(node) = leftmost {
+                leftmost = &mut node.lo;
+            }
+            *leftmost = Some(lo);
+            Some(hi)
+        }
+    }
+}
+
+// One move of a descent, with what the node it left held
+struct Step {
+    step: Ordering,
+    has_value: bool,
+    has_siblings: bool,
+}
+
+enum Frame<'a, V> {
+    // Walk a whole lo/eq/hi subtree whose nodes sit at key length `depth`
+    Visit(&'a Node<V>, usize),
+    // Append the node's byte to the key, then report it and walk `eq`
+    Descend(&'a Node<V>, usize),
+}
+
+/// Entries of a [`TernarySearchTree`] in lexicographic order, as owned keys.
+pub struct Iter<'a, V> {
+    key: Vec<u8>,
+    pending: Option<&'a V>,
+    stack: Vec<Frame<'a, V>>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<(Vec<u8>, &'a V)> {
+        if let Some(value) = self.pending.take() {
+            return Some((self.key.clone(), value));
+        }
+        loop {
+            match self.stack.pop()? {
+                Frame::Visit(node, depth) => {
+                    // Pushed in reverse so `lo` comes out first
+                    self.stack.extend(node.hi.as_deref().map(|hi| Frame::Visit(hi, depth)));
+                    self.stack.push(Frame::Descend(node, depth));
+                    self.stack.extend(node.lo.as_deref().map(|lo| Frame::Visit(lo, depth)));
+                }
+                Frame::Descend(node, depth) => {
+                    self.key.truncate(depth);
+                    self.key.push(node.byte);
+                    self.stack.extend(node.eq.as_deref().map(|eq| Frame::Visit(eq, depth + 1)));
+                    if let Some(value) = &node.value {
+                        return Some((self.key.clone(), value));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<V> Default for TernarySearchTree<V> {
+    fn default() -> Self {
+        TernarySearchTree::new()
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for TernarySearchTree<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self.iter().map(|(key, value)| (String::from_utf8_lossy(&key).into_owned(), value));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<K: AsRef<[u8]>, V> Extend<(K, V)> for TernarySearchTree<V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: AsRef<[u8]>, V> FromIterator<(K, V)> for TernarySearchTree<V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = TernarySearchTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
@@ -0,0 +1,341 @@
+use super::MemoryUsage;
+use std::fmt;
+use std::mem;
+use std::slice;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut trie = Trie::new();
+        assert_eq!(trie.insert("tea", 1), None);
+        assert_eq!(trie.insert("ten", 2), None);
+        assert_eq!(trie.insert("te", 3), None);
+        assert_eq!(trie.insert("tea", 10), Some(1));
+        assert_eq!(trie.get("tea"), Some(&10));
+        assert_eq!(trie.get("t"), None);
+        assert_eq!(trie.get(b"ten".as_slice()), Some(&2));
+        *trie.get_mut("te").unwrap() += 1;
+        assert_eq!(trie.remove("te"), Some(4));
+        assert_eq!(trie.remove("te"), None);
+        assert!(trie.contains_key("tea") && !trie.contains_key("te"));
+        assert_eq!(trie.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_prunes_dead_branches() {
+        let mut trie = Trie::new();
+        trie.insert("car", ());
+        let before = trie.memory_usage().nodes;
+        trie.insert("cartography", ());
+        trie.remove("cartography");
+        assert_eq!(trie.memory_usage().nodes, before);
+        trie.remove("car");
+        assert_eq!(trie.memory_usage().nodes, 1);
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn test_long_key_drops_without_recursion() {
+        let long = vec![b'x'; 200_000];
+        let mut trie = Trie::new();
+        trie.insert(&long, 1);
+        trie.insert(&long[..1000], 2);
+        assert_eq!(trie.remove(&long), Some(1));
+        assert_eq!(trie.memory_usage().nodes, 1001);
+        trie.insert(&long, 3);
+        assert_eq!(trie.get(&long), Some(&3));
+        trie.insert("", 0);
+        assert_eq!((trie.remove(""), trie.len()), (Some(0), 2));
+    }
+
+    #[test]
+    fn test_starts_with_is_lexicographic() {
+        let trie: Trie<usize> = ["banana", "band", "ban", "apple", "bandana", "bank"]
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (*word, i))
+            .collect();
+        let words: Vec<Vec<u8>> = trie.starts_with("ban").map(|(key, _)| key).collect();
+        assert_eq!(words, [&b"ban"[..], b"banana", b"band", b"bandana", b"bank"].map(|w| w.to_vec()));
+        assert_eq!(trie.starts_with("c").count(), 0);
+        assert_eq!(trie.starts_with("").count(), 6);
+        assert_eq!(trie.iter().next(), Some((b"apple".to_vec(), &3)));
+    }
+
+    #[test]
+    fn test_longest_prefix_match() {
+        let mut trie = Trie::new();
+        trie.insert("/", "root");
+        trie.insert("/api", "api");
+        trie.insert("/api/users", "users");
+        assert_eq!(trie.longest_prefix("/api/users/7"), Some((10, &"users")));
+        assert_eq!(trie.longest_prefix("/api/orders"), Some((4, &"api")));
+        assert_eq!(trie.longest_prefix("/static"), Some((1, &"root")));
+        assert_eq!(trie.longest_prefix("static"), None);
+        trie.insert("", "empty");
+        assert_eq!(trie.longest_prefix("static"), Some((0, &"empty")));
+    }
+}
+
+struct Node<V> {
+    value: Option<V>,
+    // Sorted by byte, so walking the children in order is lexicographic
+    children: Vec<(u8, Node<V>)>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Node { value: None, children: Vec::new() }
+    }
+
+    fn child(&self, byte: u8) -> Option<&Node<V>> {
+        let index = self.children.binary_search_by_key(&byte, |&(b, _)| b).ok()?;
+        Some(&self.children[index].1)
+    }
+
+    fn child_mut(&mut self, byte: u8) -> Option<&mut Node<V>> {
+        let index = self.children.binary_search_by_key(&byte, |&(b, _)| b).ok()?;
+        Some(&mut self.children[index].1)
+    }
+}
+
+impl<V> Drop for Node<V> {
+    fn drop(&mut self) {
+        // Detach descendants onto a stack; dropping them recursively could
+        // overflow the stack for long keys
+        let mut stack = mem::take(&mut self.children);
+        while let Some((_, mut node)) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+/// A map from byte strings to values with one node per key byte.
+///
+/// Keys are anything `AsRef<[u8]>`, so `&str`, `&[u8]` and `Vec<u8>` all
+/// work, and are handed back as `Vec<u8>`. Lookups cost O(key length)
+/// regardless of how many keys are stored; each node keeps its children in
+/// a sorted vector, which is compact but makes insertion O(alphabet) per
+/// byte.
+pub struct Trie<V> {
+    root: Node<V>,
+    len: usize,
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Self {
+        Trie { root: Node::new(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::new();
+        self.len = 0;
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key: K, value: V) -> Option<V> {
+        let mut node = &mut self.root;
+        for &byte in key.as_ref() {
+            let index = match node.children.binary_search_by_key(&byte, |&(b, _)| b) {
+                Ok(index) => index,
+                Err(index) => {
+                    node.children.insert(index, (byte, Node::new()));
+                    index
+                }
+            };
+            node = &mut node.children[index].1;
+        }
+        let old = node.value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&V> {
+        self.find(key.as_ref())?.value.as_ref()
+    }
+
+    pub fn get_mut<K: AsRef<[u8]>>(&mut self, key: K) -> Option<&mut V> {
+        self.find_mut(key.as_ref())?.value.as_mut()
+    }
+
+    pub fn contains_key<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key`, pruning the branch that only led to it.
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Option<V> {
+        let key = key.as_ref();
+        // The deepest node on the path that must stay: the root, a node with
+        // a value of its own or one that branches
+        let mut keep = 0;
+        let mut node = &self.root;
+        for (depth, &byte) in key.iter().enumerate() {
+            if node.value.is_some() || node.children.len() > 1 {
+                keep = depth;
+            }
+            node = node.child(byte)?;
+        }
+        node.value.as_ref()?;
+        self.len -= 1;
+        if key.is_empty() || !node.children.is_empty() {
+            return self.find_mut(key)?.value.take();
+        }
+
+        // Everything below `keep` only leads to `key`, so cut it off whole
+        let mut node = &mut self.root;
+        for &byte in &key[..keep] {
+            node = node.child_mut(byte).expect("path was just walked");
+        }
+        let index = node.children.binary_search_by_key(&key[keep], |&(b, _)| b).expect("path was just walked");
+        let (_, mut branch) = node.children.remove(index);
+        let mut last = &mut branch;
+        while let Some((_, child)) = last.children.first_mut() {
+            last = child;
+        }
+        last.value.take()
+    }
+
+    /// The longest stored key that is a prefix of `key`, as its length and
+    /// value.
+    pub fn longest_prefix<K: AsRef<[u8]>>(&self, key: K) -> Option<(usize, &V)> {
+        let mut node = &self.root;
+        let mut best = node.value.as_ref().map(|value| (0, value));
+        for (depth, &byte) in key.as_ref().iter().enumerate() {
+            match node.child(byte) {
+                Some(child) => node = child,
+                None => break,
+            }
+            if let Some(value) = &node.value {
+                best = Some((depth + 1, value));
+            }
+        }
+        best
+    }
+
+    /// Iterates the entries whose keys start with `prefix`, in
+    /// lexicographic order.
+    pub fn starts_with<K: AsRef<[u8]>>(&self, prefix: K) -> Iter<'_, V> {
+        let prefix = prefix.as_ref();
+        match self.find(prefix) {
+            Some(node) => Iter::new(prefix.to_vec(), node),
+            None => Iter { key: Vec::new(), pending: None, stack: Vec::new() },
+        }
+    }
+
+    /// Iterates every entry in lexicographic key order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter::new(Vec::new(), &self.root)
+    }
+
+    /// The number of nodes and the bytes they occupy, not counting heap
+    /// memory owned by the values themselves.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        // The root is inline and every other node lives in its parent's vector
+        let mut usage = MemoryUsage { nodes: 0, bytes: mem::size_of::<Self>() };
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            usage.nodes += 1;
+            usage.bytes += node.children.capacity() * mem::size_of::<(u8, Node<V>)>();
+            stack.extend(node.children.iter().map(|(_, child)| child));
+        }
+        usage
+    }
+
+    fn find(&self, key: &[u8]) -> Option<&Node<V>> {
+        let mut node = &self.root;
+        for &byte in key {
+            node = node.child(byte)?;
+        }
+        Some(node)
+    }
+
+    fn find_mut(&mut self, key: &[u8]) -> Option<&mut Node<V>> {
+        let mut node = &mut self.root;
+        for &byte in key {
+            node = node.child_mut(byte)?;
+        }
+        Some(node)
+    }
+}
+
+type Children<'a, V> = slice::Iter<'a, (u8, Node<V>)>;
+
+/// Entries of a [`Trie`] in lexicographic order, as owned keys.
+pub struct Iter<'a, V> {
+    key: Vec<u8>,
+    pending: Option<&'a V>,
+    // Children still to visit, with the key length at their parent
+    stack: Vec<(Children<'a, V>, usize)>,
+}
+
+impl<'a, V> Iter<'a, V> {
+    fn new(key: Vec<u8>, node: &'a Node<V>) -> Self {
+        let depth = key.len();
+        Iter { key, pending: node.value.as_ref(), stack: vec![(node.children.iter(), depth)] }
+    }
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<(Vec<u8>, &'a V)> {
+        if let Some(value) = self.pending.take() {
+            return Some((self.key.clone(), value));
+        }
+        loop {
+            let (children, depth) = self.stack.last_mut()?;
+            let depth = *depth;
+            let Some((byte, child)) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+            self.key.truncate(depth);
+            self.key.push(*byte);
+            self.stack.push((child.children.iter(), depth + 1));
+            if let Some(value) = &child.value {
+                return Some((self.key.clone(), value));
+            }
+        }
+    }
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Trie::new()
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for Trie<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self.iter().map(|(key, value)| (String::from_utf8_lossy(&key).into_owned(), value));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<K: AsRef<[u8]>, V> Extend<(K, V)> for Trie<V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: AsRef<[u8]>, V> FromIterator<(K, V)> for Trie<V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut trie = Trie::new();
+        trie.extend(iter);
+        trie
+    }
+}